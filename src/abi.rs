@@ -0,0 +1,421 @@
+use alloy_dyn_abi::{DynSolValue, EventExt, FunctionExt, JsonAbiExt};
+use alloy_json_abi::{Error, Event, Function, JsonAbi};
+use alloy_primitives::{hex, Address, Selector, B256, U256};
+use color_eyre::{eyre::eyre, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::output::serialize_u256;
+use crate::rpc::Log;
+
+/// A decoded function call or event with its named parameters
+#[derive(Debug, Clone)]
+pub struct Decoded {
+    pub signature: String,
+    pub params: Vec<(String, DynSolValue)>,
+}
+
+/// Function selectors and event topics indexed across any number of ABIs.
+///
+/// Each entry remembers the contract address its ABI was loaded for (if
+/// known) so selector collisions can be resolved in favour of the ABI that
+/// belongs to the contract actually being called.
+#[derive(Debug, Default)]
+pub struct AbiRegistry {
+    functions: HashMap<Selector, Vec<(Option<Address>, Function)>>,
+    events: HashMap<B256, Vec<(Option<Address>, Event)>>,
+    errors: HashMap<Selector, Vec<(Option<Address>, Error)>>,
+}
+
+impl AbiRegistry {
+    /// Load every `*.json` ABI in a directory.
+    ///
+    /// Files may hold a bare ABI array or a build artifact with an `abi` key.
+    /// The contract address is taken from an artifact's `address` key, or
+    /// from the file name when it is an address (`0xA0b8...eB48.json`).
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .map_err(|e| eyre!("Failed to read ABI directory {}: {}", dir.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        // Sort so collisions without an address match resolve deterministically
+        paths.sort();
+
+        let mut registry = Self::default();
+        for path in paths {
+            registry.load_file(&path)?;
+        }
+        Ok(registry)
+    }
+
+    /// Load a single ABI file into the registry
+    pub fn load_file(&mut self, path: &Path) -> Result<()> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read ABI file {}: {}", path.display(), e))?;
+        let value: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| eyre!("Invalid JSON in {}: {}", path.display(), e))?;
+
+        let (abi_value, address) = match &value {
+            serde_json::Value::Object(obj) => (
+                obj.get("abi").cloned().ok_or_else(|| eyre!("No `abi` key in {}", path.display()))?,
+                obj.get("address").and_then(|v| v.as_str()).and_then(|s| s.parse().ok()),
+            ),
+            _ => (value.clone(), None),
+        };
+        let address = address.or_else(|| {
+            path.file_stem().and_then(|stem| stem.to_str()).and_then(|s| s.parse().ok())
+        });
+
+        let abi: JsonAbi = serde_json::from_value(abi_value)
+            .map_err(|e| eyre!("Invalid ABI in {}: {}", path.display(), e))?;
+        self.add_abi(&abi, address);
+        Ok(())
+    }
+
+    /// Index an ABI's functions and events, optionally tied to a contract address
+    pub fn add_abi(&mut self, abi: &JsonAbi, address: Option<Address>) {
+        for function in abi.functions() {
+            self.functions.entry(function.selector()).or_default().push((address, function.clone()));
+        }
+        for event in abi.events() {
+            self.events.entry(event.selector()).or_default().push((address, event.clone()));
+        }
+        for error in abi.errors() {
+            self.errors.entry(error.selector()).or_default().push((address, error.clone()));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.events.is_empty() && self.errors.is_empty()
+    }
+
+    /// Decode transaction calldata against any known function with a matching selector
+    pub fn decode_call(&self, to: Option<&Address>, input: &[u8]) -> Option<Decoded> {
+        if input.len() < 4 {
+            return None;
+        }
+        let selector = Selector::from_slice(&input[..4]);
+        let candidates = self.functions.get(&selector)?;
+
+        prefer_address(candidates, to).find_map(|function| {
+            let values = function.abi_decode_input(&input[4..], true).ok()?;
+            Some(Decoded {
+                signature: function.signature(),
+                params: function.inputs.iter().map(|p| p.name.clone()).zip(values).collect(),
+            })
+        })
+    }
+
+    /// Decode what a call returned, against the function its calldata
+    /// selects. Unnamed return values are numbered.
+    pub fn decode_output(&self, to: Option<&Address>, input: &[u8], output: &[u8]) -> Option<Decoded> {
+        if input.len() < 4 {
+            return None;
+        }
+        let selector = Selector::from_slice(&input[..4]);
+        let candidates = self.functions.get(&selector)?;
+
+        prefer_address(candidates, to).find_map(|function| {
+            let values = function.abi_decode_output(output, true).ok()?;
+            Some(Decoded {
+                signature: function.signature(),
+                params: function.outputs.iter().enumerate()
+                    .map(|(i, p)| if p.name.is_empty() { format!("ret{}", i) } else { p.name.clone() })
+                    .zip(values)
+                    .collect(),
+            })
+        })
+    }
+
+    /// Decode an event log against any known event with a matching topic0
+    pub fn decode_log(&self, log: &Log) -> Option<Decoded> {
+        let topic0 = log.topics.first()?;
+        let candidates = self.events.get(topic0)?;
+
+        prefer_address(candidates, Some(&log.address)).find_map(|event| {
+            let decoded = event.decode_log_parts(log.topics.iter().copied(), &log.data, true).ok()?;
+            let mut indexed = decoded.indexed.into_iter();
+            let mut body = decoded.body.into_iter();
+            let params = event.inputs.iter().map(|input| {
+                let value = if input.indexed { indexed.next() } else { body.next() };
+                value.map(|v| (input.name.clone(), v))
+            }).collect::<Option<Vec<_>>>()?;
+
+            Some(Decoded { signature: event.signature(), params })
+        })
+    }
+
+    /// Decode a custom Solidity error (`error Foo(uint256 x)`) from revert data
+    pub fn decode_error(&self, address: Option<&Address>, data: &[u8]) -> Option<Decoded> {
+        if data.len() < 4 {
+            return None;
+        }
+        let selector = Selector::from_slice(&data[..4]);
+        let candidates = self.errors.get(&selector)?;
+
+        prefer_address(candidates, address).find_map(|error| {
+            let values = error.abi_decode_input(&data[4..], true).ok()?;
+            Some(Decoded {
+                signature: error.signature(),
+                params: error.inputs.iter().map(|p| p.name.clone()).zip(values).collect(),
+            })
+        })
+    }
+
+    /// Explain revert data: custom errors from loaded ABIs first, then the
+    /// built-in `Error(string)` and `Panic(uint256)`, then raw hex
+    pub fn explain_revert(&self, address: Option<&Address>, data: &[u8]) -> String {
+        if data.is_empty() {
+            return "reverted without a reason".to_string();
+        }
+        if let Some(error) = self.decode_error(address, data) {
+            let params: Vec<_> = error.params.iter()
+                .map(|(name, value)| format!("{}: {}", name, format_sol_value(value)))
+                .collect();
+            let name = error.signature.split('(').next().unwrap_or_default().to_string();
+            return format!("{}({})", name, params.join(", "));
+        }
+        decode_standard_revert(data).unwrap_or_else(|| format!("0x{}", hex::encode(data)))
+    }
+}
+
+/// `Error(string)` selector used by `require`/`revert` with a message
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// `Panic(uint256)` selector used by failing asserts and arithmetic checks
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decode the compiler's built-in `Error(string)` and `Panic(uint256)` reverts
+pub fn decode_standard_revert(data: &[u8]) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+    let (selector, args) = data.split_at(4);
+    if selector == ERROR_STRING_SELECTOR {
+        let reason = alloy_dyn_abi::DynSolType::String.abi_decode(args).ok()?;
+        return Some(format!("Error({})", format_sol_value(&reason)));
+    }
+    if selector == PANIC_SELECTOR {
+        let code = alloy_dyn_abi::DynSolType::Uint(256).abi_decode(args).ok()?;
+        let code = code.as_uint()?.0;
+        let meaning = match u64::try_from(code).ok() {
+            Some(0x01) => "assertion failed",
+            Some(0x11) => "arithmetic overflow or underflow",
+            Some(0x12) => "division or modulo by zero",
+            Some(0x21) => "invalid enum value",
+            Some(0x31) => "pop on empty array",
+            Some(0x32) => "array index out of bounds",
+            Some(0x41) => "out of memory",
+            Some(0x51) => "call to uninitialized function",
+            // Including codes too large for a u64, which a contract can still revert with
+            _ => "unknown panic code",
+        };
+        return Some(format!("Panic(0x{:x}): {}", code, meaning));
+    }
+    None
+}
+
+/// `Transfer(address,address,uint256)` event topic shared by ERC-20 and ERC-721
+pub const TRANSFER_TOPIC: B256 = alloy_primitives::b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// An ERC-20 token transfer recognized from its log, no ABI required
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenTransfer {
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    #[serde(serialize_with = "serialize_u256")]
+    pub amount: U256,
+}
+
+/// Recognize an ERC-20 `Transfer` log. ERC-721 transfers share the topic
+/// but index the token id as a fourth topic, so they don't match.
+pub fn decode_erc20_transfer(log: &Log) -> Option<TokenTransfer> {
+    match log.topics.as_slice() {
+        [topic, from, to] if *topic == TRANSFER_TOPIC && log.data.len() == 32 => Some(TokenTransfer {
+            token: log.address,
+            from: Address::from_word(*from),
+            to: Address::from_word(*to),
+            amount: U256::from_be_slice(&log.data),
+        }),
+        _ => None,
+    }
+}
+
+/// Order ABI candidates so the one loaded for `address` is tried first
+fn prefer_address<'a, T>(
+    candidates: &'a [(Option<Address>, T)],
+    address: Option<&'a Address>,
+) -> impl Iterator<Item = &'a T> {
+    let matching = candidates.iter().filter(move |(a, _)| address.is_some() && a.as_ref() == address);
+    let others = candidates.iter().filter(move |(a, _)| address.is_none() || a.as_ref() != address);
+    matching.chain(others).map(|(_, item)| item)
+}
+
+/// Render a decoded ABI value for display
+pub fn format_sol_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Address(a) => a.to_checksum(None),
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("0x{}", hex::encode(&word[..*size])),
+        DynSolValue::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        DynSolValue::String(s) => format!("{:?}", s),
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) => {
+            format!("[{}]", items.iter().map(format_sol_value).collect::<Vec<_>>().join(", "))
+        }
+        DynSolValue::Tuple(items) => {
+            format!("({})", items.iter().map(format_sol_value).collect::<Vec<_>>().join(", "))
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes};
+
+    const ERC20_ABI: &str = r#"[
+        {"type":"function","name":"transfer","stateMutability":"nonpayable",
+         "inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],
+         "outputs":[{"name":"","type":"bool"}]},
+        {"type":"event","name":"Transfer","anonymous":false,
+         "inputs":[{"name":"from","type":"address","indexed":true},
+                   {"name":"to","type":"address","indexed":true},
+                   {"name":"value","type":"uint256","indexed":false}]}
+    ]"#;
+
+    // Same selector as ERC20 transfer, different parameter names
+    const OTHER_ABI: &str = r#"[
+        {"type":"function","name":"transfer","stateMutability":"nonpayable",
+         "inputs":[{"name":"recipient","type":"address"},{"name":"wad","type":"uint256"}],
+         "outputs":[]}
+    ]"#;
+
+    fn transfer_calldata() -> Vec<u8> {
+        hex::decode(concat!(
+            "a9059cbb",
+            "000000000000000000000000742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "00000000000000000000000000000000000000000000000000000000000003e8",
+        )).unwrap()
+    }
+
+    #[test]
+    fn test_decode_call() {
+        let mut registry = AbiRegistry::default();
+        registry.add_abi(&serde_json::from_str::<JsonAbi>(ERC20_ABI).unwrap(), None);
+
+        let decoded = registry.decode_call(None, &transfer_calldata()).unwrap();
+        assert_eq!(decoded.signature, "transfer(address,uint256)");
+        assert_eq!(decoded.params[0].0, "to");
+        assert_eq!(format_sol_value(&decoded.params[1].1), "1000");
+        assert!(registry.decode_call(None, &[0xde, 0xad, 0xbe, 0xef]).is_none());
+    }
+
+    #[test]
+    fn test_decode_output() {
+        let mut registry = AbiRegistry::default();
+        registry.add_abi(&serde_json::from_str::<JsonAbi>(ERC20_ABI).unwrap(), None);
+
+        let output = B256::with_last_byte(1);
+        let decoded = registry.decode_output(None, &transfer_calldata(), output.as_slice()).unwrap();
+        assert_eq!(decoded.params.len(), 1);
+        assert_eq!(decoded.params[0].0, "ret0");
+        assert_eq!(format_sol_value(&decoded.params[0].1), "true");
+        // Return data that doesn't fit the outputs isn't decoded
+        assert!(registry.decode_output(None, &transfer_calldata(), &[0x01]).is_none());
+    }
+
+    #[test]
+    fn test_selector_collision_prefers_matching_address() {
+        let token = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let mut registry = AbiRegistry::default();
+        registry.add_abi(&serde_json::from_str::<JsonAbi>(OTHER_ABI).unwrap(), None);
+        registry.add_abi(&serde_json::from_str::<JsonAbi>(ERC20_ABI).unwrap(), Some(token));
+
+        let decoded = registry.decode_call(Some(&token), &transfer_calldata()).unwrap();
+        assert_eq!(decoded.params[0].0, "to");
+
+        let decoded = registry.decode_call(None, &transfer_calldata()).unwrap();
+        assert_eq!(decoded.params[0].0, "recipient");
+    }
+
+    #[test]
+    fn test_explain_revert() {
+        let mut registry = AbiRegistry::default();
+        registry.add_abi(&serde_json::from_str::<JsonAbi>(r#"[
+            {"type":"error","name":"InsufficientBalance",
+             "inputs":[{"name":"available","type":"uint256"},{"name":"required","type":"uint256"}]}
+        ]"#).unwrap(), None);
+
+        // InsufficientBalance(1, 2)
+        let custom = hex::decode(concat!(
+            "cf479181",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+        )).unwrap();
+        assert_eq!(registry.explain_revert(None, &custom), "InsufficientBalance(available: 1, required: 2)");
+
+        // Error("nope")
+        let error_string = hex::decode(concat!(
+            "08c379a0",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000004",
+            "6e6f706500000000000000000000000000000000000000000000000000000000",
+        )).unwrap();
+        assert_eq!(registry.explain_revert(None, &error_string), "Error(\"nope\")");
+
+        // Panic(0x11)
+        let panic = hex::decode(concat!(
+            "4e487b71",
+            "0000000000000000000000000000000000000000000000000000000000000011",
+        )).unwrap();
+        assert_eq!(registry.explain_revert(None, &panic), "Panic(0x11): arithmetic overflow or underflow");
+
+        // A contract can revert with any Panic code, even one past u64
+        let huge_panic = [&PANIC_SELECTOR[..], &[0xff; 32]].concat();
+        assert_eq!(registry.explain_revert(None, &huge_panic), format!("Panic(0x{}): unknown panic code", "f".repeat(64)));
+
+        assert_eq!(registry.explain_revert(None, &[0xde, 0xad, 0xbe, 0xef]), "0xdeadbeef");
+        assert_eq!(registry.explain_revert(None, &[]), "reverted without a reason");
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let mut registry = AbiRegistry::default();
+        registry.add_abi(&serde_json::from_str::<JsonAbi>(ERC20_ABI).unwrap(), None);
+
+        let log = Log {
+            address: address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            topics: vec![
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse().unwrap(),
+                "0x000000000000000000000000742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f".parse().unwrap(),
+                "0x0000000000000000000000000000000000000000000000000000000000000001".parse().unwrap(),
+            ],
+            data: Bytes::from(hex::decode("00000000000000000000000000000000000000000000000000000000000003e8").unwrap()),
+            block_number: None,
+            transaction_hash: None,
+            log_index: None,
+        };
+
+        let decoded = registry.decode_log(&log).unwrap();
+        assert_eq!(decoded.signature, "Transfer(address,address,uint256)");
+        let names: Vec<_> = decoded.params.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["from", "to", "value"]);
+        assert_eq!(format_sol_value(&decoded.params[2].1), "1000");
+
+        let transfer = decode_erc20_transfer(&log).unwrap();
+        assert_eq!(transfer.from, address!("742d35Cc622C1E0532F7fd0e7c0e6f7D8F2B2B6f"));
+        assert_eq!(transfer.to, Address::with_last_byte(1));
+        assert_eq!(transfer.amount, U256::from(1000));
+
+        let mut nft = log.clone();
+        nft.topics.push(B256::with_last_byte(7));
+        nft.data = Bytes::new();
+        assert_eq!(decode_erc20_transfer(&nft), None);
+    }
+}
@@ -0,0 +1,119 @@
+use color_eyre::{eyre::eyre, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Layout of the cached structs. Entries live under `v{SCHEMA_VERSION}`, so
+/// bumping it whenever a cached struct gains, loses or changes a field
+/// makes older entries unreachable instead of read back with defaults.
+///
+/// - 2: blocks carry their withdrawals
+/// - 3: blocks carry blob gas, transactions their blob fields
+const SCHEMA_VERSION: u32 = 3;
+
+/// Immutable RPC results kept across runs, one JSON file per cache key.
+/// Entries never expire; callers only store data that can't change.
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Open (creating if needed) the cache for one chain under `root`, so
+    /// that block 100 of one network is never served for another
+    pub fn open(root: &Path, chain_id: u64) -> Result<Self> {
+        Self::create(versioned(root).join(chain_id.to_string()))
+    }
+
+    /// Open the cache of what was learned about each endpoint, which unlike
+    /// the per-chain caches is keyed by URL
+    pub fn endpoints(root: &Path) -> Result<Self> {
+        Self::create(versioned(root).join("endpoints"))
+    }
+
+    fn create(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| eyre!("Failed to create cache directory {}: {}", dir.display(), e))?;
+        Ok(Self { dir })
+    }
+
+    /// The cached value for `key`. Missing or unreadable entries are misses.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let bytes = std::fs::read(self.path(key)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Store `value` under `key`. A failed write only costs a refetch next
+    /// time, so it's reported rather than returned.
+    pub fn put<T: Serialize>(&self, key: &str, value: &T) {
+        if let Err(e) = self.write(key, value) {
+            eprintln!("⚠️  Failed to write cache entry {}: {}", key, e);
+        }
+    }
+
+    fn write<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        let path = self.path(key);
+        // Write then rename, so a concurrent reader never sees half a file
+        let partial = path.with_extension("json.partial");
+        std::fs::write(&partial, serde_json::to_vec(value)?)?;
+        std::fs::rename(&partial, &path)?;
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let file_name: String = key.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        self.dir.join(format!("{}.json", file_name))
+    }
+}
+
+/// The directory under `root` for the current schema version
+fn versioned(root: &Path) -> PathBuf {
+    root.join(format!("v{}", SCHEMA_VERSION))
+}
+
+/// What the last run learned about an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointRecord {
+    pub chain_id: u64,
+    /// The node's latest block at the time
+    pub head: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::Block;
+    use alloy_primitives::{Address, B256, U256};
+
+    #[test]
+    fn test_block_round_trip() {
+        let root = std::env::temp_dir().join(format!("eth_data_extractor_cache_{}", std::process::id()));
+        let cache = DiskCache::open(&root, 1).unwrap();
+        let block = Block {
+            number: U256::from(18_000_000),
+            hash: Some(B256::with_last_byte(1)),
+            timestamp: U256::from(1_693_066_895),
+            gas_used: U256::from(15_000_000),
+            gas_limit: U256::from(30_000_000),
+            base_fee_per_gas: Some(U256::from(12_000_000_000u64)),
+            transactions: vec![B256::with_last_byte(2)],
+            miner: Address::with_last_byte(3),
+            difficulty: U256::MAX,
+            size: U256::from(1234),
+            ..Default::default()
+        };
+
+        assert!(cache.get::<Block>("block_18000000").is_none());
+        cache.put("block_18000000", &block);
+        let cached: Block = cache.get("block_18000000").unwrap();
+        assert_eq!(cached.number, block.number);
+        assert_eq!(cached.difficulty, U256::MAX);
+        assert_eq!(cached.base_fee_per_gas, block.base_fee_per_gas);
+        assert_eq!(cached.transactions, block.transactions);
+        // Keys from user input can't escape the directory
+        assert!(cache.path("tx_../../etc").starts_with(&cache.dir));
+        assert_eq!(cache.dir, root.join(format!("v{}", SCHEMA_VERSION)).join("1"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,157 @@
+/// Exit status for errors that aren't classified by an `ExtractorError` or
+/// a `RethClientError`
+pub const EXIT_OTHER: i32 = 1;
+
+/// Failures of the tool itself that scripts can tell apart, each with a
+/// stable exit code. Errors are still passed around as `eyre::Report`;
+/// `main` downcasts to find the code.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ExtractorError {
+    /// A malformed argument: block number, hash, address, range
+    #[error("{0}")]
+    InvalidInput(String),
+    /// A range scan finished, but some of its items failed
+    #[error("{failed} of {attempted} items failed")]
+    PartialFailure { failed: usize, attempted: usize },
+    /// Stopped by Ctrl-C before the command finished
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+impl ExtractorError {
+    /// The process exit status for this error. These values are a public
+    /// contract and must never be renumbered.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ExtractorError::InvalidInput(_) => 2,
+            ExtractorError::PartialFailure { .. } => 6,
+            // 128 + SIGINT, as a shell reports a process killed by Ctrl-C
+            ExtractorError::Interrupted => 130,
+        }
+    }
+}
+
+/// What went wrong talking to the node. Every public `RethClient` method
+/// fails with one of these, so callers can match on the kind of failure
+/// instead of on its message; `main` turns it into an `eyre::Report` only
+/// at the command boundary.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RethClientError {
+    /// The node could not be reached, or the connection broke
+    #[error("{0}")]
+    Connection(String),
+    /// The node took longer than `--timeout` to answer
+    #[error("{0}")]
+    Timeout(String),
+    /// The node answered with a JSON-RPC error object
+    #[error("JSON-RPC error {code}: {message}")]
+    RpcError { code: i32, message: String },
+    /// The node's answer was not what the method should return
+    #[error("{0}")]
+    Parse(String),
+    /// The node has no such block, transaction, receipt or contract
+    #[error("{0}")]
+    NotFound(String),
+    /// A malformed argument to the client: block range, hash, address,
+    /// endpoint URL
+    #[error("{0}")]
+    InvalidInput(String),
+}
+
+impl RethClientError {
+    /// The process exit status for this error; a public contract like
+    /// `ExtractorError::exit_code`
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RethClientError::InvalidInput(_) => 2,
+            RethClientError::NotFound(_) => 3,
+            RethClientError::Connection(_) | RethClientError::Timeout(_) => 4,
+            RethClientError::RpcError { .. } | RethClientError::Parse(_) => 5,
+        }
+    }
+
+    /// Whether the node, rather than the request, is the problem, so that
+    /// another endpoint may do better
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, RethClientError::Connection(_) | RethClientError::Timeout(_))
+    }
+
+    /// Prefix the message with `context`, keeping the kind of failure (and
+    /// so the exit code)
+    pub fn context(self, context: impl std::fmt::Display) -> Self {
+        match self {
+            RethClientError::Connection(message) => RethClientError::Connection(format!("{}: {}", context, message)),
+            RethClientError::Timeout(message) => RethClientError::Timeout(format!("{}: {}", context, message)),
+            RethClientError::RpcError { code, message } => RethClientError::RpcError { code, message: format!("{}: {}", context, message) },
+            RethClientError::Parse(message) => RethClientError::Parse(format!("{}: {}", context, message)),
+            RethClientError::NotFound(message) => RethClientError::NotFound(format!("{}: {}", context, message)),
+            RethClientError::InvalidInput(message) => RethClientError::InvalidInput(format!("{}: {}", context, message)),
+        }
+    }
+}
+
+/// A field the node sent back in the wrong shape
+impl From<serde_json::Error> for RethClientError {
+    fn from(e: serde_json::Error) -> Self {
+        RethClientError::Parse(e.to_string())
+    }
+}
+
+/// A hash or address the node sent back that isn't valid hex
+impl From<alloy_primitives::hex::FromHexError> for RethClientError {
+    fn from(e: alloy_primitives::hex::FromHexError) -> Self {
+        RethClientError::Parse(e.to_string())
+    }
+}
+
+/// A quantity the node sent back that isn't a valid U256
+impl From<alloy_primitives::ruint::ParseError> for RethClientError {
+    fn from(e: alloy_primitives::ruint::ParseError) -> Self {
+        RethClientError::Parse(e.to_string())
+    }
+}
+
+/// A hex quantity the node sent back that doesn't fit
+impl From<std::num::ParseIntError> for RethClientError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        RethClientError::Parse(e.to_string())
+    }
+}
+
+/// Exit status for any error coming out of a command
+pub fn exit_code(report: &color_eyre::Report) -> i32 {
+    if let Some(error) = report.downcast_ref::<ExtractorError>() {
+        return error.exit_code();
+    }
+    report
+        .downcast_ref::<RethClientError>()
+        .map_or(EXIT_OTHER, RethClientError::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_eyre::eyre::{eyre, WrapErr};
+
+    #[test]
+    fn test_exit_code_survives_context() {
+        let report: color_eyre::Report = RethClientError::NotFound("Block 99 not found".into()).into();
+        assert_eq!(exit_code(&report), 3);
+        let wrapped = Err::<(), _>(report).wrap_err("block 99 failed").unwrap_err();
+        assert_eq!(exit_code(&wrapped), 3);
+        assert_eq!(exit_code(&ExtractorError::InvalidInput("bad".into()).into()), 2);
+        assert_eq!(exit_code(&eyre!("something else")), EXIT_OTHER);
+    }
+
+    #[test]
+    fn test_rpc_error_keeps_code() {
+        let error = RethClientError::RpcError { code: -32000, message: "header not found".into() };
+        assert_eq!(error.to_string(), "JSON-RPC error -32000: header not found");
+        assert_eq!(error.exit_code(), 5);
+        assert!(RethClientError::Timeout("Request timeout".into()).is_unreachable());
+        let error = error.context("block 99 failed");
+        assert_eq!(error.to_string(), "JSON-RPC error -32000: block 99 failed: header not found");
+        assert_eq!(error.exit_code(), 5);
+        assert_eq!(RethClientError::InvalidInput("bad".into()).exit_code(), 2);
+    }
+}
@@ -1,203 +1,3329 @@
 use color_eyre::Result;
-use comfy_table::{Table, presets::UTF8_FULL};
-use crate::rpc::RethClient;
+use comfy_table::{Cell, Table, presets::UTF8_FULL};
+use alloy_primitives::{hex, keccak256, Address, Bytes, Selector, B256, U256};
+use crate::abi::{decode_erc20_transfer, format_sol_value, AbiRegistry, Decoded, TokenTransfer, TRANSFER_TOPIC};
+use crate::error::{ExtractorError, RethClientError};
+use crate::price::PriceOracle;
+use crate::progress::Progress;
+use crate::selectors::{decode_with_signature, SelectorLookup};
+use crate::output::{apply_color, render, render_csv, serialize_opt_u256, serialize_u256, status_cell, OutputFormat};
+use crate::rpc::{
+    check_log_range, check_scan_range, sort_logs, Account, Block, BlockTag, ContractCreation, GAS_PER_BLOB, BlockGasSample, FeeHistory, GasStatistics, TxGasSample, CallFrame, CallOutcome, CallRequest, Log, LogFilter, LogSort, PoolTransaction, Receipt, RethClient, Transaction, TxPoolStatus, Withdrawal,
+};
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::Write;
+use chrono::Utc;
+use std::time::Duration;
 use crate::utils::{
-    format_wei_u256, format_b256_hash, format_eth_address, format_timestamp_u256,
-    format_gas_price, format_tx_status, format_number, time_ago, account_type,
-    calculate_gas_utilization
+    format_wei_u256, format_b256_hash, format_block_hash, format_address, format_eth_address, format_eth_address_full, format_timestamp_u256,
+    format_gas_price, format_tx_status, ETHER, format_tx_type, fee_breakdown, transaction_fee, format_number, format_number_u256, format_gas_u256, storage_slot_name, time_ago, account_type,
+    calculate_gas_utilization, known_genesis, known_code_hash, minimal_proxy_target, nonce_anomalies, format_gas, GasFormat,
+    BlockBound, ErrorPolicy, ErrorTally, format_units, percentile, histogram, sparkline, format_usd, format_duration
 };
 
+/// Most blocks `watch` processes per poll when it has fallen behind
+const MAX_WATCH_CATCHUP: u64 = 32;
+
+/// Accounts `account` looks up at once when given several
+const ACCOUNT_FETCH_CONCURRENCY: usize = 16;
+
+/// Transactions `diff` lists per side before summarizing the rest
+const DIFF_TX_LIMIT: usize = 10;
+
+/// Blocks `export` fetches and writes at a time; the file is flushed after
+/// each chunk, so an interrupted export keeps everything before it
+const EXPORT_CHUNK_BLOCKS: u64 = 100;
+
 pub struct BlockExplorer {
     client: RethClient,
+    abis: AbiRegistry,
+    gas_format: Option<GasFormat>,
+    error_policy: ErrorPolicy,
+    output: OutputFormat,
+    /// Set with `--fiat` to show USD values next to ETH amounts
+    prices: Option<PriceOracle>,
+    /// Names calls that no loaded ABI decodes
+    selectors: SelectorLookup,
+    /// Set with `--quiet` to leave out banners and progress messages
+    quiet: bool,
 }
 
 impl BlockExplorer {
-    pub async fn new(rpc_url: &str) -> Result<Self> {
-        let client = RethClient::new(rpc_url).await?;
-        Ok(Self { client })
+    pub fn new(
+        client: RethClient,
+        abis: AbiRegistry,
+        gas_format: Option<GasFormat>,
+        error_policy: ErrorPolicy,
+        output: OutputFormat,
+        prices: Option<PriceOracle>,
+        quiet: bool,
+    ) -> Self {
+        Self { client, abis, gas_format, error_policy, output, prices, selectors: SelectorLookup::default(), quiet }
+    }
+
+    /// Use `selectors` in place of the offline selector table
+    pub fn with_selectors(self, selectors: SelectorLookup) -> Self {
+        Self { selectors, ..self }
+    }
+
+    /// The client commands go through
+    pub fn client(&self) -> &RethClient {
+        &self.client
+    }
+
+    /// A section title, underlined
+    fn banner(&self, title: impl std::fmt::Display) {
+        if self.quiet {
+            return;
+        }
+        let title = title.to_string();
+        println!("{}", title);
+        println!("{}\n", "=".repeat(title.chars().count()));
+    }
+
+    /// An address from the command line, either hex or a `.eth` name
+    /// resolved through ENS
+    async fn resolve_address(&self, input: &str) -> Result<Address> {
+        if input.to_lowercase().ends_with(".eth") {
+            let address = self.client.resolve_ens(input).await?;
+            if !self.quiet {
+                eprintln!("🔗 {} resolves to {}", input, address);
+            }
+            return Ok(address);
+        }
+        input.parse()
+            .map_err(|e| ExtractorError::InvalidInput(format!("Invalid address {:?}: {}", input, e)).into())
+    }
+
+    /// Format an ETH amount, with its USD value when `--fiat` is on. A price
+    /// lookup failure only costs the annotation, and so does a chain whose
+    /// native currency the ETH price doesn't apply to.
+    async fn format_wei_fiat(&self, wei: &U256) -> String {
+        let eth = format_wei_u256(wei);
+        let Some(prices) = self.prices.as_ref().filter(|_| self.client.native_currency() == ETHER) else {
+            return eth;
+        };
+        match prices.eth_usd(&self.client).await {
+            Ok(price) => format!("{} (≈ {})", eth, format_usd(wei, price)),
+            Err(e) => {
+                eprintln!("⚠️  {}", e);
+                eth
+            }
+        }
+    }
+
+    /// Print `value` in the structured output format, if one was chosen.
+    /// Returns whether it did, in which case the table view is skipped.
+    fn emit_structured<T: Serialize>(&self, value: &T) -> Result<bool> {
+        if !self.structured() {
+            return Ok(false);
+        }
+        println!("{}", render(value, self.output)?);
+        Ok(true)
+    }
+
+    /// Print `rows` as CSV if that format was chosen, returning whether it
+    /// did. Commands supporting CSV call this before `emit_structured`.
+    fn emit_csv<T: Serialize + Default>(&self, rows: &[T]) -> Result<bool> {
+        if self.output != OutputFormat::Csv {
+            return Ok(false);
+        }
+        print!("{}", render_csv(rows)?);
+        Ok(true)
+    }
+
+    /// The block number a range bound stands for
+    async fn resolve_bound(&self, bound: BlockBound) -> Result<u64> {
+        match bound {
+            BlockBound::Number(number) => Ok(number),
+            BlockBound::Latest => Ok(self.client.get_latest_block_number().await?),
+        }
+    }
+
+    /// Whether a structured format replaces the tables
+    fn structured(&self) -> bool {
+        self.output != OutputFormat::Table
+    }
+
+    /// Format gas using `--gas-format` if given, otherwise the table's own default
+    fn format_gas(&self, gas: u64, default: GasFormat) -> String {
+        format_gas(gas, self.gas_format.unwrap_or(default))
+    }
+
+    fn format_gas_u256(&self, gas: &U256, default: GasFormat) -> String {
+        format_gas_u256(gas, self.gas_format.unwrap_or(default))
     }
     
-    pub async fn show_block(&self, block_id: &str) -> Result<()> {
-        println!("📦 Block Information");
-        println!("===================\n");
-        
-        let block = if block_id.starts_with("0x") {
+    /// Fetch a block, with transactions, by number, hash or tag
+    async fn fetch_block(&self, block_id: &str) -> Result<Block> {
+        if block_id.starts_with("0x") {
             // It's a hash
-            self.client.get_block_by_hash(block_id).await?
+            Ok(self.client.get_block_by_hash(block_id).await?)
+        } else if let Some(tag) = BlockTag::parse(block_id) {
+            Ok(self.client.get_block_by_tag(tag, true).await?)
+        } else {
+            Ok(self.client.get_block_by_number(parse_block_number(block_id)?).await?)
+        }
+    }
+
+    /// `block --raw-json`: the block with its transactions as the node
+    /// sent it
+    pub async fn show_raw_block(&self, block_id: &str) -> Result<()> {
+        let (method, param) = if block_id.starts_with("0x") {
+            ("eth_getBlockByHash", block_id.to_string())
+        } else if let Some(tag) = BlockTag::parse(block_id) {
+            ("eth_getBlockByNumber", tag.as_str().to_string())
+        } else {
+            ("eth_getBlockByNumber", format!("0x{:x}", parse_block_number(block_id)?))
+        };
+        self.show_raw_json(method, vec![param.into(), true.into()], &format!("Block {}", block_id)).await
+    }
+
+    /// `transaction --raw-json` and `receipt --raw-json`
+    pub async fn show_raw_tx_json(&self, tx_hash: &str, receipt: bool) -> Result<()> {
+        let (method, what) = if receipt {
+            ("eth_getTransactionReceipt", format!("Receipt for transaction {}", tx_hash))
         } else {
-            // It's a number
-            let block_number = block_id.parse::<u64>()
-                .map_err(|_| color_eyre::eyre::eyre!("Invalid block number"))?;
-            self.client.get_block_by_number(block_number).await?
+            ("eth_getTransactionByHash", format!("Transaction {}", tx_hash))
+        };
+        self.show_raw_json(method, vec![tx_hash.into()], &what).await
+    }
+
+    /// Print the node's answer pretty-printed but otherwise untouched,
+    /// without parsing it, to see fields the tool doesn't model or a
+    /// response it fails to read. A null answer is still `NotFound`.
+    async fn show_raw_json(&self, method: &str, params: Vec<serde_json::Value>, what: &str) -> Result<()> {
+        let result = self.client.raw_request(method, params).await?;
+        if result.is_null() {
+            return Err(RethClientError::NotFound(format!("{} not found", what)).into());
+        }
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        Ok(())
+    }
+
+    /// The number of the block with hash `hash`, for state queries that
+    /// take a number. A block that has been reorged out is rejected rather
+    /// than answered with the state of its replacement.
+    pub async fn resolve_block_hash(&self, hash: B256) -> Result<u64> {
+        let block = self.client.get_block_by_hash(&format!("{:?}", hash)).await?;
+        let number = block.number.saturating_to::<u64>();
+        let canonical = self.client.get_block_header_by_number(number).await?;
+        if canonical.hash != Some(hash) {
+            return Err(RethClientError::NotFound(format!("Block {:?} is no longer on the canonical chain", hash)).into());
+        }
+        Ok(number)
+    }
+
+    pub async fn show_block(&self, block_id: &str, options: &BlockOptions) -> Result<()> {
+        let BlockOptions { min_value, value_sum, show_logs_bloom, diff_parent, verbose, top_gas } = *options;
+        let block = self.fetch_block(block_id).await?;
+        let top_gas = match top_gas {
+            Some(count) => {
+                let receipts = self.client.get_receipts(block.number.saturating_to()).await?;
+                Some(top_gas_consumers(&receipts, block.gas_used, count))
+            }
+            None => None,
         };
+        if self.emit_structured(&BlockView { block: &block, top_gas: top_gas.as_deref() })? {
+            return Ok(());
+        }
+
+        self.banner("📦 Block Information");
+        println!("{}", block_table(&block, self.gas_format, self.client.chain_id()));
+
+        if verbose && !block.uncles.is_empty() {
+            self.show_uncles(&block).await;
+        }
+
+        if !block.withdrawals.is_empty() {
+            println!("\n🏦 Withdrawals:");
+            println!("{}", withdrawals_table(&block.withdrawals));
+            let total: U256 = block.withdrawals.iter().map(Withdrawal::amount_wei).sum();
+            println!("  Total withdrawn: {} across {} withdrawals", format_wei_u256(&total), block.withdrawals.len());
+        }
+
+        if diff_parent {
+            self.show_parent_diff(&block).await?;
+        }
+
+        if let Some(consumers) = &top_gas {
+            println!("\n⛽ Top Gas Consumers ({} of {} transactions):", consumers.len(), block.transactions.len());
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec!["Tx Hash", "From", "To", "Gas Used", "% of Block"]);
+            for consumer in consumers {
+                table.add_row(vec![
+                    format_b256_hash(&consumer.hash),
+                    format_eth_address(&consumer.from),
+                    consumer.to.as_ref().map(format_eth_address).unwrap_or_else(|| "📄 Contract Creation".to_string()),
+                    self.format_gas(consumer.gas_used, GasFormat::Raw),
+                    format!("{:.2}%", consumer.share_pct),
+                ]);
+            }
+            println!("{}", table);
+        }
+
+        if show_logs_bloom {
+            match &block.logs_bloom {
+                Some(bloom) => {
+                    let bits: u32 = bloom.iter().map(|byte| byte.count_ones()).sum();
+                    println!("\n🌸 Logs Bloom ({} of 2048 bits set):", bits);
+                    for row in bloom.chunks(32) {
+                        println!("  {}", alloy_primitives::hex::encode(row));
+                    }
+                }
+                None => println!("\n🌸 Logs Bloom: not reported by the node"),
+            }
+        }
+        
+        let shown: Vec<&Transaction> = block.full_transactions.iter()
+            .filter(|tx| min_value.is_none_or(|min| tx.value >= min))
+            .collect();
+
+        if !shown.is_empty() {
+            match min_value {
+                Some(min) => println!("\n🔗 Transactions of at least {}:", format_wei_u256(&min)),
+                None => println!("\n🔗 Recent Transactions:"),
+            }
+            let display_count = std::cmp::min(5, shown.len());
+            for (i, tx) in shown.iter().take(display_count).enumerate() {
+                println!("  {}. {}", i + 1, format_b256_hash(&tx.hash));
+            }
+            if shown.len() > 5 {
+                println!("  ... and {} more transactions", shown.len() - 5);
+            }
+        }
+
+        if value_sum {
+            let total = shown.iter()
+                .try_fold(U256::ZERO, |sum, tx| sum.checked_add(tx.value))
+                .ok_or_else(|| color_eyre::eyre::eyre!("Transaction value sum overflowed"))?;
+            let creations = shown.iter().filter(|tx| tx.to.is_none()).count();
+            let scope = match min_value {
+                Some(min) => format!("filtered to ≥ {}", format_wei_u256(&min)),
+                None => "all transactions".to_string(),
+            };
+            println!("\n💰 Total Value: {} across {} transactions ({})", format_wei_u256(&total), shown.len(), scope);
+            println!("🏗️  Contract Creations: {}", creations);
+        }
         
+        Ok(())
+    }
+    
+    /// What changed between a block and its parent
+    async fn show_parent_diff(&self, block: &Block) -> Result<()> {
+        if block.number.is_zero() {
+            println!("\n🔀 Genesis has no parent block");
+            return Ok(());
+        }
+        let parent = self.client.get_block_by_hash(&format!("{:?}", block.parent_hash)).await?;
+        let delta = BlockDelta::between(&parent, block);
+
+        println!("\n🔀 Changes Since Parent (Block {})", parent.number);
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
-        table.set_header(vec!["Property", "Value"]);
-        
-        table.add_row(vec!["Block Number", &block.number.to_string()]);
-        table.add_row(vec!["Block Hash", &format_b256_hash(&block.hash)]);
-        table.add_row(vec!["Parent Hash", &format_b256_hash(&block.parent_hash)]);
-        table.add_row(vec!["Timestamp", &format_timestamp_u256(&block.timestamp)]);
-        table.add_row(vec!["Time Ago", &time_ago(block.timestamp.to::<u64>())]);
-        table.add_row(vec!["Gas Used", &format_number(block.gas_used.to::<u64>())]);
-        table.add_row(vec!["Gas Limit", &format_number(block.gas_limit.to::<u64>())]);
-        table.add_row(vec!["Gas Utilization", &format!("{:.1}%", 
-            calculate_gas_utilization(block.gas_used.to::<u64>(), block.gas_limit.to::<u64>()))]);
-        table.add_row(vec!["Transactions", &block.transactions.len().to_string()]);
-        table.add_row(vec!["Miner", &format_eth_address(&block.miner)]);
-        table.add_row(vec!["Difficulty", &format_number(block.difficulty.to::<u64>())]);
-        table.add_row(vec!["Size", &format!("{} bytes", block.size.to::<u64>())]);
-        
+        table.set_header(vec!["Metric", "Parent", "This Block", "Change"]);
+
+        table.add_row(vec!["Time".to_string(), format_timestamp_u256(&parent.timestamp), format_timestamp_u256(&block.timestamp), format!("+{}s", delta.seconds)]);
+        if let (Some(parent_fee), Some(fee), Some(pct)) = (parent.base_fee_per_gas, block.base_fee_per_gas, delta.base_fee_pct) {
+            let expected = match delta.expected_base_fee {
+                Ordering::Greater => "parent above target, expected ↑",
+                Ordering::Less => "parent below target, expected ↓",
+                Ordering::Equal => "parent at target, expected =",
+            };
+            table.add_row(vec!["Base Fee".to_string(), format_gas_price(&parent_fee), format_gas_price(&fee), format!("{:+.2}% ({})", pct, expected)]);
+        }
+        table.add_row(vec![
+            "Gas Used".to_string(),
+            self.format_gas_u256(&parent.gas_used, GasFormat::Raw),
+            self.format_gas_u256(&block.gas_used, GasFormat::Raw),
+            format!("{:+}", delta.gas_used),
+        ]);
+        table.add_row(vec![
+            "Transactions".to_string(),
+            parent.transactions.len().to_string(),
+            block.transactions.len().to_string(),
+            format!("{:+}", delta.tx_count),
+        ]);
         println!("{}", table);
-        
-        if !block.transactions.is_empty() {
-            println!("\n🔗 Recent Transactions:");
-            let display_count = std::cmp::min(5, block.transactions.len());
-            for (i, tx_hash) in block.transactions.iter().take(display_count).enumerate() {
-                println!("  {}. {}", i + 1, format_b256_hash(tx_hash));
+        Ok(())
+    }
+
+    /// Compare two blocks field by field and by the transactions each one
+    /// has that the other lacks, e.g. the two sides of a reorg
+    pub async fn show_block_diff(&self, a: &str, b: &str) -> Result<()> {
+        let (block_a, block_b) = futures::try_join!(self.fetch_block(a), self.fetch_block(b))?;
+        let rows = diff_rows(&block_a, &block_b);
+        let (only_in_a, only_in_b) = tx_set_difference(&block_a, &block_b);
+        let view = BlockDiffView {
+            a: &block_a,
+            b: &block_b,
+            differing_fields: rows.iter().filter(|row| row.differs).map(|row| row.property).collect(),
+            only_in_a: &only_in_a,
+            only_in_b: &only_in_b,
+        };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        self.banner(format!("🔀 Block Diff ({} vs {})", a, b));
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Block A", "Block B"]);
+        for row in &rows {
+            let property = if row.differs { format!("≠ {}", row.property) } else { row.property.to_string() };
+            table.add_row(vec![property, row.a.clone(), row.b.clone()]);
+        }
+        println!("{}", table);
+        println!("≠ marks fields that differ");
+
+        for (label, hashes) in [("A", &only_in_a), ("B", &only_in_b)] {
+            println!("\n🔗 Transactions only in block {} ({}):", label, hashes.len());
+            for hash in hashes.iter().take(DIFF_TX_LIMIT) {
+                println!("  {}", format_b256_hash(hash));
             }
-            if block.transactions.len() > 5 {
-                println!("  ... and {} more transactions", block.transactions.len() - 5);
+            if hashes.len() > DIFF_TX_LIMIT {
+                println!("  ... and {} more", hashes.len() - DIFF_TX_LIMIT);
             }
         }
-        
+        Ok(())
+    }
+
+    pub async fn show_genesis(&self) -> Result<()> {
+        let block = self.client.get_block_by_number(0).await?;
+        let hash = block.hash.ok_or_else(|| color_eyre::eyre::eyre!("Genesis block has no hash"))?;
+        let network = known_genesis(&hash);
+        if self.emit_structured(&GenesisView { network, block: &block })? {
+            return Ok(());
+        }
+
+        self.banner("🌱 Genesis Block");
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Value"]);
+
+        table.add_row(vec!["Block Hash", &format!("{:?}", hash)]);
+        table.add_row(vec!["Timestamp", &format_timestamp_u256(&block.timestamp)]);
+        table.add_row(vec!["Gas Limit", &self.format_gas_u256(&block.gas_limit, GasFormat::Raw)]);
+        table.add_row(vec!["Extra Data", &block.extra_data.to_string()]);
+
+        println!("{}", table);
+
+        match network {
+            Some(network) => println!("\n✅ Genesis matches {}", network),
+            None => println!("\n❓ Unknown chain (genesis hash not in the known networks table)"),
+        }
         Ok(())
     }
     
-    pub async fn show_transaction(&self, tx_hash: &str) -> Result<()> {
-        println!("💸 Transaction Details");
-        println!("=====================\n");
-        
+    pub async fn show_transaction(&self, tx_hash: &str, explain_revert: bool, position_context: bool) -> Result<()> {
         let transaction = self.client.get_transaction(tx_hash).await?;
+        if self.emit_structured(&transaction)? {
+            return Ok(());
+        }
+
+        self.banner("💸 Transaction Details");
         
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
+        apply_color(&mut table);
         table.set_header(vec!["Property", "Value"]);
         
         table.add_row(vec!["Hash", &format_b256_hash(&transaction.hash)]);
         if let Some(block_num) = &transaction.block_number {
-            table.add_row(vec!["Block Number", &format_number(block_num.to::<u64>())]);
+            table.add_row(vec!["Block Number", &format_number(block_num.saturating_to::<u64>())]);
         }
-        table.add_row(vec!["From", &format_eth_address(&transaction.from)]);
+        table.add_row(vec!["From", &format_eth_address_full(&transaction.from)]);
         
         match &transaction.to {
             Some(to_addr) => {
-                table.add_row(vec!["To", &format_eth_address(to_addr)]);
+                table.add_row(vec!["To", &format_eth_address_full(to_addr)]);
             }
             None => {
                 table.add_row(vec!["To", "📄 Contract Creation"]);
             }
         }
         
-        table.add_row(vec!["Value", &format_wei_u256(&transaction.value)]);
-        table.add_row(vec!["Gas Limit", &format_number(transaction.gas.to::<u64>())]);
+        table.add_row(vec!["Value", &self.format_wei_fiat(&transaction.value).await]);
+        table.add_row(vec!["Gas Limit", &self.format_gas(transaction.gas.saturating_to::<u64>(), GasFormat::Raw)]);
         table.add_row(vec!["Gas Price", &format_gas_price(&transaction.gas_price)]);
+        if let Some(tx_type) = transaction.tx_type {
+            table.add_row(vec!["Type", &format_tx_type(tx_type)]);
+        }
+        if let Some(max_fee) = &transaction.max_fee_per_gas {
+            table.add_row(vec!["Max Fee", &format_gas_price(max_fee)]);
+        }
+        if let Some(max_priority_fee) = &transaction.max_priority_fee_per_gas {
+            table.add_row(vec!["Max Priority Fee", &format_gas_price(max_priority_fee)]);
+        }
+        if let Some(max_blob_fee) = &transaction.max_fee_per_blob_gas {
+            table.add_row(vec!["Max Fee per Blob Gas", &format_blob_gas_price(max_blob_fee)]);
+        }
+        if !transaction.blob_versioned_hashes.is_empty() {
+            table.add_row(vec!["Blobs", &transaction.blob_versioned_hashes.len().to_string()]);
+        }
         
         if let Some(gas_used) = &transaction.gas_used {
-            table.add_row(vec!["Gas Used", &format_number(gas_used.to::<u64>())]);
-            let tx_fee = transaction.gas_price * *gas_used;
-            table.add_row(vec!["Transaction Fee", &format_wei_u256(&tx_fee)]);
+            table.add_row(vec!["Gas Used", &self.format_gas(gas_used.saturating_to::<u64>(), GasFormat::Raw)]);
+            let tx_fee = match transaction_fee(transaction.gas_price, *gas_used) {
+                Some(tx_fee) => self.format_wei_fiat(&tx_fee).await,
+                None => "overflow".to_string(),
+            };
+            table.add_row(vec!["Transaction Fee", &tx_fee]);
+
+            // Split the fee using its block's base fee, once mined after London
+            let base_fee = match transaction.block_number {
+                Some(number) => self.client.get_block_header_by_number(number.saturating_to::<u64>()).await?.base_fee_per_gas,
+                None => None,
+            };
+            if let Some(base_fee) = base_fee {
+                let (burned, tip) = fee_breakdown(transaction.gas_price, *gas_used, base_fee);
+                table.add_row(vec!["Base Fee", &format_gas_price(&base_fee)]);
+                table.add_row(vec!["Burned", &self.format_wei_fiat(&burned).await]);
+                table.add_row(vec!["Priority Fee", &self.format_wei_fiat(&tip).await]);
+            }
         }
         
-        table.add_row(vec!["Status", &format_tx_status(&transaction.status)]);
+        table.add_row(vec![Cell::new("Status"), status_cell(format_tx_status(&transaction.status))]);
+
+        let failed = transaction.status.is_some_and(|s| s.is_zero());
+        if explain_revert && failed {
+            table.add_row(vec!["Revert Reason", &self.explain_revert(&transaction).await]);
+        }
+
+        table.add_row(vec!["Calldata", &format!("{} bytes", transaction.input.len())]);
+        let selector = transaction.to.and(transaction.input.get(..4)).map(Selector::from_slice);
+        if let Some(selector) = selector {
+            table.add_row(vec!["Selector", &format!("0x{}", hex::encode(selector))]);
+        }
+
+        if let Some((call, implementation)) = self.decode_call(&transaction).await {
+            table.add_row(vec!["Function", &call.signature]);
+            if let Some(implementation) = implementation {
+                table.add_row(vec!["Decoded Via", &format!("implementation {}", implementation)]);
+            }
+            for (name, value) in &call.params {
+                table.add_row(vec![&format!("  {}", name), &format_sol_value(value)]);
+            }
+        } else if let Some(selector) = selector {
+            // Without an ABI, fall back to naming the call by its selector
+            match self.selectors.signature(selector).await {
+                Some(signature) => match decode_with_signature(&signature, &transaction.input) {
+                    Some(call) => {
+                        table.add_row(vec!["Function", &format!("{} (by selector)", call.signature)]);
+                        for (name, value) in &call.params {
+                            table.add_row(vec![&format!("  {}", name), &format_sol_value(value)]);
+                        }
+                    }
+                    None => {
+                        table.add_row(vec!["Function", &format!("{} (by selector; arguments don't match)", signature)]);
+                    }
+                },
+                None => {
+                    table.add_row(vec!["Function", "unknown"]);
+                }
+            }
+        }
         
         println!("{}", table);
+
+        if !self.abis.is_empty() {
+            let decoded: Vec<_> = transaction.logs.iter()
+                .filter_map(|log| self.abis.decode_log(log).map(|d| (log, d)))
+                .collect();
+            if !decoded.is_empty() {
+                println!("\n📜 Decoded Events:");
+                for (log, event) in decoded {
+                    println!("  {} {} ({})", format_eth_address(&log.address), event.signature, format_decoded_params(&event));
+                }
+            }
+        }
+
+        if let Some(access_list) = &transaction.access_list {
+            let keys: usize = access_list.0.iter().map(|item| item.storage_keys.len()).sum();
+            println!("\n🔑 Access List ({} addresses, {} storage keys):", access_list.0.len(), keys);
+            for item in &access_list.0 {
+                println!("  {} ({} keys)", format_eth_address(&item.address), item.storage_keys.len());
+                for key in &item.storage_keys {
+                    println!("    {}", format_b256_hash(key));
+                }
+            }
+        }
+
+        if position_context {
+            self.show_position_context(&transaction).await?;
+        }
         Ok(())
     }
-    
-    pub async fn show_account(&self, address: &str, block: Option<u64>) -> Result<()> {
-        let block_str = block.map_or("latest".to_string(), |b| b.to_string());
-        println!("👤 Account Information (Block: {})", block_str);
-        println!("==================================\n");
-        
-        let account = self.client.get_account_balance(address, block).await?;
-        
+
+    /// Show the transactions immediately before and after this one in its
+    /// block, to see how it was ordered
+    async fn show_position_context(&self, transaction: &Transaction) -> Result<()> {
+        println!("\n🧭 Position in Block:");
+        let (Some(block_number), Some(index)) = (transaction.block_number, transaction.transaction_index) else {
+            println!("  unavailable (transaction not mined)");
+            return Ok(());
+        };
+        let index = index.saturating_to::<usize>();
+        let block = self.client.get_block_by_number(block_number.saturating_to::<u64>()).await?;
+
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
-        table.set_header(vec!["Property", "Value"]);
-        
-        table.add_row(vec!["Address", &format_eth_address(&account.address)]);
-        table.add_row(vec!["Balance", &format_wei_u256(&account.balance)]);
-        table.add_row(vec!["Nonce", &account.nonce.to_string()]);
-        table.add_row(vec!["Type", account_type(&account.code_size)]);
-        
-        if !account.code_size.is_zero() {
-            table.add_row(vec!["Code Size", &format!("{} bytes", account.code_size.to::<u64>())]);
+        table.set_header(vec!["Position", "Hash", "From", "Effective Gas Price"]);
+
+        let neighbors = [
+            ("Previous", index.checked_sub(1)),
+            ("This", Some(index)),
+            ("Next", Some(index + 1)),
+        ];
+        for (label, position) in neighbors {
+            match position.and_then(|i| block.full_transactions.get(i).map(|tx| (i, tx))) {
+                Some((i, tx)) => table.add_row(vec![
+                    &format!("{} (#{})", label, i),
+                    &format_b256_hash(&tx.hash),
+                    &format_eth_address(&tx.from),
+                    &format_gas_price(&tx.gas_price),
+                ]),
+                None => table.add_row(vec![label, "—", "—", "—"]),
+            };
         }
-        
+
         println!("{}", table);
         Ok(())
     }
     
-    pub async fn show_latest_blocks(&self, count: usize) -> Result<()> {
-        println!("📊 Latest {} Blocks", count);
-        println!("==================\n");
-        
-        let latest_block_num = self.client.get_latest_block_number().await?;
-        
+    /// Re-execute a mined transaction with `eth_call` on the state just
+    /// before its block, reporting whether it succeeds and what it returns
+    pub async fn show_replay(&self, tx_hash: &str) -> Result<()> {
+        let transaction = self.client.get_transaction(tx_hash).await?;
+        let block = transaction.block_number
+            .ok_or_else(|| color_eyre::eyre::eyre!("Transaction {} is not mined yet", tx_hash))?;
+        let parent = block.saturating_to::<u64>().saturating_sub(1);
+        self.client.check_historical_state(parent).await?;
+
+        let outcome = self.client.simulate_transaction(&transaction, parent).await?;
+        let originally_failed = transaction.status.is_some_and(|s| s.is_zero());
+        let replay_failed = matches!(outcome, CallOutcome::Revert(_));
+
+        let view = match &outcome {
+            CallOutcome::Success(output) => ReplayView {
+                hash: transaction.hash,
+                state_block: parent,
+                original_status: transaction.status,
+                reverted: false,
+                output: output.clone(),
+                revert_reason: None,
+                matches_original: originally_failed == replay_failed,
+            },
+            CallOutcome::Revert(data) => ReplayView {
+                hash: transaction.hash,
+                state_block: parent,
+                original_status: transaction.status,
+                reverted: true,
+                output: data.clone(),
+                revert_reason: Some(self.abis.explain_revert(transaction.to.as_ref(), data)),
+                matches_original: originally_failed == replay_failed,
+            },
+        };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        self.banner("🔁 Transaction Replay");
+
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
-        table.set_header(vec!["Block #", "Hash", "Transactions", "Gas Used", "Time Ago"]);
-        
-        let start_block = latest_block_num.saturating_sub(count as u64 - 1);
-        
-        for block_num in (start_block..=latest_block_num).rev() {
-            match self.client.get_block_by_number(block_num).await {
-                Ok(block) => {
-                    let gas_used_m = block.gas_used.to::<u128>() as f64 / 1_000_000.0;
-                    let time_ago_str = time_ago(block.timestamp.to::<u64>());
-                    
-                    table.add_row(vec![
-                        &block.number.to_string(),
-                        &format_b256_hash(&block.hash),
-                        &block.transactions.len().to_string(),
-                        &format!("{:.1}M", gas_used_m),
-                        &time_ago_str,
-                    ]);
-                }
-                Err(e) => {
-                    println!("⚠️  Failed to fetch block {}: {}", block_num, e);
-                }
+        apply_color(&mut table);
+        table.set_header(vec!["Property", "Value"]);
+
+        table.add_row(vec!["Hash", &format_b256_hash(&transaction.hash)]);
+        table.add_row(vec!["Replayed At", &format!("block {} (state before block {})", parent, block)]);
+        table.add_row(vec![Cell::new("Original Status"), status_cell(format_tx_status(&transaction.status))]);
+        match &outcome {
+            CallOutcome::Success(output) => {
+                table.add_row(vec![Cell::new("Replay"), status_cell("✅ Succeeds")]);
+                table.add_row(vec!["Return Data", &output.to_string()]);
+            }
+            CallOutcome::Revert(data) => {
+                table.add_row(vec![Cell::new("Replay"), status_cell("❌ Reverts")]);
+                table.add_row(vec!["Revert Reason", view.revert_reason.as_deref().unwrap_or_default()]);
+                table.add_row(vec!["Revert Data", &data.to_string()]);
             }
         }
-        
         println!("{}", table);
+
+        if !view.matches_original {
+            println!("\nℹ️  The replay differs from the original outcome: earlier transactions in block {} changed the state it ran against", block);
+        }
         Ok(())
     }
-    
-    pub async fn show_gas_statistics(&self, blocks: usize) -> Result<()> {
-        println!("⛽ Gas Statistics (Last {} Blocks)", blocks);
-        println!("=================================\n");
-        
-        let stats = self.client.get_gas_statistics(blocks).await?;
-        
+
+    /// Replay a failed transaction on its parent block's state and explain
+    /// the revert data it produces
+    async fn explain_revert(&self, transaction: &Transaction) -> String {
+        let Some(block) = transaction.block_number else {
+            return "unavailable (transaction not mined)".to_string();
+        };
+        let parent = block.saturating_to::<u64>().saturating_sub(1);
+
+        match self.client.simulate_transaction(transaction, parent).await {
+            Ok(CallOutcome::Revert(data)) => self.abis.explain_revert(transaction.to.as_ref(), &data),
+            Ok(CallOutcome::Success(_)) => {
+                "unavailable (replay on the parent block succeeds; failure depended on earlier transactions in its block)".to_string()
+            }
+            Err(e) => format!("unavailable ({})", e),
+        }
+    }
+
+    /// Show a transaction's full receipt: execution outcome, gas figures,
+    /// the deployed contract for creations, and every log emitted
+    pub async fn show_receipt(&self, tx_hash: &str) -> Result<()> {
+        let receipt = self.client.get_receipt(tx_hash).await?;
+        if self.emit_structured(&receipt)? {
+            return Ok(());
+        }
+
+        self.banner("🧾 Transaction Receipt");
+
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
-        table.set_header(vec!["Metric", "Value"]);
-        
-        table.add_row(vec!["Average Gas Used", &format_number(stats.avg_gas_used)]);
-        table.add_row(vec!["Average Gas Price", &format!("{} Gwei", stats.avg_gas_price / 1_000_000_000)]);
-        table.add_row(vec!["Max Gas Used", &format_number(stats.max_gas_used)]);
-        table.add_row(vec!["Min Gas Used", &format_number(stats.min_gas_used)]);
-        table.add_row(vec!["Gas Utilization", &format!("{:.1}%", stats.gas_utilization)]);
-        table.add_row(vec!["Blocks Analyzed", &stats.blocks_analyzed.to_string()]);
-        
+        apply_color(&mut table);
+        table.set_header(vec!["Property", "Value"]);
+
+        table.add_row(vec!["Transaction Hash", &format_b256_hash(&receipt.transaction_hash)]);
+        if let Some(block_num) = &receipt.block_number {
+            table.add_row(vec!["Block Number", &format_number_u256(block_num)]);
+        }
+        if let Some(block_hash) = &receipt.block_hash {
+            table.add_row(vec!["Block Hash", &format_b256_hash(block_hash)]);
+        }
+        if let Some(index) = &receipt.transaction_index {
+            table.add_row(vec!["Index in Block", &index.to_string()]);
+        }
+        table.add_row(vec!["From", &format_eth_address_full(&receipt.from)]);
+        match &receipt.to {
+            Some(to_addr) => table.add_row(vec!["To", &format_eth_address_full(to_addr)]),
+            None => table.add_row(vec!["To", "📄 Contract Creation"]),
+        };
+        if let Some(contract) = &receipt.contract_address {
+            table.add_row(vec!["Contract Address", &format_eth_address_full(contract)]);
+        }
+        table.add_row(vec![Cell::new("Status"), status_cell(format_tx_status(&receipt.status))]);
+        table.add_row(vec!["Gas Used", &self.format_gas(receipt.gas_used.saturating_to(), GasFormat::Raw)]);
+        table.add_row(vec!["Cumulative Gas Used", &self.format_gas(receipt.cumulative_gas_used.saturating_to(), GasFormat::Raw)]);
+        table.add_row(vec!["Effective Gas Price", &format_gas_price(&receipt.effective_gas_price)]);
+        let tx_fee = receipt.effective_gas_price.saturating_mul(receipt.gas_used);
+        table.add_row(vec!["Transaction Fee", &self.format_wei_fiat(&tx_fee).await]);
+        if let Some(bloom) = &receipt.logs_bloom {
+            let bits: u32 = bloom.as_slice().iter().map(|byte| byte.count_ones()).sum();
+            table.add_row(vec!["Logs Bloom", &format!("{} of 2048 bits set", bits)]);
+        }
+        table.add_row(vec!["Logs", &receipt.logs.len().to_string()]);
+
         println!("{}", table);
-        
-        // Show gas usage trend visualization
-        println!("\n📈 Gas Usage Trend:");
-        self.show_gas_trend(&stats).await;
-        
+
+        if !receipt.logs.is_empty() {
+            println!("\n📜 Logs:");
+            println!("{}", self.receipt_logs_table(&receipt));
+        }
+        Ok(())
+    }
+
+    /// One row per log, named by its decoded event where an ABI knows it
+    fn receipt_logs_table(&self, receipt: &Receipt) -> Table {
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Log #", "Address", "Event", "Topics", "Data"]);
+
+        for log in &receipt.logs {
+            let event = match self.abis.decode_log(log) {
+                Some(event) => event.signature,
+                None => log.topics.first().map_or("-".to_string(), format_b256_hash),
+            };
+            table.add_row(vec![
+                log.log_index.map_or("-".to_string(), |n| n.to_string()),
+                format_eth_address(&log.address),
+                event,
+                log.topics.len().to_string(),
+                format!("{} bytes", log.data.len()),
+            ]);
+        }
+        table
+    }
+
+    /// Print a transaction, its receipt, its block header and everything
+    /// decodable from its logs as one JSON document, so a single call gives
+    /// scripts the whole picture
+    pub async fn show_transaction_summary(&self, tx_hash: &str) -> Result<()> {
+        let summary = self.transaction_summary(tx_hash).await?;
+        println!("{}", serde_json::to_string_pretty(&summary)?);
         Ok(())
     }
+
+    /// Gather the summary for `show_transaction_summary`. A pending
+    /// transaction has no receipt or block yet, so both are left null.
+    async fn transaction_summary(&self, tx_hash: &str) -> Result<TransactionSummary> {
+        let transaction = self.client.get_transaction(tx_hash).await?;
+        let (receipt, block) = match transaction.block_number {
+            Some(number) => {
+                let number = number.saturating_to::<u64>();
+                let receipt = self.client.get_receipt_json(tx_hash, Some(number)).await?;
+                let header = self.client.get_block_header_by_number(number).await?;
+                (receipt, Some(BlockHeaderSummary::from(&header)))
+            }
+            None => (serde_json::Value::Null, None),
+        };
+
+        let call = self.decode_call(&transaction).await.map(|(call, _)| DecodedSummary::from(&call));
+        let events = transaction.logs.iter()
+            .filter_map(|log| {
+                self.abis.decode_log(log).map(|event| DecodedSummary {
+                    address: Some(log.address),
+                    ..DecodedSummary::from(&event)
+                })
+            })
+            .collect();
+        let token_transfers = transaction.logs.iter().filter_map(decode_erc20_transfer).collect();
+
+        Ok(TransactionSummary {
+            schema: TRANSACTION_SUMMARY_SCHEMA,
+            version: TRANSACTION_SUMMARY_VERSION,
+            chain_id: self.client.chain_id(),
+            transaction,
+            receipt,
+            block,
+            call,
+            events,
+            token_transfers,
+        })
+    }
+
+    /// Decode a transaction's calldata, looking through proxies so calls are
+    /// decoded against the implementation's ABI rather than the proxy's.
+    /// The implementation address is returned when it was used.
+    async fn decode_call(&self, transaction: &Transaction) -> Option<(Decoded, Option<Address>)> {
+        let to = transaction.to.as_ref()?;
+        if self.abis.is_empty() || transaction.input.len() < 4 {
+            return None;
+        }
+
+        // State at old blocks may be pruned; fall back to decoding directly
+        let block = transaction.block_number.map(|n| n.saturating_to::<u64>());
+        if let Ok(Some(implementation)) = self.client.get_proxy_implementation(to, block).await {
+            if let Some(call) = self.abis.decode_call(Some(&implementation), &transaction.input) {
+                return Some((call, Some(implementation)));
+            }
+        }
+
+        self.abis.decode_call(Some(to), &transaction.input).map(|call| (call, None))
+    }
     
-    async fn show_gas_trend(&self, _stats: &crate::rpc::GasStatistics) {
-        // Simple ASCII visualization of gas usage trend
-        // In a real implementation, you'd fetch recent blocks and show actual trend
-        let trend_chars = vec!["▁", "▂", "▃", "▅", "▄", "▅", "▆", "▇", "█", "▆", "▅", "▄", "▃", "▂", "▁", "▂", "▃", "▄", "▅", "▆", "▅", "▄", "▃", "▂", "▁"];
-        let trend_line: String = trend_chars.iter().cycle().take(50).map(|&s| s).collect();
-        println!("{}", trend_line);
-        println!("Low ←────────────────────────────────────────────→ High");
+    pub async fn show_account(&self, address: &str, block: Option<u64>, code_hash: bool, first_tx: bool, pending: bool) -> Result<()> {
+        let address = self.resolve_address(address).await?;
+        let account = self.client.get_account_balance(&address.to_string(), block).await?;
+        let nonces = if pending {
+            let latest = self.client.get_nonce_at_tag(&address, BlockTag::Latest).await?;
+            let pending = self.client.get_nonce_at_tag(&address, BlockTag::Pending).await?;
+            Some(PendingNonces { latest, pending, gap: pending.saturating_sub(latest) })
+        } else {
+            None
+        };
+        if self.emit_structured(&AccountView { account: &account, pending_nonces: nonces.as_ref() })? {
+            return Ok(());
+        }
+
+        let block_str = block.map_or("latest".to_string(), |b| b.to_string());
+        self.banner(format!("👤 Account Information (Block: {})", block_str));
+        
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Value"]);
+        
+        table.add_row(vec!["Address", &format_eth_address_full(&account.address)]);
+        table.add_row(vec!["Balance", &self.format_wei_fiat(&account.balance).await]);
+        table.add_row(vec!["Nonce", &account.nonce.to_string()]);
+        table.add_row(vec!["Type", account_type(&account.code_size)]);
+        
+        if !account.code_size.is_zero() {
+            table.add_row(vec!["Code Size", &format!("{} bytes", account.code_size.to::<u64>())]);
+        }
+
+        if code_hash {
+            table.add_row(vec!["Code Hash", &account.code_hash.to_string()]);
+            let known = known_code_hash(&account.code_hash).unwrap_or("No match in registry");
+            table.add_row(vec!["Known Implementation", known]);
+        }
+
+        if let Some(nonces) = &nonces {
+            table.add_row(vec!["Nonce (latest)", &nonces.latest.to_string()]);
+            table.add_row(vec!["Nonce (pending)", &nonces.pending.to_string()]);
+            table.add_row(vec!["Pending Transactions", &nonces.gap.to_string()]);
+        }
+        
+        println!("{}", table);
+
+        if let Some(nonces) = nonces.filter(|nonces| nonces.gap > 0) {
+            println!(
+                "\n⚠️  {} transaction(s) waiting in the mempool (nonces {}..={}); check for stuck transactions",
+                nonces.gap, nonces.latest, nonces.pending - 1
+            );
+        }
+
+        if first_tx {
+            self.show_first_activity(&account.address).await?;
+        }
+        Ok(())
+    }
+
+    /// Balances of several accounts at one block, fetched concurrently and
+    /// listed richest first with their total. An account that can't be
+    /// fetched gets an error row unless `--on-error fail-fast` is set.
+    pub async fn show_accounts(&self, addresses: &[String], block: Option<u64>) -> Result<()> {
+        if addresses.is_empty() {
+            return Err(ExtractorError::InvalidInput("No addresses given".into()).into());
+        }
+        let mut tally = ErrorTally::new(self.error_policy);
+        let AccountBalances { accounts, failed, total } = self.account_balances(addresses, block, &mut tally).await?;
+
+        let view = AccountsView { block, accounts: &accounts, failed: &failed, total };
+        if self.emit_structured(&view)? {
+            return tally.finish();
+        }
+
+        let block_str = block.map_or("latest".to_string(), |b| b.to_string());
+        self.banner(format!("👥 Account Balances (Block: {})", block_str));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        apply_color(&mut table);
+        table.set_header(vec!["Address", "Balance", "Nonce", "Type"]);
+        for account in &accounts {
+            table.add_row(vec![
+                format_eth_address_full(&account.address),
+                format_wei_u256(&account.balance),
+                account.nonce.to_string(),
+                account_type(&account.code_size).to_string(),
+            ]);
+        }
+        for input in &failed {
+            table.add_row(vec![Cell::new(input), status_cell("❌ failed"), Cell::new("-"), Cell::new("-")]);
+        }
+        table.add_row(vec![
+            format!("Total ({} accounts)", accounts.len()),
+            self.format_wei_fiat(&total).await,
+            String::new(),
+            String::new(),
+        ]);
+        println!("{}", table);
+        tally.finish()
+    }
+
+    /// Look up each address, richest first with failures after them in the
+    /// order given. Under `FailFast` the first failure stops the lookups.
+    async fn account_balances(&self, addresses: &[String], block: Option<u64>, tally: &mut ErrorTally) -> Result<AccountBalances> {
+        let results = stream::iter(addresses)
+            .map(|input| async move {
+                let account = match self.resolve_address(input).await {
+                    Ok(address) => self.client.get_account_balance(&address.to_string(), block).await.map_err(Into::into),
+                    Err(e) => Err(e),
+                };
+                (input, account)
+            })
+            .buffer_unordered(ACCOUNT_FETCH_CONCURRENCY);
+        tokio::pin!(results);
+
+        let mut accounts = Vec::new();
+        let mut failed = Vec::new();
+        while let Some((input, account)) = results.next().await {
+            match tally.check(input, account)? {
+                Some(account) => accounts.push(account),
+                None => failed.push(input.clone()),
+            }
+        }
+        accounts.sort_by_key(|account| std::cmp::Reverse(account.balance));
+        let position: HashMap<&String, usize> = addresses.iter().enumerate().map(|(i, a)| (a, i)).collect();
+        failed.sort_by_key(|input| position[input]);
+        let total = accounts.iter().fold(U256::ZERO, |sum, account| sum.saturating_add(account.balance));
+        Ok(AccountBalances { accounts, failed, total })
+    }
+
+    /// Show the bytecode deployed at an address with its size and hash,
+    /// or write it to `out` as hex
+    pub async fn show_code(&self, address: Address, block: Option<u64>, out: Option<&std::path::Path>) -> Result<()> {
+        let code = self.client.get_code(&address, block).await?;
+        if let Some(path) = out {
+            // An empty file would pass for a successful dump in a script
+            if code.is_empty() {
+                return Err(RethClientError::NotFound(format!("No code at {} to write to {}", format_eth_address_full(&address), path.display())).into());
+            }
+            std::fs::write(path, format!("{}\n", code))
+                .map_err(|e| color_eyre::eyre::eyre!("Failed to write {}: {}", path.display(), e))?;
+            if !self.quiet {
+                eprintln!("💾 Wrote {} bytes of bytecode to {}", code.len(), path.display());
+            }
+        }
+        let code_hash = keccak256(&code);
+        let view = CodeView { address, block, size: code.len(), code_hash, code: &code };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        let block_str = block.map_or("latest".to_string(), |b| b.to_string());
+        self.banner(format!("📄 Contract Code (Block: {})", block_str));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Value"]);
+        table.add_row(vec!["Address", &format_eth_address_full(&address)]);
+        table.add_row(vec!["Code Size", &format!("{} bytes", format_number(code.len() as u64))]);
+        table.add_row(vec!["Code Hash", &format!("{:?}", code_hash)]);
+        if let Some(known) = known_code_hash(&code_hash) {
+            table.add_row(vec!["Known Implementation", known]);
+        } else if let Some(target) = minimal_proxy_target(&code) {
+            table.add_row(vec!["Known Implementation", &format!("EIP-1167 Minimal Proxy (OpenZeppelin Clones) → {}", format_eth_address_full(&target))]);
+        }
+        println!("{}", table);
+
+        if code.is_empty() {
+            println!("\nℹ️  No code at this address: an externally owned account, or a contract not deployed (or self-destructed) at this block");
+        } else if out.is_none() {
+            println!("\nBytecode:\n{}", code);
+        }
+        Ok(())
+    }
+
+    /// When, in which transaction and by whom a contract was deployed
+    pub async fn show_creation(&self, address: Address) -> Result<()> {
+        let creation = self.client.get_contract_creation(address).await?;
+        let block = self.client.get_block_header_by_number(creation.block_number).await?;
+        let view = CreationView { creation: &creation, timestamp: block.timestamp.saturating_to() };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        self.banner("🏗️  Contract Creation");
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Value"]);
+        table.add_row(vec!["Contract", &format_eth_address_full(&address)]);
+        table.add_row(vec!["Block Number", &format_number(creation.block_number)]);
+        table.add_row(vec!["Deployed At", &format_timestamp_u256(&block.timestamp)]);
+        table.add_row(vec!["Transaction", &format_b256_hash(&creation.transaction_hash)]);
+        table.add_row(vec!["Deployer", &format_eth_address_full(&creation.deployer)]);
+        if let Some(factory) = &creation.factory {
+            table.add_row(vec!["Factory", &format_eth_address_full(factory)]);
+        }
+        println!("{}", table);
+        Ok(())
+    }
+
+    /// Read one storage slot, shown raw and decoded as a uint256 and as an
+    /// address
+    pub async fn show_storage(&self, address: Address, slot: B256, block: Option<u64>) -> Result<()> {
+        let value = self.client.get_storage_at(&address, slot, block).await?;
+        let view = StorageView {
+            address,
+            block,
+            slot,
+            value,
+            as_uint: U256::from_be_bytes(value.0),
+            as_address: Address::from_word(value),
+        };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        let block_str = block.map_or("latest".to_string(), |b| b.to_string());
+        self.banner(format!("🗄️  Storage Slot (Block: {})", block_str));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Value"]);
+        table.add_row(vec!["Address", &format_eth_address_full(&address)]);
+        let slot_str = match storage_slot_name(&slot) {
+            Some(name) => format!("{:?} (EIP-1967 {})", slot, name),
+            None => format!("{:?}", slot),
+        };
+        table.add_row(vec!["Slot", &slot_str]);
+        table.add_row(vec!["Raw Value", &format!("{:?}", value)]);
+        table.add_row(vec!["As Uint256", &view.as_uint.to_string()]);
+        table.add_row(vec!["As Address", &format_eth_address_full(&view.as_address)]);
+        println!("{}", table);
+        Ok(())
+    }
+
+    /// Run an arbitrary `eth_call` and show the return data raw, plus as a
+    /// number, address and bool when it is a single word
+    pub async fn show_call(&self, request: &CallRequest, block: Option<u64>) -> Result<()> {
+        let outcome = self.client.eth_call(request, block).await?;
+        let (output, revert_reason) = match &outcome {
+            CallOutcome::Success(output) => (output, None),
+            CallOutcome::Revert(data) => (data, Some(self.abis.explain_revert(Some(&request.to), data))),
+        };
+        let word = (revert_reason.is_none() && output.len() == 32).then(|| B256::from_slice(output));
+        let decoded = revert_reason.is_none()
+            .then(|| self.abis.decode_output(Some(&request.to), &request.data, output))
+            .flatten();
+        let view = CallView {
+            to: request.to,
+            from: request.from,
+            block,
+            success: revert_reason.is_none(),
+            output,
+            as_uint: word.map(|word| U256::from_be_bytes(word.0)),
+            as_address: word.filter(|word| word[..12].iter().all(|b| *b == 0)).map(Address::from_word),
+            as_bool: word.and_then(|word| match U256::from_be_bytes(word.0) {
+                value if value.is_zero() => Some(false),
+                value if value == U256::from(1) => Some(true),
+                _ => None,
+            }),
+            revert_reason: revert_reason.as_deref(),
+            decoded: decoded.as_ref().map(DecodedSummary::from),
+        };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        let block_str = block.map_or("latest".to_string(), |b| b.to_string());
+        self.banner(format!("📞 Contract Call (Block: {})", block_str));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        apply_color(&mut table);
+        table.set_header(vec!["Property", "Value"]);
+        table.add_row(vec!["To", &format_eth_address_full(&request.to)]);
+        if let Some(from) = &request.from {
+            table.add_row(vec!["From", &format_eth_address_full(from)]);
+        }
+        if let Some(value) = &request.value {
+            table.add_row(vec!["Value", &format_wei_u256(value)]);
+        }
+        match view.revert_reason {
+            Some(reason) => table.add_row(vec![Cell::new("Outcome"), status_cell(format!("❌ Reverted: {}", reason))]),
+            None => table.add_row(vec![Cell::new("Outcome"), status_cell("✅ Success")]),
+        };
+        table.add_row(vec!["Return Data", &output.to_string()]);
+        table.add_row(vec!["Size", &format!("{} bytes", output.len())]);
+        if let Some(as_uint) = &view.as_uint {
+            table.add_row(vec!["As Uint256", &as_uint.to_string()]);
+        }
+        if let Some(as_address) = &view.as_address {
+            table.add_row(vec!["As Address", &format_eth_address_full(as_address)]);
+        }
+        if let Some(as_bool) = view.as_bool {
+            table.add_row(vec!["As Bool", &as_bool.to_string()]);
+        }
+        if let Some(decoded) = &view.decoded {
+            table.add_row(vec!["Function", &decoded.signature]);
+            for param in &decoded.params {
+                table.add_row(vec![format!("  {}", param.name), param.value.clone()]);
+            }
+        }
+        println!("{}", table);
+        Ok(())
+    }
+
+    pub async fn show_token_balance(&self, token: Address, holder: &str, block: Option<u64>) -> Result<()> {
+        let holder = self.resolve_address(holder).await?;
+        let balance = self.client.get_token_balance(token, holder, block).await?;
+        let metadata = self.client.get_token_metadata(token).await?;
+        let view = TokenBalanceView {
+            token,
+            holder,
+            block,
+            symbol: metadata.symbol.clone(),
+            decimals: metadata.decimals,
+            balance,
+            formatted: metadata.decimals.map(|decimals| format_units(&balance, decimals)),
+        };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        let block_str = block.map_or("latest".to_string(), |b| b.to_string());
+        self.banner(format!("🪙 Token Balance (Block: {})", block_str));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Value"]);
+
+        let token_name = match &metadata.symbol {
+            Some(symbol) => format!("{} ({})", symbol, format_eth_address_full(&token)),
+            None => format_eth_address_full(&token),
+        };
+        table.add_row(vec!["Token", &token_name]);
+        table.add_row(vec!["Holder", &format_eth_address_full(&holder)]);
+        table.add_row(vec!["Raw Balance", &balance.to_string()]);
+        table.add_row(vec!["Decimals", &metadata.decimals.map_or("unknown".to_string(), |d| d.to_string())]);
+        let formatted = match (&view.formatted, &metadata.symbol) {
+            (Some(amount), Some(symbol)) => format!("{} {}", amount, symbol),
+            (Some(amount), None) => amount.clone(),
+            (None, _) => "unknown (token has no decimals())".to_string(),
+        };
+        table.add_row(vec!["Balance", &formatted]);
+
+        println!("{}", table);
+        Ok(())
+    }
+
+    /// Binary-search historical state for the block where an account first
+    /// sent a transaction or received code
+    async fn show_first_activity(&self, address: &Address) -> Result<()> {
+        println!("\n🔎 Searching for first activity (requires an archive node)...");
+        self.client.check_historical_state(0).await?;
+
+        let latest = self.client.get_latest_block_number().await?;
+        match self.client.first_active_block(address, latest).await? {
+            Some(block_num) => {
+                let block = self.client.get_block_header_by_number(block_num).await?;
+                println!("🐣 First active in block {} ({})", format_number(block_num), format_timestamp_u256(&block.timestamp));
+            }
+            None => println!("No activity found (nonce is zero and no code is deployed)"),
+        }
+        Ok(())
+    }
+    
+    /// The latest `count` blocks, or with `since` every block mined within
+    /// that long of now
+    pub async fn show_latest_blocks(&self, count: usize, since: Option<Duration>, tx_detail: bool, include_pending: bool) -> Result<()> {
+        let latest_block_num = self.client.get_latest_block_number().await?;
+        let (numbers, title): (Vec<u64>, _) = match since {
+            Some(since) => {
+                let cutoff = (Utc::now().timestamp() as u64).saturating_sub(since.as_secs());
+                let title = format!("📊 Blocks from the Last {}", format_duration(since));
+                match self.client.find_block_at_timestamp(cutoff, latest_block_num).await? {
+                    Some(start_block) => {
+                        check_scan_range(start_block, latest_block_num)?;
+                        if start_block == 0 && !self.quiet {
+                            eprintln!("ℹ️  The window starts before the chain's first block; showing every block");
+                        }
+                        ((start_block..=latest_block_num).rev().collect(), title)
+                    }
+                    None => (Vec::new(), title),
+                }
+            }
+            None => {
+                let start_block = latest_block_num.saturating_sub(count as u64 - 1);
+                ((start_block..=latest_block_num).rev().collect(), format!("📊 Latest {} Blocks", count))
+            }
+        };
+        let pending = if include_pending {
+            self.client.get_pending_block().await?
+        } else {
+            None
+        };
+
+        let mut tally = ErrorTally::new(self.error_policy);
+        let mut blocks = Vec::new();
+        for (block_num, block) in self.client.get_blocks(&numbers, tx_detail).await {
+            if let Some(block) = tally.check(format!("block {}", block_num), block)? {
+                blocks.push(block);
+            }
+        }
+
+        let rows: Vec<_> = pending.iter().chain(&blocks).map(LatestBlockRow::from).collect();
+        if self.emit_csv(&rows)? {
+            return tally.finish();
+        }
+        let view = LatestBlocksView { pending: pending.as_ref(), blocks: &blocks };
+        if self.emit_structured(&view)? {
+            return tally.finish();
+        }
+
+        self.banner(title);
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Block #", "Hash", "Transactions", "Gas Used", "Time Ago"]);
+
+        if include_pending {
+            match &pending {
+                Some(pending) => {
+                    table.add_row(vec![
+                        "⏳ pending".to_string(),
+                        format_block_hash(&pending.hash),
+                        pending.transactions.len().to_string(),
+                        format!("{} (projected)", self.format_gas(pending.gas_used.saturating_to::<u64>(), GasFormat::Human)),
+                        "not yet mined".to_string(),
+                    ]);
+                }
+                None => println!("ℹ️  Node did not return a pending block\n"),
+            }
+        }
+
+        for block in &blocks {
+            let time_ago_str = time_ago(block.timestamp.saturating_to::<u64>());
+            
+            table.add_row(vec![
+                &block.number.to_string(),
+                &format_block_hash(&block.hash),
+                &block.transactions.len().to_string(),
+                &self.format_gas(block.gas_used.saturating_to::<u64>(), GasFormat::Human),
+                &time_ago_str,
+            ]);
+
+            if tx_detail {
+                if let Some(tx) = block.top_transaction_by_value() {
+                    let to = tx.to.as_ref()
+                        .map_or("📄 Contract Creation".to_string(), format_eth_address);
+                    table.add_row(vec![
+                        "  └ top tx",
+                        &format_b256_hash(&tx.hash),
+                        &format!("{} → {}", format_eth_address(&tx.from), to),
+                        &format_wei_u256(&tx.value),
+                        "",
+                    ]);
+                }
+            }
+        }
+        
+        println!("{}", table);
+        if let (Some(since), true) = (since, numbers.is_empty()) {
+            println!("\nNo blocks mined in the last {}", format_duration(since));
+        }
+        tally.finish()
+    }
+    
+    pub async fn show_raw_transaction(&self, tx_hash: &str) -> Result<()> {
+        let raw = self.client.get_raw_transaction(tx_hash).await?;
+        let transaction = self.client.get_transaction(tx_hash).await?;
+        if self.emit_structured(&raw)? {
+            return Ok(());
+        }
+
+        println!("{}", raw.bytes);
+        println!();
+        if raw.reconstructed {
+            println!("ℹ️  Node lacks eth_getRawTransactionByHash; re-encoded from transaction fields");
+        }
+        if raw.signer == transaction.from {
+            println!("✅ Hash verified, recovered signer {} matches sender", raw.signer);
+        } else {
+            println!("❌ Recovered signer {} does not match sender {}", raw.signer, transaction.from);
+        }
+        Ok(())
+    }
+
+    /// Write blocks `from..=to` to `out` as newline-delimited JSON, one
+    /// block per line, with progress on stderr
+    pub async fn export_blocks(&self, from: u64, to: u64, out: &std::path::Path, with_transactions: bool) -> Result<()> {
+        if from > to {
+            return Err(ExtractorError::InvalidInput(format!(
+                "Invalid block range: {} is after {} (ranges run oldest to newest)", from, to
+            )).into());
+        }
+        let file = std::fs::File::create(out)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to create {}: {}", out.display(), e))?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        let progress = Progress::new("📦 Exporting", to - from + 1, !self.quiet);
+        let mut tally = ErrorTally::new(self.error_policy);
+        let mut written = 0u64;
+        let mut chunk_start = from;
+        loop {
+            let chunk_end = to.min(chunk_start.saturating_add(EXPORT_CHUNK_BLOCKS - 1));
+            let numbers: Vec<u64> = (chunk_start..=chunk_end).collect();
+            for (block_num, block) in self.client.get_blocks(&numbers, with_transactions).await {
+                if let Some(block) = tally.check(format!("block {}", block_num), block)? {
+                    serde_json::to_writer(&mut writer, &block)?;
+                    writer.write_all(b"\n")?;
+                    written += 1;
+                }
+            }
+            writer.flush()?;
+
+            progress.inc(numbers.len() as u64);
+            if chunk_end == to {
+                break;
+            }
+            chunk_start = chunk_end + 1;
+        }
+        progress.finish();
+        if !self.quiet {
+            println!("💾 Wrote {} blocks to {}", format_number(written), out.display());
+        }
+        tally.finish()
+    }
+
+    pub async fn show_transactions_from(&self, address: &str, from_block: u64, to_block: Option<u64>, detect_gaps: bool) -> Result<()> {
+        let to_block = match to_block {
+            Some(n) => n,
+            None => self.client.get_latest_block_number().await?,
+        };
+
+        let address = self.resolve_address(address).await?;
+        let mut tally = ErrorTally::new(self.error_policy);
+        let transactions = self.client.get_transactions_from(&address.to_string(), from_block, to_block, &mut tally).await?;
+        if self.emit_structured(&transactions)? {
+            return tally.finish();
+        }
+
+        self.banner(format!("🔎 Transactions Sent (Scan of Blocks {}..={})", from_block, to_block));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        apply_color(&mut table);
+        table.set_header(vec!["Block #", "Hash", "Nonce", "To", "Value", "Status"]);
+
+        for tx in &transactions {
+            let to = tx.to.as_ref()
+                .map_or("📄 Contract Creation".to_string(), format_eth_address);
+            table.add_row(vec![
+                Cell::new(tx.block_number.map_or("-".to_string(), |n| n.to_string())),
+                Cell::new(format_b256_hash(&tx.hash)),
+                Cell::new(tx.nonce),
+                Cell::new(to),
+                Cell::new(format_wei_u256(&tx.value)),
+                status_cell(format_tx_status(&tx.status)),
+            ]);
+        }
+
+        println!("{}", table);
+        println!("\nFound {} transactions. Only the scanned range is covered; this is not a full account history.",
+            transactions.len());
+
+        if detect_gaps {
+            let nonces: Vec<u64> = transactions.iter().map(|tx| tx.nonce.saturating_to::<u64>()).collect();
+            let anomalies = nonce_anomalies(&nonces);
+            if tally.failed() > 0 {
+                println!("ℹ️  Some blocks could not be scanned, so gaps below may be spurious");
+            }
+            if anomalies.is_empty() {
+                println!("✅ No nonce gaps or replacements in range");
+            } else {
+                println!("\n⚠️  Nonce anomalies:");
+                for anomaly in anomalies {
+                    println!("  • {}", anomaly);
+                }
+            }
+        }
+        tally.finish()
+    }
+
+    pub async fn show_logs(&self, filter: &LogFilter, from_block: BlockBound, to_block: Option<BlockBound>, options: &LogsOptions) -> Result<()> {
+        let chunk_size = options.chunk_size;
+        let from_block = self.resolve_bound(from_block).await?;
+        let to_block = self.resolve_bound(to_block.unwrap_or(BlockBound::Latest)).await?;
+        check_log_range(from_block, to_block)?;
+
+        if options.count_only {
+            let mut tally = ErrorTally::new(self.error_policy);
+            let counts = self.client.count_logs(filter, from_block, to_block, chunk_size, &mut tally).await?;
+            let total: usize = counts.iter().map(|(_, count)| count).sum();
+            let view = LogCountsView {
+                total,
+                chunks: options.per_chunk.then(|| {
+                    counts.iter().map(|((from, to), count)| ChunkCount { from_block: *from, to_block: *to, count: *count }).collect()
+                }),
+            };
+            if self.emit_structured(&view)? {
+                return tally.finish();
+            }
+
+            self.banner(format!("📜 Event Logs (Blocks {}..={}, chunk size {})", from_block, to_block, chunk_size));
+            if options.per_chunk {
+                let mut table = Table::new();
+                table.load_preset(UTF8_FULL);
+                table.set_header(vec!["From Block", "To Block", "Logs"]);
+                for ((start, end), count) in &counts {
+                    table.add_row(vec![start.to_string(), end.to_string(), format_number(*count as u64)]);
+                }
+                println!("{}\n", table);
+            }
+            println!("Total matching logs: {}", format_number(total as u64));
+            return tally.finish();
+        }
+
+        let mut tally = ErrorTally::new(self.error_policy);
+        let mut logs = if options.bloom_prefilter {
+            check_scan_range(from_block, to_block)?;
+            self.client.get_logs_bloom_filtered(filter, from_block, to_block, chunk_size, &mut tally).await?
+        } else {
+            self.client.get_logs(filter, from_block, to_block, chunk_size, &mut tally).await?
+        };
+        sort_logs(&mut logs, options.sort_by, options.reverse);
+        // Without an ABI the listing stays raw, and so does its structured form
+        let decode = !self.abis.is_empty();
+        let decoded: Vec<_> = logs.iter().map(|log| decode.then(|| self.abis.decode_log(log)).flatten()).collect();
+        let structured = if decode {
+            let views: Vec<_> = logs.iter().zip(&decoded)
+                .map(|(log, event)| DecodedLogView { log, event: event.as_ref().map(DecodedSummary::from) })
+                .collect();
+            self.emit_structured(&views)?
+        } else {
+            self.emit_structured(&logs)?
+        };
+        if structured {
+            return tally.finish();
+        }
+
+        self.banner(format!("📜 Event Logs (Blocks {}..={}, chunk size {})", from_block, to_block, chunk_size));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        let mut header = vec!["Block #", "Tx Hash", "Address", "Topic0"];
+        if decode {
+            header[3] = "Event";
+            header.push("Params");
+        }
+        table.set_header(header);
+
+        for (log, event) in logs.iter().zip(&decoded) {
+            let mut row = vec![
+                log.block_number.map_or("-".to_string(), |n| n.to_string()),
+                log.transaction_hash.as_ref().map_or("-".to_string(), format_b256_hash),
+                format_eth_address(&log.address),
+            ];
+            match event {
+                Some(event) => row.extend([event.signature.clone(), format_decoded_params(event)]),
+                None => {
+                    row.push(log.topics.first().map_or("-".to_string(), format_b256_hash));
+                    if decode {
+                        row.push("-".to_string());
+                    }
+                }
+            }
+            table.add_row(row);
+        }
+
+        println!("{}", table);
+        println!("\nFound {} logs", logs.len());
+        tally.finish()
+    }
+
+    /// Holder snapshot of a token: every address that received it through a
+    /// `Transfer` in the range, with its balance as of the latest block
+    pub async fn show_holder_snapshot(&self, token: Address, from_block: BlockBound, to_block: Option<BlockBound>, chunk_size: u64, bloom_prefilter: bool) -> Result<()> {
+        let from_block = self.resolve_bound(from_block).await?;
+        let to_block = self.resolve_bound(to_block.unwrap_or(BlockBound::Latest)).await?;
+        check_scan_range(from_block, to_block)?;
+
+        let filter = LogFilter { address: Some(token), topics: vec![Some(TRANSFER_TOPIC)] };
+        let mut tally = ErrorTally::new(self.error_policy);
+        let logs = if bloom_prefilter {
+            self.client.get_logs_bloom_filtered(&filter, from_block, to_block, chunk_size, &mut tally).await?
+        } else {
+            self.client.get_logs(&filter, from_block, to_block, chunk_size, &mut tally).await?
+        };
+        let mut recipients: Vec<Address> = logs.iter()
+            .filter_map(decode_erc20_transfer)
+            .map(|transfer| transfer.to)
+            .filter(|to| !to.is_zero())
+            .collect();
+        recipients.sort_unstable();
+        recipients.dedup();
+        if !self.quiet {
+            eprintln!("Reading balances of {} unique recipients...", recipients.len());
+        }
+
+        let balances = self.client.get_token_balances(token, &recipients).await?;
+        let failed = balances.iter().filter(|balance| balance.is_none()).count();
+        let mut holders: Vec<(Address, U256)> = recipients.into_iter()
+            .zip(balances)
+            .filter_map(|(holder, balance)| balance.filter(|b| !b.is_zero()).map(|b| (holder, b)))
+            .collect();
+        holders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let view = HolderSnapshotView {
+            token,
+            from_block,
+            to_block,
+            failed_reads: failed,
+            holders: holders.iter().map(|(holder, balance)| HolderBalance { holder: *holder, balance: *balance }).collect(),
+        };
+        if self.emit_structured(&view)? {
+            return tally.finish();
+        }
+
+        self.banner(format!("📸 Holder Snapshot for {} (recipients in blocks {}..={})", format_eth_address(&token), from_block, to_block));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["#", "Holder", "Balance (raw units)"]);
+        for (i, (holder, balance)) in holders.iter().enumerate() {
+            table.add_row(vec![(i + 1).to_string(), format_eth_address(holder), balance.to_string()]);
+        }
+        println!("{}", table);
+
+        println!("\n{} holders with a nonzero balance", format_number(holders.len() as u64));
+        if failed > 0 {
+            println!("⚠️  {} balance reads failed and were left out", failed);
+        }
+        println!("Balances are a point-in-time snapshot at the latest block, not a live index;");
+        println!("holders who only received the token outside the scanned range are missing.");
+        tally.finish()
+    }
+
+    /// Send an arbitrary JSON-RPC request and print the result as-is
+    pub async fn show_raw(&self, method: &str, params: Vec<serde_json::Value>) -> Result<()> {
+        let result = self.client.raw_request(method, params).await?;
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        Ok(())
+    }
+
+    /// Trace every transaction in a block and summarize the internal calls:
+    /// per transaction, then rolled up across the block
+    pub async fn show_block_trace(&self, block_id: &str, tracer: &str) -> Result<()> {
+        let traces = self.client.trace_block(block_id, tracer).await?;
+        if self.emit_structured(&traces)? {
+            return Ok(());
+        }
+
+        self.banner(format!("🔬 Block Trace ({}, {})", block_id, tracer));
+        if tracer != "callTracer" {
+            // Only call frames can be summarized; show other tracers as-is
+            for trace in &traces {
+                let hash = trace.tx_hash.as_ref().map_or("-".to_string(), format_b256_hash);
+                println!("{}\n{}\n", hash, serde_json::to_string_pretty(&trace.result)?);
+            }
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        apply_color(&mut table);
+        table.set_header(vec!["#", "Tx Hash", "Internal Calls", "Transfers", "Reverted Calls", "Outcome"]);
+
+        let mut transfers = 0usize;
+        let mut transferred = U256::ZERO;
+        let mut call_counts: HashMap<Address, usize> = HashMap::new();
+        let mut reverted = Vec::new();
+
+        for (i, trace) in traces.iter().enumerate() {
+            let hash = trace.tx_hash.as_ref().map_or("-".to_string(), format_b256_hash);
+            let root: CallFrame = serde_json::from_value(trace.result.clone())
+                .map_err(|e| color_eyre::eyre::eyre!("Unexpected trace format for transaction {}: {}", i, e))?;
+            let frames = root.flatten();
+            let internal: Vec<_> = frames.iter().filter(|(depth, _)| *depth > 0).map(|(_, frame)| *frame).collect();
+
+            let tx_transfers: Vec<_> = internal.iter().filter_map(|frame| frame.value.filter(|v| !v.is_zero())).collect();
+            transfers += tx_transfers.len();
+            transferred += tx_transfers.iter().copied().sum::<U256>();
+            for (_, frame) in &frames {
+                if let Some(to) = frame.to {
+                    *call_counts.entry(to).or_default() += 1;
+                }
+            }
+            let tx_reverted: Vec<_> = internal.iter().filter(|frame| frame.error.is_some()).collect();
+            for frame in &tx_reverted {
+                reverted.push((hash.clone(), frame.to, frame.error.clone().unwrap_or_default()));
+            }
+
+            table.add_row(vec![
+                Cell::new(i),
+                Cell::new(hash),
+                Cell::new(internal.len()),
+                Cell::new(tx_transfers.len()),
+                Cell::new(tx_reverted.len()),
+                status_cell(root.error.as_ref().map_or("✅ Success".to_string(), |e| format!("❌ {}", e))),
+            ]);
+        }
+
+        println!("{}", table);
+
+        println!("\n📦 Block Rollup:");
+        println!("  Transactions traced: {}", traces.len());
+        println!("  Internal transfers: {} moving {}", transfers, format_wei_u256(&transferred));
+
+        let mut most_called: Vec<_> = call_counts.into_iter().collect();
+        most_called.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        if !most_called.is_empty() {
+            println!("\n📞 Most-Called Contracts:");
+            for (address, calls) in most_called.iter().take(5) {
+                println!("  {} — {} calls", format_eth_address(address), calls);
+            }
+        }
+
+        if !reverted.is_empty() {
+            println!("\n↩️  Reverted Internal Calls:");
+            for (hash, to, error) in &reverted {
+                let to = to.as_ref().map_or("📄 Contract Creation".to_string(), format_eth_address);
+                println!("  {} → {}: {}", hash, to, error);
+            }
+        }
+        Ok(())
+    }
+
+    /// List a block's uncles with the number and miner from each uncle's
+    /// header. A header that can't be fetched only loses those details.
+    async fn show_uncles(&self, block: &Block) {
+        println!("\n👪 Uncles:");
+        for (i, hash) in block.uncles.iter().enumerate() {
+            let uncle = match block.hash {
+                Some(block_hash) => self.client.get_uncle_by_block_hash_and_index(block_hash, i).await,
+                None => Err(RethClientError::NotFound("block is still pending".into())),
+            };
+            match uncle {
+                Ok(uncle) => println!(
+                    "  {}. {} — block {}, mined by {}",
+                    i + 1, format_b256_hash(hash), uncle.number, format_eth_address(&uncle.miner)
+                ),
+                Err(e) => println!("  {}. {} (header unavailable: {})", i + 1, format_b256_hash(hash), e),
+            }
+        }
+    }
+
+    /// Trace one transaction. `callTracer` output is drawn as an indented
+    /// call tree; other tracers are printed as the node returns them.
+    pub async fn show_transaction_trace(&self, tx_hash: &str, tracer: &str) -> Result<()> {
+        let hash = tx_hash.parse::<B256>()
+            .map_err(|_| ExtractorError::InvalidInput(format!("Invalid transaction hash {}", tx_hash)))?;
+        let trace = self.client.trace_transaction(hash, tracer).await?;
+        if self.emit_structured(&trace)? {
+            return Ok(());
+        }
+
+        self.banner(format!("🔬 Transaction Trace ({}, {})", format_b256_hash(&hash), tracer));
+        if tracer != "callTracer" {
+            println!("{}", serde_json::to_string_pretty(&trace)?);
+            return Ok(());
+        }
+
+        let root: CallFrame = serde_json::from_value(trace)
+            .map_err(|e| color_eyre::eyre::eyre!("Unexpected callTracer output: {}", e))?;
+        let frames = root.flatten();
+        for (depth, frame) in &frames {
+            let reason = frame.error.as_ref().map(|error| self.revert_reason(frame, error));
+            println!("{}", call_frame_line(*depth, frame, reason.as_deref()));
+        }
+
+        let reverted = frames.iter().filter(|(_, frame)| frame.error.is_some()).count();
+        println!("\n  Calls: {} ({} reverted)", frames.len(), reverted);
+        Ok(())
+    }
+
+    /// Why a traced call failed: the node's decoded reason, else the revert
+    /// data explained against loaded ABIs, else the bare error
+    fn revert_reason(&self, frame: &CallFrame, error: &str) -> String {
+        if let Some(reason) = &frame.revert_reason {
+            return format!("{}: {}", error, reason);
+        }
+        match &frame.output {
+            Some(output) if !output.is_empty() => format!("{}: {}", error, self.abis.explain_revert(frame.to.as_ref(), output)),
+            _ => error.to_string(),
+        }
+    }
+
+    /// Rank fee recipients over a block range by blocks produced, with the
+    /// gas they included and the priority fees they collected
+    pub async fn show_miner_stats(&self, from_block: u64, to_block: Option<u64>, top: usize, resolve_names: bool) -> Result<()> {
+        let to_block = match to_block {
+            Some(n) => n,
+            None => self.client.get_latest_block_number().await?,
+        };
+        check_scan_range(from_block, to_block)?;
+
+        let mut tally = ErrorTally::new(self.error_policy);
+        let mut producers: HashMap<Address, ProducerStats> = HashMap::new();
+        for (block_num, block) in self.client.get_block_headers(from_block, to_block).await {
+            let Some(block) = tally.check(format!("block {}", block_num), block)? else {
+                continue;
+            };
+            let tips = self.client.get_priority_fees(&block).await;
+            let Some(tips) = tally.check(format!("block {} receipts", block_num), tips)? else {
+                continue;
+            };
+            let stats = producers.entry(block.miner).or_default();
+            stats.blocks += 1;
+            stats.gas_used += block.gas_used.saturating_to::<u64>();
+            stats.tips += tips;
+        }
+
+        let total_blocks: usize = producers.values().map(|stats| stats.blocks).sum();
+        let mut ranked: Vec<_> = producers.into_iter().collect();
+        ranked.sort_by(|(_, a), (_, b)| b.blocks.cmp(&a.blocks).then(b.tips.cmp(&a.tips)));
+
+        if !self.structured() {
+            self.banner(format!("⛏️  Block Producers (Blocks {}..={})", from_block, to_block));
+        }
+        let mut rows = Vec::new();
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Rank", "Producer", "Blocks", "Share", "Gas Used", "Priority Fees"]);
+
+        for (rank, (address, stats)) in ranked.iter().take(top).enumerate() {
+            let name = if resolve_names {
+                self.client.lookup_ens_name(address).await.ok().flatten()
+            } else {
+                None
+            };
+            let producer = match &name {
+                Some(name) => format!("{} ({})", name, format_address(&address.to_checksum(None))),
+                None => format_eth_address(address),
+            };
+            rows.push(ProducerView { address: *address, name, blocks: stats.blocks, gas_used: stats.gas_used, priority_fees: stats.tips });
+            table.add_row(vec![
+                &(rank + 1).to_string(),
+                &producer,
+                &stats.blocks.to_string(),
+                &format!("{:.1}%", stats.blocks as f64 / total_blocks.max(1) as f64 * 100.0),
+                &self.format_gas(stats.gas_used, GasFormat::Human),
+                &format_wei_u256(&stats.tips),
+            ]);
+        }
+
+        if self.emit_structured(&rows)? {
+            return tally.finish();
+        }
+        println!("{}", table);
+        println!("\n{} distinct producers across {} blocks", ranked.len(), total_blocks);
+        tally.finish()
+    }
+
+    /// Follow the chain head, printing each new block and alerting on
+    /// transfers at or above `value_threshold`. Over WebSocket and IPC the
+    /// node pushes new heads; over HTTP the head is polled.
+    pub async fn watch(&self, value_threshold: Option<U256>, head_only: bool, poll_interval: Duration) -> Result<()> {
+        if poll_interval.is_zero() {
+            return Err(color_eyre::eyre::eyre!("Poll interval must be greater than zero"));
+        }
+        if matches!(self.output, OutputFormat::Json | OutputFormat::Yaml) {
+            return Err(ExtractorError::InvalidInput(
+                "watch streams one document per event; use --format ndjson".into()
+            ).into());
+        }
+        let mut heads = HeadSource::new(&self.client, poll_interval).await?;
+        if head_only {
+            return self.watch_head(&mut heads).await;
+        }
+
+        let ndjson = self.output == OutputFormat::Ndjson;
+        if !self.quiet && !ndjson {
+            println!("👀 Watching for new blocks ({})", heads.describe());
+            if let Some(threshold) = &value_threshold {
+                println!("🐋 Alerting on transfers of at least {}", format_wei_u256(threshold));
+            }
+            println!();
+        }
+
+        let mut last_seen = self.client.get_latest_block_number().await?;
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+
+        loop {
+            let latest = tokio::select! {
+                head = heads.next() => head,
+                _ = &mut ctrl_c => {
+                    if !ndjson {
+                        println!("\n👋 Stopped watching after block {}", last_seen);
+                    }
+                    return Ok(());
+                }
+            };
+            let latest = match latest {
+                Ok(head) => head.number.saturating_to::<u64>(),
+                Err(e) if ndjson => {
+                    eprintln!("⚠️  Failed to fetch latest block: {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    println!("⚠️  Failed to fetch latest block: {}", e);
+                    continue;
+                }
+            };
+            if latest <= last_seen {
+                continue;
+            }
+
+            // Don't fall further behind the head by replaying a long backlog
+            let start = last_seen.saturating_add(1).max(latest.saturating_sub(MAX_WATCH_CATCHUP - 1));
+            for block_num in start..=latest {
+                let block = if value_threshold.is_some() {
+                    self.client.get_block_by_number(block_num).await
+                } else {
+                    self.client.get_block_header_by_number(block_num).await
+                };
+                let block = match block {
+                    Ok(block) => block,
+                    Err(e) if ndjson => {
+                        eprintln!("⚠️  Failed to fetch block {}: {}", block_num, e);
+                        continue;
+                    }
+                    Err(e) => {
+                        println!("⚠️  Failed to fetch block {}: {}", block_num, e);
+                        continue;
+                    }
+                };
+
+                if ndjson {
+                    println!("{}", render(&WatchEvent::block(&block), self.output)?);
+                    if let Some(threshold) = &value_threshold {
+                        for tx in block.full_transactions.iter().filter(|tx| tx.value >= *threshold) {
+                            println!("{}", render(&WatchEvent::alert(tx), self.output)?);
+                        }
+                    }
+                    continue;
+                }
+                println!("🧱 Block {} | {} | {} txs | {} gas ({:.1}%)",
+                    block.number,
+                    format_block_hash(&block.hash),
+                    block.transactions.len(),
+                    self.format_gas(block.gas_used.saturating_to::<u64>(), GasFormat::Human),
+                    calculate_gas_utilization(block.gas_used.saturating_to::<u64>(), block.gas_limit.saturating_to::<u64>()));
+
+                if let Some(threshold) = &value_threshold {
+                    for tx in block.full_transactions.iter().filter(|tx| tx.value >= *threshold) {
+                        let to = tx.to.as_ref()
+                            .map_or("📄 Contract Creation".to_string(), format_eth_address);
+                        println!("   🐋 {} → {} {} ({})",
+                            format_eth_address(&tx.from), to, format_wei_u256(&tx.value), format_b256_hash(&tx.hash));
+                    }
+                }
+            }
+            last_seen = latest;
+        }
+    }
+
+    /// Tail the chain head from its headers alone: pushed ones, or one
+    /// header request per tick. Blocks produced between ticks are counted
+    /// rather than fetched.
+    async fn watch_head(&self, heads: &mut HeadSource<'_>) -> Result<()> {
+        let ndjson = self.output == OutputFormat::Ndjson;
+        if !self.quiet && !ndjson {
+            println!("👀 Watching the chain head ({}, headers only)\n", heads.describe());
+        }
+
+        let mut last_seen: Option<u64> = None;
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+
+        loop {
+            let head = tokio::select! {
+                head = heads.next() => head,
+                _ = &mut ctrl_c => {
+                    if !ndjson {
+                        println!("\n👋 Stopped watching");
+                    }
+                    return Ok(());
+                }
+            };
+            let head = match head {
+                Ok(head) => head,
+                Err(e) if ndjson => {
+                    eprintln!("⚠️  Failed to fetch latest block: {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    println!("⚠️  Failed to fetch latest block: {}", e);
+                    continue;
+                }
+            };
+            let number = head.number.saturating_to::<u64>();
+            if last_seen.is_some_and(|seen| number <= seen) {
+                continue;
+            }
+            if ndjson {
+                println!("{}", render(&WatchEvent::block(&head), self.output)?);
+                last_seen = Some(number);
+                continue;
+            }
+
+            println!("🧱 Block {} | {} | {} gas | {}",
+                number,
+                format_block_hash(&head.hash),
+                self.format_gas(head.gas_used.saturating_to::<u64>(), GasFormat::Human),
+                format_timestamp_u256(&head.timestamp));
+            if let Some(skipped) = last_seen.map(|seen| number - seen - 1).filter(|n| *n > 0) {
+                println!("   ⏩ {} block(s) since the last tick not shown", skipped);
+            }
+            last_seen = Some(number);
+        }
+    }
+
+    /// The inclusive block range `window` covers
+    async fn resolve_gas_window(&self, window: GasWindow) -> Result<(u64, u64)> {
+        window.range(self.client.get_latest_block_number().await?)
+    }
+
+    pub async fn show_gas_statistics(&self, window: GasWindow, summary_only: bool, csv: bool, concurrency: usize) -> Result<()> {
+        let mut tally = ErrorTally::new(self.error_policy);
+        let (from_block, to_block) = self.resolve_gas_window(window).await?;
+        if csv {
+            let stats = self.client.get_gas_statistics(from_block, to_block, concurrency, &mut tally).await?;
+            println!("{}", GAS_CSV_HEADER);
+            for sample in &stats.blocks {
+                println!("{}", gas_csv_row(sample));
+            }
+            return tally.finish();
+        }
+
+        let stats = self.client.get_gas_statistics(from_block, to_block, concurrency, &mut tally).await?;
+        if self.emit_csv(&[GasSummaryRow::from(&stats)])? {
+            return tally.finish();
+        }
+        if self.emit_structured(&stats)? {
+            return tally.finish();
+        }
+
+        self.banner(format!("⛽ Gas Statistics ({})", window.title()));
+        
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Metric", "Value"]);
+        
+        table.add_row(vec!["Average Gas Used", &self.format_gas(stats.avg_gas_used, GasFormat::Raw)]);
+        table.add_row(vec!["Average Gas Price", &format!("{} Gwei", stats.avg_gas_price / 1_000_000_000)]);
+        table.add_row(vec!["Max Gas Used", &self.format_gas(stats.max_gas_used, GasFormat::Raw)]);
+        table.add_row(vec!["Min Gas Used", &self.format_gas(stats.min_gas_used, GasFormat::Raw)]);
+        table.add_row(vec!["Gas Utilization", &format!("{:.1}%", stats.gas_utilization)]);
+        table.add_row(vec!["Blocks Analyzed", &stats.blocks_analyzed.to_string()]);
+        
+        println!("{}", table);
+
+        if !summary_only {
+            // Show gas usage trend visualization
+            println!("\n📈 Gas Usage Trend:");
+            self.show_gas_trend(&stats);
+        }
+        
+        tally.finish()
+    }
+    
+    /// Base fee and priority-fee percentiles per block from
+    /// `eth_feeHistory`, with suggested EIP-1559 fee caps
+    pub async fn show_fee_history(&self, blocks: u64, percentiles: &[f64]) -> Result<()> {
+        let history = self.client.get_fee_history(blocks, percentiles).await?;
+        let suggested = history.suggested_fees();
+        let view = FeeHistoryView {
+            history: &history,
+            suggested_max_fee_per_gas: suggested.map(|(max_fee, _)| max_fee),
+            suggested_max_priority_fee_per_gas: suggested.map(|(_, tip)| tip),
+        };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        self.banner(format!("💰 Fee History (Last {} Blocks)", history.blocks.len()));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        let mut header = vec!["Block #".to_string(), "Base Fee".to_string(), "Gas Used".to_string()];
+        header.extend(history.percentiles.iter().map(|p| format!("P{} Tip", p)));
+        table.set_header(header);
+
+        for block in &history.blocks {
+            let mut row = vec![
+                block.number.to_string(),
+                format_gas_price(&block.base_fee),
+                format!("{:.1}%", block.gas_used_ratio * 100.0),
+            ];
+            row.extend(block.rewards.iter().map(format_gas_price));
+            table.add_row(row);
+        }
+        println!("{}", table);
+
+        println!("\nNext block base fee: {}", format_gas_price(&history.next_base_fee));
+        match suggested {
+            Some((max_fee, tip)) => {
+                println!("Suggested maxPriorityFeePerGas: {}", format_gas_price(&tip));
+                println!("Suggested maxFeePerGas: {}", format_gas_price(&max_fee));
+            }
+            None => println!("Pass --percentiles to get suggested fee caps"),
+        }
+        Ok(())
+    }
+
+    /// Snapshot of the node's transaction pool: pending and queued counts,
+    /// the senders with the most pending transactions and what pending
+    /// transactions bid for gas
+    pub async fn show_mempool(&self, top: usize) -> Result<()> {
+        let status = self.client.get_txpool_status().await?;
+        let content = self.client.get_txpool_content().await?;
+        let senders = top_senders(&content.pending, top);
+        let queued_from = |sender: &Address| content.queued.iter().filter(|tx| tx.from == *sender).count();
+        let mut gas_prices: Vec<U256> = content.pending.iter().map(|tx| tx.gas_price).collect();
+        gas_prices.sort_unstable();
+
+        let view = MempoolView {
+            status,
+            top_senders: senders.iter()
+                .map(|(address, pending)| SenderCount { address: *address, pending: *pending, queued: queued_from(address) })
+                .collect(),
+            gas_price_percentiles: MEMPOOL_PERCENTILES.iter()
+                .filter_map(|(label, pct)| Some(GasPricePercentile { percentile: label, gas_price: percentile(&gas_prices, *pct)? }))
+                .collect(),
+        };
+        if self.emit_structured(&view)? {
+            return Ok(());
+        }
+
+        self.banner("🧺 Transaction Pool".to_string());
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Property", "Value"]);
+        table.add_row(vec!["Pending", &format_number(status.pending)]);
+        table.add_row(vec!["Queued", &format_number(status.queued)]);
+        table.add_row(vec!["Senders with Pending", &format_number(top_senders(&content.pending, usize::MAX).len() as u64)]);
+        println!("{}", table);
+        if content.pending.is_empty() {
+            println!("\nNo pending transactions");
+            return Ok(());
+        }
+
+        println!("\n🏆 Top {} Senders by Pending Transactions:", senders.len());
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Sender", "Pending", "Queued"]);
+        for sender in &view.top_senders {
+            table.add_row(vec![
+                format_eth_address_full(&sender.address),
+                format_number(sender.pending as u64),
+                format_number(sender.queued as u64),
+            ]);
+        }
+        println!("{}", table);
+
+        println!("\n💰 Pending Gas Price Bids:");
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Percentile", "Gas Price"]);
+        for row in &view.gas_price_percentiles {
+            table.add_row(vec![row.percentile.to_string(), format_gas_price(&row.gas_price)]);
+        }
+        println!("{}", table);
+
+        println!("\n📊 Gas Price Distribution:");
+        let prices: Vec<u64> = gas_prices.iter().map(|price| price.saturating_to()).collect();
+        let buckets = histogram(&prices, TX_GAS_HISTOGRAM_BUCKETS);
+        let largest = buckets.iter().map(|(_, count)| *count).max().unwrap_or(1).max(1);
+        for (lower, count) in buckets {
+            let bar = "█".repeat(count * HISTOGRAM_BAR_WIDTH / largest);
+            println!("  ≥ {:>12} │{} {}", format_gas_price(&U256::from(lower)), bar, count);
+        }
+        Ok(())
+    }
+
+    /// Gas statistics over the individual transactions in the window rather
+    /// than over whole blocks
+    pub async fn show_tx_gas_statistics(&self, window: GasWindow, top: usize, concurrency: usize) -> Result<()> {
+        let mut tally = ErrorTally::new(self.error_policy);
+        let (from_block, to_block) = self.resolve_gas_window(window).await?;
+        let (blocks_analyzed, mut samples) = self.client.get_tx_gas_samples(from_block, to_block, concurrency, &mut tally).await?;
+        if self.emit_csv(&samples)? {
+            return tally.finish();
+        }
+        if self.emit_structured(&TxGasView { blocks_analyzed, transactions: &samples })? {
+            return tally.finish();
+        }
+
+        self.banner(format!("⛽ Per-Transaction Gas Statistics ({})", window.title()));
+        println!("Transactions analyzed: {} across {} blocks\n", format_number(samples.len() as u64), blocks_analyzed);
+        if samples.is_empty() {
+            return tally.finish();
+        }
+
+        let mut gas_used: Vec<u64> = samples.iter().map(|tx| tx.gas_used).collect();
+        gas_used.sort_unstable();
+        let mut gas_prices: Vec<U256> = samples.iter().map(|tx| tx.gas_price).collect();
+        gas_prices.sort_unstable();
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["Percentile", "Gas Used", "Gas Price"]);
+        for (label, pct) in [("Min", 0.0), ("P10", 10.0), ("P25", 25.0), ("Median", 50.0), ("P75", 75.0), ("P90", 90.0), ("P99", 99.0), ("Max", 100.0)] {
+            // Both lists are non-empty, so every percentile exists
+            let used = percentile(&gas_used, pct).unwrap_or_default();
+            let price = percentile(&gas_prices, pct).unwrap_or_default();
+            table.add_row(vec![label.to_string(), self.format_gas(used, GasFormat::Raw), format_gas_price(&price)]);
+        }
+        println!("{}", table);
+
+        println!("\n📊 Gas Used Distribution:");
+        let buckets = histogram(&gas_used, TX_GAS_HISTOGRAM_BUCKETS);
+        let largest = buckets.iter().map(|(_, count)| *count).max().unwrap_or(1).max(1);
+        for (lower, count) in buckets {
+            let bar = "█".repeat(count * HISTOGRAM_BAR_WIDTH / largest);
+            println!("  ≥ {:>12} │{} {}", self.format_gas(lower, GasFormat::Raw), bar, count);
+        }
+
+        let total_gas: u64 = gas_used.iter().sum();
+        samples.sort_by_key(|tx| std::cmp::Reverse(tx.gas_used));
+        let top_samples = &samples[..top.min(samples.len())];
+        let top_gas: u64 = top_samples.iter().map(|tx| tx.gas_used).sum();
+        println!("\n🏆 Top {} transactions used {:.1}% of all gas:",
+            top_samples.len(), calculate_gas_utilization(top_gas, total_gas));
+        for tx in top_samples {
+            println!("  {} block {} {}", format_b256_hash(&tx.hash), tx.block_number, self.format_gas(tx.gas_used, GasFormat::Raw));
+        }
+
+        tally.finish()
+    }
+
+    /// Sparkline of gas used per analyzed block, oldest on the left
+    fn show_gas_trend(&self, stats: &GasStatistics) {
+        let (Some(first), Some(last)) = (stats.blocks.first(), stats.blocks.last()) else {
+            return;
+        };
+        let gas_used: Vec<u64> = stats.blocks.iter().map(|block| block.gas_used).collect();
+        let width = Table::new().width().map_or(SPARKLINE_FALLBACK_WIDTH, usize::from);
+        let line = sparkline(&gas_used, width);
+        println!("{}", line);
+
+        let (first, last) = (first.number.to_string(), last.number.to_string());
+        let padding = line.chars().count().saturating_sub(first.len() + last.len()).max(1);
+        println!("{}{}{}", first, " ".repeat(padding), last);
+        println!(
+            "Gas used per block: {} to {}",
+            self.format_gas(stats.min_gas_used, GasFormat::Human),
+            self.format_gas(stats.max_gas_used, GasFormat::Human)
+        );
+    }
+}
+
+/// Width of the gas trend sparkline when the terminal's can't be detected
+const SPARKLINE_FALLBACK_WIDTH: usize = 80;
+
+/// Buckets in the `gas --per-tx` histogram, and the width of its longest bar
+const TX_GAS_HISTOGRAM_BUCKETS: usize = 10;
+const HISTOGRAM_BAR_WIDTH: usize = 40;
+/// Percentiles of pending gas price bids shown by `mempool`
+const MEMPOOL_PERCENTILES: [(&str, f64); 6] = [("Min", 0.0), ("P25", 25.0), ("Median", 50.0), ("P75", 75.0), ("P90", 90.0), ("Max", 100.0)];
+
+/// How `logs` fetches and presents its results
+#[derive(Debug, Clone)]
+pub struct LogsOptions {
+    /// Blocks per `eth_getLogs` request
+    pub chunk_size: u64,
+    /// Only count matching logs instead of listing them
+    pub count_only: bool,
+    /// With `count_only`, also show the count for each chunk
+    pub per_chunk: bool,
+    pub sort_by: LogSort,
+    pub reverse: bool,
+    /// Skip blocks whose logs bloom rules out a match
+    pub bloom_prefilter: bool,
+}
+
+/// A block number given as a block id
+fn parse_block_number(block_id: &str) -> Result<u64> {
+    block_id.parse::<u64>()
+        .map_err(|_| ExtractorError::InvalidInput(format!(
+            "Invalid block {} (expected a number, hash, or latest/earliest/pending/safe/finalized)", block_id
+        )).into())
+}
+
+/// Where `watch` learns about new heads: a `newHeads` subscription when
+/// the transport carries one, otherwise a poll every interval. Polling
+/// also takes over if the node ends the subscription.
+struct HeadSource<'a> {
+    client: &'a RethClient,
+    subscription: Option<BoxStream<'a, Result<Block, RethClientError>>>,
+    interval: tokio::time::Interval,
+    period: Duration,
+}
+
+impl<'a> HeadSource<'a> {
+    async fn new(client: &'a RethClient, period: Duration) -> Result<Self> {
+        Ok(Self {
+            client,
+            subscription: client.subscribe_new_heads().await?,
+            interval: tokio::time::interval(period),
+            period,
+        })
+    }
+
+    /// The next head's header
+    async fn next(&mut self) -> Result<Block> {
+        if let Some(subscription) = &mut self.subscription {
+            match subscription.next().await {
+                Some(head) => return Ok(head?),
+                None => {
+                    eprintln!("⚠️  The node ended the newHeads subscription, polling every {} instead", format_duration(self.period));
+                    self.subscription = None;
+                }
+            }
+        }
+        self.interval.tick().await;
+        Ok(self.client.get_latest_block_header().await?)
+    }
+
+    fn describe(&self) -> String {
+        match self.subscription {
+            Some(_) => "subscribed to newHeads".to_string(),
+            None => format!("polling every {}", format_duration(self.period)),
+        }
+    }
+}
+
+/// One line of `watch --format ndjson`: a new block, or a transfer at or
+/// above `--value-threshold` within it
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum WatchEvent {
+    Block {
+        #[serde(serialize_with = "serialize_u256")]
+        number: U256,
+        hash: Option<B256>,
+        #[serde(serialize_with = "serialize_u256")]
+        timestamp: U256,
+        transactions: usize,
+        #[serde(serialize_with = "serialize_u256")]
+        gas_used: U256,
+        #[serde(serialize_with = "serialize_u256")]
+        gas_limit: U256,
+    },
+    Alert {
+        #[serde(serialize_with = "serialize_opt_u256")]
+        block_number: Option<U256>,
+        hash: B256,
+        from: Address,
+        /// `None` for a contract creation
+        to: Option<Address>,
+        #[serde(serialize_with = "serialize_u256")]
+        value: U256,
+    },
+}
+
+impl WatchEvent {
+    fn block(block: &Block) -> Self {
+        WatchEvent::Block {
+            number: block.number,
+            hash: block.hash,
+            timestamp: block.timestamp,
+            transactions: block.transactions.len(),
+            gas_used: block.gas_used,
+            gas_limit: block.gas_limit,
+        }
+    }
+
+    fn alert(tx: &Transaction) -> Self {
+        WatchEvent::Alert { block_number: tx.block_number, hash: tx.hash, from: tx.from, to: tx.to, value: tx.value }
+    }
+}
+
+/// The blocks `gas` analyzes
+#[derive(Debug, Clone, Copy)]
+pub enum GasWindow {
+    /// This many blocks, ending at the head
+    Latest(usize),
+    /// An explicit range; the end defaults to the head
+    Range { from_block: u64, to_block: Option<u64> },
+}
+
+impl GasWindow {
+    /// The inclusive block range covered when the head is `latest`. A range
+    /// reaching past the head is invalid input rather than a scan in which
+    /// every missing block fails.
+    fn range(&self, latest: u64) -> Result<(u64, u64)> {
+        match *self {
+            GasWindow::Latest(0) => Err(ExtractorError::InvalidInput("--blocks must be at least 1".into()).into()),
+            GasWindow::Latest(blocks) => Ok((latest.saturating_sub(blocks as u64 - 1), latest)),
+            GasWindow::Range { to_block: Some(to_block), .. } if to_block > latest => Err(ExtractorError::InvalidInput(
+                format!("--to-block {} is past the latest block {}", to_block, latest),
+            ).into()),
+            GasWindow::Range { from_block, to_block } => Ok((from_block, to_block.unwrap_or(latest))),
+        }
+    }
+
+    /// Heading for the statistics tables
+    fn title(&self) -> String {
+        match self {
+            GasWindow::Latest(blocks) => format!("Last {} Blocks", blocks),
+            GasWindow::Range { from_block, to_block: Some(to_block) } => format!("Blocks {}..={}", from_block, to_block),
+            GasWindow::Range { from_block, to_block: None } => format!("Blocks {}..=latest", from_block),
+        }
+    }
+}
+
+/// What `block` shows beyond the header table
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlockOptions {
+    /// Only list transactions moving at least this much
+    pub min_value: Option<U256>,
+    /// Print the total value moved and the number of contract creations
+    pub value_sum: bool,
+    pub show_logs_bloom: bool,
+    pub diff_parent: bool,
+    /// Also list the block's uncles
+    pub verbose: bool,
+    /// List this many transactions using the most gas
+    pub top_gas: Option<usize>,
+}
+
+/// `block` in structured output, with `--top-gas` when asked for
+#[derive(Serialize)]
+struct BlockView<'a> {
+    #[serde(flatten)]
+    block: &'a Block,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_gas: Option<&'a [GasConsumer]>,
+}
+
+/// One of a block's heaviest transactions by gas used
+#[derive(Debug, Clone, Serialize)]
+struct GasConsumer {
+    hash: B256,
+    from: Address,
+    to: Option<Address>,
+    gas_used: u64,
+    /// Share of the gas used by the whole block
+    share_pct: f64,
+}
+
+/// The `count` receipts using the most gas, heaviest first
+fn top_gas_consumers(receipts: &[Receipt], block_gas_used: U256, count: usize) -> Vec<GasConsumer> {
+    let block_gas_used = block_gas_used.saturating_to::<u64>();
+    let mut consumers: Vec<GasConsumer> = receipts.iter()
+        .map(|receipt| {
+            let gas_used = receipt.gas_used.saturating_to::<u64>();
+            GasConsumer {
+                hash: receipt.transaction_hash,
+                from: receipt.from,
+                to: receipt.to,
+                gas_used,
+                share_pct: if block_gas_used == 0 { 0.0 } else { gas_used as f64 * 100.0 / block_gas_used as f64 },
+            }
+        })
+        .collect();
+    consumers.sort_by_key(|consumer| std::cmp::Reverse(consumer.gas_used));
+    consumers.truncate(count);
+    consumers
+}
+
+/// Schema name and version of `transaction --summary-json`. Bump the
+/// version when a field is removed or changes meaning.
+const TRANSACTION_SUMMARY_SCHEMA: &str = "eth_data_extractor/transaction-summary";
+const TRANSACTION_SUMMARY_VERSION: u32 = 1;
+
+/// Everything known about one transaction, for `transaction --summary-json`
+#[derive(Debug, Serialize)]
+struct TransactionSummary {
+    schema: &'static str,
+    version: u32,
+    chain_id: u64,
+    transaction: Transaction,
+    /// The node's receipt, passed through untouched; null while pending
+    receipt: serde_json::Value,
+    /// Header of the containing block; absent while the transaction is pending
+    block: Option<BlockHeaderSummary>,
+    call: Option<DecodedSummary>,
+    events: Vec<DecodedSummary>,
+    token_transfers: Vec<TokenTransfer>,
+}
+
+/// Block header fields, without the block's transaction list
+#[derive(Debug, Serialize)]
+struct BlockHeaderSummary {
+    number: u64,
+    hash: Option<B256>,
+    parent_hash: B256,
+    #[serde(serialize_with = "serialize_u256")]
+    timestamp: U256,
+    #[serde(serialize_with = "serialize_u256")]
+    gas_used: U256,
+    #[serde(serialize_with = "serialize_u256")]
+    gas_limit: U256,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    base_fee_per_gas: Option<U256>,
+    miner: Address,
+    transaction_count: usize,
+}
+
+impl From<&Block> for BlockHeaderSummary {
+    fn from(block: &Block) -> Self {
+        Self {
+            number: block.number.saturating_to::<u64>(),
+            hash: block.hash,
+            parent_hash: block.parent_hash,
+            timestamp: block.timestamp,
+            gas_used: block.gas_used,
+            gas_limit: block.gas_limit,
+            base_fee_per_gas: block.base_fee_per_gas,
+            miner: block.miner,
+            transaction_count: block.transactions.len(),
+        }
+    }
+}
+
+/// Decoded parameters as `name: value` pairs on one line
+fn format_decoded_params(decoded: &Decoded) -> String {
+    decoded.params.iter()
+        .map(|(name, value)| format!("{}: {}", name, format_sol_value(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A log in `logs` structured output when an ABI is loaded, with the event
+/// it decodes to, if any
+#[derive(Serialize)]
+struct DecodedLogView<'a> {
+    #[serde(flatten)]
+    log: &'a Log,
+    event: Option<DecodedSummary>,
+}
+
+/// A decoded call or event with its values rendered as strings
+#[derive(Debug, Serialize)]
+struct DecodedSummary {
+    /// Emitting contract, for events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<Address>,
+    signature: String,
+    params: Vec<DecodedParam>,
+}
+
+#[derive(Debug, Serialize)]
+struct DecodedParam {
+    name: String,
+    value: String,
+}
+
+impl From<&Decoded> for DecodedSummary {
+    fn from(decoded: &Decoded) -> Self {
+        Self {
+            address: None,
+            signature: decoded.signature.clone(),
+            params: decoded.params.iter()
+                .map(|(name, value)| DecodedParam { name: name.clone(), value: format_sol_value(value) })
+                .collect(),
+        }
+    }
+}
+
+/// One compared field of `diff`, already formatted for both blocks
+struct DiffRow {
+    property: &'static str,
+    a: String,
+    b: String,
+    differs: bool,
+}
+
+/// The fields `diff` compares. Timestamps differ by a delta, shown next
+/// to block B's.
+fn diff_rows(a: &Block, b: &Block) -> Vec<DiffRow> {
+    let row = |property, a: String, b: String| DiffRow { differs: a != b, property, a, b };
+    let base_fee = |block: &Block| block.base_fee_per_gas.as_ref().map_or("-".to_string(), format_gas_price);
+    let seconds = b.timestamp.saturating_to::<u64>() as i64 - a.timestamp.saturating_to::<u64>() as i64;
+
+    let mut timestamp = row("Timestamp", format_timestamp_u256(&a.timestamp), format_timestamp_u256(&b.timestamp));
+    if timestamp.differs {
+        timestamp.b = format!("{} ({:+}s)", timestamp.b, seconds);
+    }
+    vec![
+        row("Number", a.number.to_string(), b.number.to_string()),
+        row("Hash", format_block_hash(&a.hash), format_block_hash(&b.hash)),
+        row("Parent Hash", format_b256_hash(&a.parent_hash), format_b256_hash(&b.parent_hash)),
+        timestamp,
+        row("Miner", format_eth_address_full(&a.miner), format_eth_address_full(&b.miner)),
+        row("Gas Used", format_number_u256(&a.gas_used), format_number_u256(&b.gas_used)),
+        row("Gas Limit", format_number_u256(&a.gas_limit), format_number_u256(&b.gas_limit)),
+        row("Base Fee", base_fee(a), base_fee(b)),
+        row("Transactions", a.transactions.len().to_string(), b.transactions.len().to_string()),
+        row("Size", format_number_u256(&a.size), format_number_u256(&b.size)),
+    ]
+}
+
+/// Transactions in `a` but not `b`, and in `b` but not `a`, each in block
+/// order
+fn tx_set_difference(a: &Block, b: &Block) -> (Vec<B256>, Vec<B256>) {
+    let in_a: std::collections::HashSet<&B256> = a.transactions.iter().collect();
+    let in_b: std::collections::HashSet<&B256> = b.transactions.iter().collect();
+    (
+        a.transactions.iter().filter(|hash| !in_b.contains(hash)).copied().collect(),
+        b.transactions.iter().filter(|hash| !in_a.contains(hash)).copied().collect(),
+    )
+}
+
+/// `diff` in structured output
+#[derive(Serialize)]
+struct BlockDiffView<'a> {
+    a: &'a Block,
+    b: &'a Block,
+    differing_fields: Vec<&'static str>,
+    only_in_a: &'a [B256],
+    only_in_b: &'a [B256],
+}
+
+/// Differences from a parent block to its child, for `block --diff-parent`
+#[derive(Debug, PartialEq)]
+struct BlockDelta {
+    seconds: i64,
+    /// Base fee change in percent; `None` unless both blocks have one
+    base_fee_pct: Option<f64>,
+    /// Where EIP-1559 pushes the base fee, judged by how full the parent
+    /// was against its target of half the gas limit
+    expected_base_fee: Ordering,
+    gas_used: i64,
+    tx_count: i64,
+}
+
+impl BlockDelta {
+    fn between(parent: &Block, block: &Block) -> Self {
+        let gas = |value: U256| value.saturating_to::<u64>() as i64;
+        let base_fee_pct = match (parent.base_fee_per_gas, block.base_fee_per_gas) {
+            (Some(old), Some(new)) if !old.is_zero() => {
+                let (old, new) = (old.saturating_to::<u128>() as f64, new.saturating_to::<u128>() as f64);
+                Some((new - old) / old * 100.0)
+            }
+            _ => None,
+        };
+        Self {
+            seconds: gas(block.timestamp) - gas(parent.timestamp),
+            base_fee_pct,
+            expected_base_fee: parent.gas_used.cmp(&(parent.gas_limit / U256::from(2))),
+            gas_used: gas(block.gas_used) - gas(parent.gas_used),
+            tx_count: block.transactions.len() as i64 - parent.transactions.len() as i64,
+        }
+    }
+}
+
+/// The `block` details table. Header fields are U256 on the wire and
+/// pre-merge difficulty (or anything on an exotic chain) can exceed a u64,
+/// so nothing here may panic on large values.
+fn block_table(block: &Block, gas_format: Option<GasFormat>, chain_id: u64) -> Table {
+    let gas_used = block.gas_used.saturating_to::<u64>();
+    let gas_limit = block.gas_limit.saturating_to::<u64>();
+    let gas_format = gas_format.unwrap_or(GasFormat::Raw);
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Property", "Value"]);
+
+    table.add_row(vec!["Block Number", &block.number.to_string()]);
+    table.add_row(vec!["Block Hash", &format_block_hash(&block.hash)]);
+    table.add_row(vec!["Parent Hash", &format_b256_hash(&block.parent_hash)]);
+    table.add_row(vec!["Timestamp", &format_timestamp_u256(&block.timestamp)]);
+    table.add_row(vec!["Time Ago", &time_ago(block.timestamp.saturating_to::<u64>())]);
+    table.add_row(vec!["Gas Used", &format_gas_u256(&block.gas_used, gas_format)]);
+    table.add_row(vec!["Gas Limit", &format_gas_u256(&block.gas_limit, gas_format)]);
+    table.add_row(vec!["Gas Utilization", &format!("{:.1}%", calculate_gas_utilization(gas_used, gas_limit))]);
+    if let Some(base_fee) = &block.base_fee_per_gas {
+        table.add_row(vec!["Base Fee", &format_gas_price(base_fee)]);
+        table.add_row(vec!["Burned", &format_wei_u256(&base_fee.saturating_mul(block.gas_used))]);
+    }
+    if let (Some(blob_gas_used), Some(blobs)) = (block.blob_gas_used, block.blob_count()) {
+        table.add_row(vec!["Blob Gas Used", &format!("{} ({} blobs)", format_number(blob_gas_used), blobs)]);
+    }
+    if let Some(excess) = block.excess_blob_gas {
+        table.add_row(vec!["Excess Blob Gas", &format_number(excess)]);
+    }
+    if let Some(blob_fee) = block.blob_base_fee(chain_id) {
+        table.add_row(vec![
+            "Blob Base Fee",
+            &format!("{} ({} per blob)", format_blob_gas_price(&blob_fee), format_wei_u256(&blob_fee.saturating_mul(U256::from(GAS_PER_BLOB)))),
+        ]);
+    }
+    table.add_row(vec!["Transactions", &block.transactions.len().to_string()]);
+    table.add_row(vec!["Uncles", &block.uncles.len().to_string()]);
+    table.add_row(vec!["Miner", &format_eth_address_full(&block.miner)]);
+    table.add_row(vec!["Difficulty", &format_number_u256(&block.difficulty)]);
+    table.add_row(vec!["Size", &format!("{} bytes", format_number_u256(&block.size))]);
+    table
+}
+
+/// A blob gas price, in wei while it sits near the 1 wei minimum, where
+/// gwei would round it away
+fn format_blob_gas_price(price: &U256) -> String {
+    if *price < U256::from(1_000_000u64) {
+        format!("{} wei", price)
+    } else {
+        format_gas_price(price)
+    }
+}
+
+/// A block's validator withdrawals, one row each
+fn withdrawals_table(withdrawals: &[Withdrawal]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Index", "Validator", "Address", "Amount"]);
+    for withdrawal in withdrawals {
+        table.add_row(vec![
+            withdrawal.index.to_string(),
+            withdrawal.validator_index.to_string(),
+            format_eth_address_full(&withdrawal.address),
+            format_wei_u256(&withdrawal.amount_wei()),
+        ]);
+    }
+    table
+}
+
+/// `creation` in structured output, with the creation block's timestamp
+#[derive(Serialize)]
+struct CreationView<'a> {
+    #[serde(flatten)]
+    creation: &'a ContractCreation,
+    timestamp: u64,
+}
+
+/// `account` in structured output, with `--pending` nonces when asked for
+#[derive(Serialize)]
+struct AccountView<'a> {
+    #[serde(flatten)]
+    account: &'a Account,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pending_nonces: Option<&'a PendingNonces>,
+}
+
+/// The mined and pending transaction counts of an account. A gap means
+/// transactions the node holds that haven't been mined yet.
+#[derive(Serialize)]
+struct PendingNonces {
+    latest: u64,
+    pending: u64,
+    gap: u64,
+}
+
+/// The lookups behind `account` with several addresses
+struct AccountBalances {
+    /// Richest first
+    accounts: Vec<Account>,
+    /// Addresses as given whose lookup failed
+    failed: Vec<String>,
+    total: U256,
+}
+
+/// `account` with several addresses in structured output
+#[derive(Serialize)]
+struct AccountsView<'a> {
+    /// `None` for the latest block
+    block: Option<u64>,
+    /// Richest first
+    accounts: &'a [Account],
+    /// Addresses as given whose lookup failed
+    failed: &'a [String],
+    #[serde(serialize_with = "serialize_u256")]
+    total: U256,
+}
+
+/// `code` in structured output
+#[derive(Serialize)]
+struct CodeView<'a> {
+    address: Address,
+    /// `None` for the latest block
+    block: Option<u64>,
+    size: usize,
+    code_hash: B256,
+    code: &'a Bytes,
+}
+
+/// `storage` in structured output
+#[derive(Serialize)]
+struct StorageView {
+    address: Address,
+    /// `None` for the latest block
+    block: Option<u64>,
+    slot: B256,
+    value: B256,
+    #[serde(serialize_with = "serialize_u256")]
+    as_uint: U256,
+    /// The low 20 bytes of `value`
+    as_address: Address,
+}
+
+/// `call` in structured output. The interpretations are only given for a
+/// successful call returning a single word that fits them.
+#[derive(Serialize)]
+struct CallView<'a> {
+    to: Address,
+    from: Option<Address>,
+    /// `None` for the latest block
+    block: Option<u64>,
+    success: bool,
+    /// Return data, or revert data when the call failed
+    output: &'a Bytes,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    as_uint: Option<U256>,
+    as_address: Option<Address>,
+    as_bool: Option<bool>,
+    revert_reason: Option<&'a str>,
+    /// Return values, when an ABI knows the called function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decoded: Option<DecodedSummary>,
+}
+
+/// `token-balance` in structured output
+#[derive(Serialize)]
+struct TokenBalanceView {
+    token: Address,
+    holder: Address,
+    /// `None` for the latest block
+    block: Option<u64>,
+    symbol: Option<String>,
+    decimals: Option<u8>,
+    /// Raw amount in the token's smallest unit
+    #[serde(serialize_with = "serialize_u256")]
+    balance: U256,
+    /// `balance` scaled by `decimals`, when the token reports them
+    formatted: Option<String>,
+}
+
+/// `genesis` in structured output
+#[derive(Serialize)]
+struct GenesisView<'a> {
+    /// Network the genesis hash belongs to, if known
+    network: Option<&'static str>,
+    block: &'a Block,
+}
+
+/// `replay-tx` in structured output
+#[derive(Serialize)]
+struct ReplayView {
+    hash: B256,
+    /// The replay ran against the state after this block
+    state_block: u64,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    original_status: Option<U256>,
+    reverted: bool,
+    /// Return data, or revert data when `reverted`
+    output: Bytes,
+    revert_reason: Option<String>,
+    /// Whether the replay succeeded or failed like the original did
+    matches_original: bool,
+}
+
+/// `latest` in structured output, newest block first
+#[derive(Serialize)]
+struct LatestBlocksView<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pending: Option<&'a Block>,
+    blocks: &'a [Block],
+}
+
+/// One `latest --format csv` row
+#[derive(Serialize, Default)]
+struct LatestBlockRow {
+    #[serde(serialize_with = "serialize_u256")]
+    number: U256,
+    hash: Option<B256>,
+    tx_count: usize,
+    #[serde(serialize_with = "serialize_u256")]
+    gas_used: U256,
+    #[serde(serialize_with = "serialize_u256")]
+    gas_limit: U256,
+    #[serde(serialize_with = "serialize_u256")]
+    timestamp: U256,
+}
+
+impl From<&Block> for LatestBlockRow {
+    fn from(block: &Block) -> Self {
+        Self {
+            number: block.number,
+            hash: block.hash,
+            tx_count: block.transactions.len(),
+            gas_used: block.gas_used,
+            gas_limit: block.gas_limit,
+            timestamp: block.timestamp,
+        }
+    }
+}
+
+/// The `gas --format csv` row: the summary metrics, without per-block samples
+#[derive(Serialize, Default)]
+struct GasSummaryRow {
+    blocks_analyzed: usize,
+    avg_gas_used: u64,
+    avg_gas_price: u64,
+    max_gas_used: u64,
+    min_gas_used: u64,
+    gas_utilization: f64,
+}
+
+impl From<&GasStatistics> for GasSummaryRow {
+    fn from(stats: &GasStatistics) -> Self {
+        Self {
+            blocks_analyzed: stats.blocks_analyzed,
+            avg_gas_used: stats.avg_gas_used,
+            avg_gas_price: stats.avg_gas_price,
+            max_gas_used: stats.max_gas_used,
+            min_gas_used: stats.min_gas_used,
+            gas_utilization: stats.gas_utilization,
+        }
+    }
+}
+
+/// `feehistory` in structured output
+#[derive(Serialize)]
+struct FeeHistoryView<'a> {
+    #[serde(flatten)]
+    history: &'a FeeHistory,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    suggested_max_fee_per_gas: Option<U256>,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    suggested_max_priority_fee_per_gas: Option<U256>,
+}
+
+/// `logs --count-only` in structured output
+#[derive(Serialize)]
+struct LogCountsView {
+    total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chunks: Option<Vec<ChunkCount>>,
+}
+
+#[derive(Serialize)]
+struct ChunkCount {
+    from_block: u64,
+    to_block: u64,
+    count: usize,
+}
+
+/// `logs --batch-accounts-from-logs` in structured output
+#[derive(Serialize)]
+struct HolderSnapshotView {
+    token: Address,
+    from_block: u64,
+    to_block: u64,
+    failed_reads: usize,
+    holders: Vec<HolderBalance>,
+}
+
+#[derive(Serialize)]
+struct HolderBalance {
+    holder: Address,
+    #[serde(serialize_with = "serialize_u256")]
+    balance: U256,
+}
+
+/// One row of `miner-stats` in structured output
+#[derive(Serialize)]
+struct ProducerView {
+    address: Address,
+    name: Option<String>,
+    blocks: usize,
+    gas_used: u64,
+    #[serde(serialize_with = "serialize_u256")]
+    priority_fees: U256,
+}
+
+/// `gas --per-tx` in structured output
+#[derive(Serialize)]
+struct TxGasView<'a> {
+    blocks_analyzed: usize,
+    transactions: &'a [TxGasSample],
+}
+
+/// `mempool` in structured output
+#[derive(Serialize)]
+struct MempoolView {
+    status: TxPoolStatus,
+    top_senders: Vec<SenderCount>,
+    gas_price_percentiles: Vec<GasPricePercentile>,
+}
+
+#[derive(Serialize)]
+struct SenderCount {
+    address: Address,
+    pending: usize,
+    /// Transactions from the same sender stuck behind a nonce gap
+    queued: usize,
+}
+
+#[derive(Serialize)]
+struct GasPricePercentile {
+    percentile: &'static str,
+    #[serde(serialize_with = "serialize_u256")]
+    gas_price: U256,
+}
+
+/// The `limit` senders with the most transactions in `transactions`, most
+/// first and by address among equals
+fn top_senders(transactions: &[PoolTransaction], limit: usize) -> Vec<(Address, usize)> {
+    let mut counts: HashMap<Address, usize> = HashMap::new();
+    for tx in transactions {
+        *counts.entry(tx.from).or_default() += 1;
+    }
+    let mut senders: Vec<_> = counts.into_iter().collect();
+    senders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    senders.truncate(limit);
+    senders
+}
+
+/// Per-producer totals for `miner-stats`
+#[derive(Debug, Default)]
+struct ProducerStats {
+    blocks: usize,
+    gas_used: u64,
+    tips: U256,
+}
+
+/// Column contract for `gas --csv-stdout`. Scripts depend on it, so columns
+/// may only ever be appended.
+const GAS_CSV_HEADER: &str = "block,timestamp,gas_used,gas_limit,utilization,base_fee,burned_eth,tx_count";
+
+/// One CSV line per block. Amounts are raw integers (wei, despite the
+/// `burned_eth` name) and utilization is a percentage; fields that don't
+/// exist before London are left empty.
+fn gas_csv_row(sample: &BlockGasSample) -> String {
+    format!("{},{},{},{},{:.2},{},{},{}",
+        sample.number,
+        sample.timestamp,
+        sample.gas_used,
+        sample.gas_limit,
+        calculate_gas_utilization(sample.gas_used, sample.gas_limit),
+        sample.base_fee.map_or(String::new(), |fee| fee.to_string()),
+        sample.burned().map_or(String::new(), |burned| burned.to_string()),
+        sample.tx_count)
+}
+
+/// One line of a call tree: the call type, from and to, any value moved,
+/// gas, and how the call ended
+fn call_frame_line(depth: usize, frame: &CallFrame, revert_reason: Option<&str>) -> String {
+    let indent = if depth == 0 { String::new() } else { format!("{}└─ ", "   ".repeat(depth - 1)) };
+    let to = frame.to.as_ref().map_or("📄 Contract Creation".to_string(), format_eth_address);
+    let mut line = format!("{}{} {} → {}", indent, frame.call_type, format_eth_address(&frame.from), to);
+    if let Some(value) = frame.value.filter(|value| !value.is_zero()) {
+        line.push_str(&format!("  {}", format_wei_u256(&value)));
+    }
+    match (&frame.gas, &frame.gas_used) {
+        (Some(gas), Some(used)) => line.push_str(&format!("  gas {} / {}", format_number_u256(used), format_number_u256(gas))),
+        (Some(gas), None) => line.push_str(&format!("  gas {}", format_number_u256(gas))),
+        _ => {}
+    }
+    match revert_reason {
+        Some(reason) => line.push_str(&format!("  ❌ {}", reason)),
+        None => line.push_str("  ✅"),
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, rpc_call, rpc_result};
+
+    #[test]
+    fn test_top_senders() {
+        let tx = |from: u8, nonce: u64| PoolTransaction {
+            hash: B256::ZERO,
+            from: Address::with_last_byte(from),
+            nonce,
+            gas_price: U256::ZERO,
+        };
+        let pool = [tx(2, 0), tx(1, 0), tx(2, 1), tx(3, 0), tx(1, 1), tx(2, 2)];
+        let senders = top_senders(&pool, 2);
+        // Most first, and the lower address among the equals
+        assert_eq!(senders, vec![(Address::with_last_byte(2), 3), (Address::with_last_byte(1), 2)]);
+    }
+
+    #[test]
+    fn test_top_gas_consumers() {
+        let receipt = |byte: u8, gas_used: u64| Receipt {
+            transaction_hash: B256::with_last_byte(byte),
+            block_number: None,
+            block_hash: None,
+            transaction_index: None,
+            from: Address::with_last_byte(byte),
+            to: None,
+            status: None,
+            gas_used: U256::from(gas_used),
+            cumulative_gas_used: U256::ZERO,
+            effective_gas_price: U256::ZERO,
+            contract_address: None,
+            logs_bloom: None,
+            logs: Vec::new(),
+        };
+        let receipts = [receipt(1, 21_000), receipt(2, 150_000), receipt(3, 29_000)];
+        let top = top_gas_consumers(&receipts, U256::from(200_000), 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].hash, B256::with_last_byte(2));
+        assert_eq!(top[0].share_pct, 75.0);
+        assert_eq!(top[1].gas_used, 29_000);
+        assert_eq!(top_gas_consumers(&receipts, U256::ZERO, 10)[2].share_pct, 0.0);
+    }
+
+    #[test]
+    fn test_call_frame_line() {
+        let frame = CallFrame {
+            call_type: "DELEGATECALL".to_string(),
+            from: Address::with_last_byte(1),
+            to: Some(Address::with_last_byte(2)),
+            value: None,
+            gas: Some(U256::from(50_000)),
+            gas_used: Some(U256::from(1_234)),
+            output: None,
+            error: Some("execution reverted".to_string()),
+            revert_reason: None,
+            calls: Vec::new(),
+        };
+        let line = call_frame_line(2, &frame, Some("execution reverted"));
+        assert!(line.starts_with("   └─ DELEGATECALL "), "{}", line);
+        assert!(line.contains("gas 1,234 / 50,000"), "{}", line);
+        assert!(line.ends_with("❌ execution reverted"), "{}", line);
+        assert!(call_frame_line(0, &frame, None).ends_with("✅"));
+    }
+
+    #[test]
+    fn test_block_delta() {
+        let block = |number: u64, timestamp: u64, gas_used: u64, base_fee: u64, txs: u8| Block {
+            number: U256::from(number),
+            timestamp: U256::from(timestamp),
+            gas_used: U256::from(gas_used),
+            gas_limit: U256::from(30_000_000),
+            base_fee_per_gas: Some(U256::from(base_fee)),
+            transactions: (0..txs).map(B256::with_last_byte).collect(),
+            ..Default::default()
+        };
+        // A full parent raises the base fee by the maximum 12.5%
+        let parent = block(100, 1_000, 30_000_000, 8_000_000_000, 200);
+        let child = block(101, 1_012, 12_000_000, 9_000_000_000, 90);
+        let delta = BlockDelta::between(&parent, &child);
+        assert_eq!(delta.seconds, 12);
+        assert_eq!(delta.base_fee_pct, Some(12.5));
+        assert_eq!(delta.expected_base_fee, Ordering::Greater);
+        assert_eq!(delta.gas_used, -18_000_000);
+        assert_eq!(delta.tx_count, -110);
+
+        let quiet = BlockDelta::between(&child, &block(102, 1_024, 0, 8_000_000_000, 0));
+        assert_eq!(quiet.expected_base_fee, Ordering::Less);
+    }
+
+    #[test]
+    fn test_block_diff() {
+        let block = |miner: u8, timestamp: u64, txs: std::ops::Range<u8>| Block {
+            number: U256::from(100),
+            hash: Some(B256::with_last_byte(miner)),
+            timestamp: U256::from(timestamp),
+            gas_used: U256::from(21_000),
+            gas_limit: U256::from(30_000_000),
+            transactions: txs.map(B256::with_last_byte).collect(),
+            miner: Address::with_last_byte(miner),
+            ..Default::default()
+        };
+        // Two competing blocks at the same height sharing some transactions
+        let (a, b) = (block(1, 1_000, 0..4), block(2, 1_002, 2..6));
+        let rows = diff_rows(&a, &b);
+        let differing: Vec<_> = rows.iter().filter(|row| row.differs).map(|row| row.property).collect();
+        assert_eq!(differing, vec!["Hash", "Timestamp", "Miner"]);
+        assert!(rows[3].b.ends_with("(+2s)"));
+
+        let (only_in_a, only_in_b) = tx_set_difference(&a, &b);
+        assert_eq!(only_in_a, vec![B256::with_last_byte(0), B256::with_last_byte(1)]);
+        assert_eq!(only_in_b, vec![B256::with_last_byte(4), B256::with_last_byte(5)]);
+    }
+
+    #[test]
+    fn test_block_table_with_huge_header_values() {
+        let block = Block {
+            number: U256::from(12_965_000),
+            timestamp: U256::MAX,
+            gas_used: U256::MAX,
+            gas_limit: U256::from(30_000_000),
+            base_fee_per_gas: Some(U256::MAX),
+            difficulty: U256::MAX,
+            size: U256::MAX,
+            blob_gas_used: Some(u64::MAX - 1),
+            excess_blob_gas: Some(u64::MAX - 1),
+            ..Default::default()
+        };
+        let rendered = block_table(&block, None, 1).to_string();
+        assert!(rendered.contains(&format_number_u256(&U256::MAX)));
+        assert!(rendered.contains(&U256::MAX.to_string()));
+        // Gas used is shown in full, not clamped to a u64
+        assert!(!rendered.contains(&format_number(u64::MAX)));
+    }
+
+    #[test]
+    fn test_watch_alert_event_is_one_line() {
+        let alert = WatchEvent::Alert {
+            block_number: Some(U256::from(18_000_000)),
+            hash: B256::with_last_byte(1),
+            from: Address::with_last_byte(2),
+            to: None,
+            value: U256::from(250_000_000_000_000_000_000u128),
+        };
+        let line = render(&alert, OutputFormat::Ndjson).unwrap();
+        assert!(!line.contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["event"], "alert");
+        assert_eq!(value["value"], "250000000000000000000");
+        assert_eq!(value["block_number"], "18000000");
+        assert!(value["to"].is_null());
+    }
+
+    #[test]
+    fn test_gas_csv_row() {
+        let mut sample = BlockGasSample {
+            number: 18_000_000,
+            timestamp: 1_693_066_895,
+            gas_used: 15_000_000,
+            gas_limit: 30_000_000,
+            base_fee: Some(U256::from(20_000_000_000u64)),
+            tx_count: 150,
+        };
+        assert_eq!(gas_csv_row(&sample), "18000000,1693066895,15000000,30000000,50.00,20000000000,300000000000000000,150");
+        assert_eq!(gas_csv_row(&sample).split(',').count(), GAS_CSV_HEADER.split(',').count());
+
+        sample.base_fee = None;
+        assert_eq!(gas_csv_row(&sample), "18000000,1693066895,15000000,30000000,50.00,,,150");
+    }
+
+    /// A mock node that answers the connection check, and an explorer on it
+    async fn explorer_for() -> (wiremock::MockServer, BlockExplorer) {
+        let server = test_support::mock_node().await;
+        let client = RethClient::new(&[server.uri()], &crate::rpc::CacheConfig::default(), crate::rpc::DEFAULT_RPC_TIMEOUT, false)
+            .await
+            .unwrap();
+        (server, BlockExplorer::new(client, AbiRegistry::default(), None, ErrorPolicy::FailFast, OutputFormat::Json, None, true))
+    }
+
+    fn summary_tx(block_number: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "blockNumber": block_number,
+            "transactionIndex": block_number.map(|_| "0x0"),
+            "type": "0x2",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "nonce": "0x7",
+            "value": "0xde0b6b3a7640000",
+            "gas": "0x5208",
+            "gasPrice": "0x4a817c800",
+            "maxFeePerGas": "0x4a817c800",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "input": "0x",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_transaction_summary_snapshot() {
+        let (server, explorer) = explorer_for().await;
+        rpc_result("eth_getTransactionByHash", summary_tx(Some("0x10"))).mount(&server).await;
+        let receipt = serde_json::json!({
+            "transactionHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "blockNumber": "0x10",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "status": "0x1",
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x4a817c800",
+            "logs": [],
+        });
+        rpc_result("eth_getTransactionReceipt", receipt.clone()).mount(&server).await;
+        // Only the header is needed, so the block is asked for without its transactions
+        let header_request = serde_json::json!({ "method": "eth_getBlockByNumber", "params": ["0x10", false] });
+        let mut header = test_support::block("0x10");
+        header["gasUsed"] = serde_json::json!("0x5208");
+        header["baseFeePerGas"] = serde_json::json!("0x3b9aca00");
+        header["transactions"] = serde_json::json!(["0x1111111111111111111111111111111111111111111111111111111111111111"]);
+        rpc_call(header_request, move |_| serde_json::json!({ "result": header.clone() }))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+        let summary = explorer.transaction_summary("0x1111111111111111111111111111111111111111111111111111111111111111").await.unwrap();
+        // The field names and number encodings are what scripts depend on
+        assert_eq!(serde_json::to_value(&summary).unwrap(), serde_json::json!({
+            "schema": "eth_data_extractor/transaction-summary",
+            "version": 1,
+            "chain_id": 1,
+            "transaction": {
+                "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                "block_number": "16",
+                "transaction_index": "0",
+                "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+                "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "nonce": "7",
+                "value": "1000000000000000000",
+                "gas": "21000",
+                "gas_price": "20000000000",
+                "tx_type": 2,
+                "max_fee_per_gas": "20000000000",
+                "max_priority_fee_per_gas": "1000000000",
+                "max_fee_per_blob_gas": null,
+                "blob_versioned_hashes": [],
+                "gas_used": "21000",
+                "status": "1",
+                "input": "0x",
+                "access_list": null,
+                "logs": [],
+            },
+            "receipt": receipt,
+            "block": {
+                "number": 16,
+                "hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+                "parent_hash": "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890",
+                "timestamp": "1694546080",
+                "gas_used": "21000",
+                "gas_limit": "30000000",
+                "base_fee_per_gas": "1000000000",
+                "miner": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+                "transaction_count": 1,
+            },
+            "call": null,
+            "events": [],
+            "token_transfers": [],
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_pending_transaction_summary() {
+        let (server, explorer) = explorer_for().await;
+        rpc_result("eth_getTransactionByHash", summary_tx(None)).mount(&server).await;
+        rpc_result("eth_getTransactionReceipt", serde_json::Value::Null).mount(&server).await;
+        rpc_result("eth_getBlockByNumber", serde_json::Value::Null).expect(0).mount(&server).await;
+
+        let summary = explorer.transaction_summary("0x1111111111111111111111111111111111111111111111111111111111111111").await.unwrap();
+        let value = serde_json::to_value(&summary).unwrap();
+        assert!(value["receipt"].is_null());
+        assert!(value["block"].is_null());
+        assert!(value["transaction"]["block_number"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_account_balances_order_and_total() {
+        let (server, explorer) = explorer_for().await;
+        let poor = "0x1111111111111111111111111111111111111111";
+        let rich = "0x2222222222222222222222222222222222222222";
+        rpc_call(serde_json::json!({ "method": "eth_getBalance", "params": [poor] }), |_| serde_json::json!({ "result": "0x5" })).mount(&server).await;
+        rpc_call(serde_json::json!({ "method": "eth_getBalance", "params": [rich] }), |_| serde_json::json!({ "result": "0x64" })).mount(&server).await;
+        rpc_result("eth_getTransactionCount", serde_json::json!("0x0")).mount(&server).await;
+        rpc_result("eth_getCode", serde_json::json!("0x")).mount(&server).await;
+
+        let addresses = ["not-an-address", poor, "also-bad", rich].map(String::from);
+        let mut tally = ErrorTally::new(ErrorPolicy::Continue);
+        let balances = explorer.account_balances(&addresses, None, &mut tally).await.unwrap();
+        let order: Vec<_> = balances.accounts.iter().map(|account| format_eth_address_full(&account.address).to_lowercase()).collect();
+        assert_eq!(order, [rich, poor]);
+        assert_eq!(balances.failed, ["not-an-address", "also-bad"]);
+        assert_eq!(balances.total, U256::from(105));
+        assert_eq!(tally.failed(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_account_balances_fail_fast() {
+        let (server, explorer) = explorer_for().await;
+        // Lookups that would take far longer than the test allows
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::body_partial_json(serde_json::json!({ "method": "eth_getBalance" })))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .mount(&server)
+            .await;
+
+        let addresses = ["0x1111111111111111111111111111111111111111", "not-an-address"].map(String::from);
+        let mut tally = ErrorTally::new(ErrorPolicy::FailFast);
+        let lookup = explorer.account_balances(&addresses, None, &mut tally);
+        let result = tokio::time::timeout(Duration::from_secs(5), lookup).await.expect("kept waiting after a failure");
+        assert!(matches!(result.err().unwrap().downcast_ref::<ExtractorError>(), Some(ExtractorError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_code_out_without_code() {
+        let (server, explorer) = explorer_for().await;
+        rpc_result("eth_getCode", serde_json::json!("0x")).mount(&server).await;
+        let path = std::env::temp_dir().join(format!("eth_data_extractor_empty_code_{}.hex", std::process::id()));
+
+        let err = explorer.show_code(Address::ZERO, None, Some(&path)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<RethClientError>(), Some(RethClientError::NotFound(_))));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_gas_window_range() {
+        assert_eq!(GasWindow::Latest(1).range(16).unwrap(), (16, 16));
+        assert_eq!(GasWindow::Latest(100).range(1_000).unwrap(), (901, 1_000));
+        // Near genesis the window starts at block 0
+        assert_eq!(GasWindow::Latest(100).range(16).unwrap(), (0, 16));
+        assert!(GasWindow::Latest(0).range(16).is_err());
+        assert_eq!(GasWindow::Range { from_block: 5, to_block: None }.range(16).unwrap(), (5, 16));
+        assert_eq!(GasWindow::Range { from_block: 5, to_block: Some(9) }.range(16).unwrap(), (5, 9));
+        assert_eq!(GasWindow::Range { from_block: 5, to_block: Some(16) }.range(16).unwrap(), (5, 16));
+        let past_head = GasWindow::Range { from_block: 5, to_block: Some(17) }.range(16).unwrap_err();
+        assert!(matches!(past_head.downcast_ref::<ExtractorError>(), Some(ExtractorError::InvalidInput(_))));
     }
 }
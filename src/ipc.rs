@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use jsonrpsee::core::client::{ReceivedMessage, TransportReceiverT, TransportSenderT};
+use std::io;
+use std::path::Path;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::UnixStream;
+
+/// How much is read from the socket at a time
+const READ_CHUNK: usize = 64 * 1024;
+
+/// Writes requests to a node's IPC socket
+pub struct IpcSender {
+    writer: OwnedWriteHalf,
+}
+
+/// Reads responses from a node's IPC socket. The socket is a plain byte
+/// stream, so messages are split wherever a complete JSON value ends.
+pub struct IpcReceiver {
+    reader: OwnedReadHalf,
+    framer: Framer,
+}
+
+/// Connect to the Unix socket at `path`, split into the two halves the
+/// jsonrpsee client drives from its own tasks
+pub async fn connect(path: &Path) -> io::Result<(IpcSender, IpcReceiver)> {
+    let (reader, writer) = UnixStream::connect(path).await?.into_split();
+    Ok((IpcSender { writer }, IpcReceiver { reader, framer: Framer::default() }))
+}
+
+#[async_trait]
+impl TransportSenderT for IpcSender {
+    type Error = io::Error;
+
+    async fn send(&mut self, msg: String) -> Result<(), Self::Error> {
+        self.writer.write_all(msg.as_bytes()).await?;
+        self.writer.write_all(b"\n").await
+    }
+
+    async fn close(&mut self) -> Result<(), Self::Error> {
+        self.writer.shutdown().await
+    }
+}
+
+#[async_trait]
+impl TransportReceiverT for IpcReceiver {
+    type Error = io::Error;
+
+    async fn receive(&mut self) -> Result<ReceivedMessage, Self::Error> {
+        loop {
+            if let Some(message) = self.framer.take_message()? {
+                return Ok(ReceivedMessage::Bytes(message));
+            }
+            // Read straight into the unscanned tail of the buffer
+            self.framer.buffer.reserve(READ_CHUNK);
+            if self.reader.read_buf(&mut self.framer.buffer).await? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "IPC socket closed by the node"));
+            }
+        }
+    }
+}
+
+/// Splits the byte stream into JSON values. Scanning resumes where the last
+/// call stopped, so a large response arriving in many reads is only walked
+/// once.
+#[derive(Default)]
+struct Framer {
+    buffer: Vec<u8>,
+    /// Bytes of `buffer` already scanned
+    scanned: usize,
+    /// Open objects and arrays
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl Framer {
+    /// Remove and return the first complete JSON value in the buffer, or
+    /// `None` if it only holds part of one so far. Responses are always an
+    /// object or, for batches, an array.
+    fn take_message(&mut self) -> io::Result<Option<Vec<u8>>> {
+        if self.depth == 0 {
+            // Drop the whitespace between messages
+            let start = self.buffer.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(self.buffer.len());
+            self.buffer.drain(..start);
+            self.scanned = 0;
+        }
+        while self.scanned < self.buffer.len() {
+            let byte = self.buffer[self.scanned];
+            self.scanned += 1;
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'{' | b'[' => self.depth += 1,
+                _ if self.depth == 0 => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unexpected byte {:?} between IPC messages", byte as char)));
+                }
+                b'"' => self.in_string = true,
+                b'}' | b']' => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        let message = self.buffer.drain(..self.scanned).collect();
+                        self.scanned = 0;
+                        return Ok(Some(message));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_message_splits_stream() {
+        let mut framer = Framer::default();
+        framer.buffer.extend_from_slice(br#"{"id":1,"result":"0x1"} {"id":2,"res"#);
+        let first = framer.take_message().unwrap().unwrap();
+        assert_eq!(first, br#"{"id":1,"result":"0x1"}"#);
+        // The second response hasn't fully arrived yet
+        assert!(framer.take_message().unwrap().is_none());
+        framer.buffer.extend_from_slice(br#"ult":"0x2"}"#);
+        let second = framer.take_message().unwrap().unwrap();
+        assert_eq!(second, br#"{"id":2,"result":"0x2"}"#);
+        assert!(framer.take_message().unwrap().is_none());
+        assert!(framer.buffer.is_empty());
+    }
+
+    #[test]
+    fn test_take_message_ignores_brackets_in_strings() {
+        let mut framer = Framer::default();
+        framer.buffer.extend_from_slice(br#"[{"id":1,"error":{"message":"bad \"}]\" {"}}"#);
+        assert!(framer.take_message().unwrap().is_none());
+        framer.buffer.extend_from_slice(br#"]"#);
+        let batch = framer.take_message().unwrap().unwrap();
+        assert!(serde_json::from_slice::<serde_json::Value>(&batch).unwrap().is_array());
+
+        framer.buffer.extend_from_slice(b"\nnull");
+        assert!(framer.take_message().is_err());
+    }
+}
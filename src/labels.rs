@@ -0,0 +1,146 @@
+use alloy_primitives::{address, Address};
+use color_eyre::{eyre::eyre, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Well-known mainnet contracts, shown wherever an address is displayed
+const BUNDLED_LABELS: &[(Address, &str)] = &[
+    (address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"), "WETH"),
+    (address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"), "USDC"),
+    (address!("dAC17F958D2ee523a2206206994597C13D831ec7"), "USDT"),
+    (address!("6B175474E89094C44Da98b954EedeAC495271d0F"), "DAI"),
+    (address!("7a250d5630B4cF539739dF2C5dAcb4c659F2488D"), "Uniswap V2 Router"),
+    (address!("E592427A0AEce92De3Edee1F18E0157C05861564"), "Uniswap V3 Router"),
+    (address!("00000000219ab540356cBB839Cbe05303d7705Fa"), "Beacon Deposit Contract"),
+    (address!("4e59b44847b379578588920cA78FbF26c0B4956C"), "Deterministic Deployment Proxy"),
+];
+
+/// User labels from `--address-book`, set once at startup
+static ADDRESS_BOOK: OnceLock<HashMap<Address, String>> = OnceLock::new();
+
+/// Label for an address, preferring the user's address book over the
+/// bundled registry
+pub fn address_label(address: &Address) -> Option<&'static str> {
+    if let Some(label) = ADDRESS_BOOK.get().and_then(|book| book.get(address)) {
+        return Some(label.as_str());
+    }
+    BUNDLED_LABELS
+        .iter()
+        .find(|(known, _)| known == address)
+        .map(|(_, label)| *label)
+}
+
+/// Load an address book and install it for `address_label`. Files ending in
+/// `.csv` hold `address,label` lines; anything else is read as TOML with one
+/// `"0x…" = "label"` pair per line. Bad entries are reported and skipped.
+pub fn load_address_book(path: &Path) -> Result<usize> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| eyre!("Failed to read address book {}: {}", path.display(), e))?;
+    let entries = if path.extension().is_some_and(|ext| ext == "csv") {
+        parse_csv(&contents)
+    } else {
+        parse_toml(&contents)
+            .map_err(|e| eyre!("Invalid TOML in {}: {}", path.display(), e))?
+    };
+
+    let (book, warnings) = build_address_book(entries);
+    for warning in warnings {
+        eprintln!("⚠️  {}: {}", path.display(), warning);
+    }
+    let count = book.len();
+    ADDRESS_BOOK
+        .set(book)
+        .map_err(|_| eyre!("Address book already loaded"))?;
+    Ok(count)
+}
+
+/// Raw `(address, label)` pairs, or a warning for entries that couldn't be
+/// split into a pair
+type RawEntries = Vec<Result<(String, String), String>>;
+
+fn parse_csv(contents: &str) -> RawEntries {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| match line.split_once(',') {
+            Some((address, label)) => Ok((address.trim().to_string(), label.trim().to_string())),
+            None => Err(format!("line {}: expected `address,label`", i + 1)),
+        })
+        .collect()
+}
+
+fn parse_toml(contents: &str) -> std::result::Result<RawEntries, toml_edit::TomlError> {
+    let doc: toml_edit::DocumentMut = contents.parse()?;
+    Ok(doc
+        .iter()
+        .map(|(key, item)| match item.as_str() {
+            Some(label) => Ok((key.to_string(), label.to_string())),
+            None => Err(format!("{}: label must be a string", key)),
+        })
+        .collect())
+}
+
+/// Validate raw entries into a lookup map, collecting a warning for each
+/// malformed or duplicate entry. Later duplicates win.
+fn build_address_book(entries: RawEntries) -> (HashMap<Address, String>, Vec<String>) {
+    let mut book = HashMap::new();
+    let mut warnings = Vec::new();
+    for entry in entries {
+        let (raw_address, label) = match entry {
+            Ok(pair) => pair,
+            Err(warning) => {
+                warnings.push(warning);
+                continue;
+            }
+        };
+        let address: Address = match raw_address.parse() {
+            Ok(address) => address,
+            Err(_) => {
+                warnings.push(format!("invalid address {:?}", raw_address));
+                continue;
+            }
+        };
+        if label.is_empty() {
+            warnings.push(format!("empty label for {}", raw_address));
+            continue;
+        }
+        if book.insert(address, label).is_some() {
+            warnings.push(format!("duplicate entry for {}", raw_address));
+        }
+    }
+    (book, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TREASURY: Address = address!("742d35Cc622C1E0532F7fd0e7c0e6f7D8F2B2B6f");
+
+    #[test]
+    fn test_csv_address_book() {
+        let csv = "# team wallets\n0x742d35Cc622C1E0532F7fd0e7c0e6f7D8F2B2B6f, Treasury\nnot-an-address,Oops\nmissing comma\n0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f,Treasury v2\n";
+        let (book, warnings) = build_address_book(parse_csv(csv));
+        assert_eq!(book.get(&TREASURY).map(String::as_str), Some("Treasury v2"));
+        assert_eq!(book.len(), 1);
+        assert_eq!(warnings.len(), 3);
+    }
+
+    #[test]
+    fn test_toml_address_book() {
+        let toml = "\"0x742d35Cc622C1E0532F7fd0e7c0e6f7D8F2B2B6f\" = \"Treasury\"\n\"0xbad\" = \"Broken\"\n";
+        let (book, warnings) = build_address_book(parse_toml(toml).unwrap());
+        assert_eq!(book.get(&TREASURY).map(String::as_str), Some("Treasury"));
+        assert_eq!(warnings, vec!["invalid address \"0xbad\"".to_string()]);
+    }
+
+    #[test]
+    fn test_bundled_label() {
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        assert_eq!(address_label(&weth), Some("WETH"));
+        assert_eq!(address_label(&Address::ZERO), None);
+    }
+}
@@ -0,0 +1,16 @@
+//! The node client behind the `eth_data_extractor` command, for embedding
+//! in other programs: `rpc::RethClient` and the errors it returns in
+//! `error`. The remaining public modules are shared with the binary.
+
+pub mod error;
+pub mod labels;
+pub mod output;
+pub mod progress;
+pub mod rpc;
+pub mod utils;
+
+mod disk_cache;
+#[cfg(unix)]
+mod ipc;
+#[cfg(test)]
+mod test_support;
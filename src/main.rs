@@ -1,19 +1,152 @@
 use clap::{Parser, Subcommand};
+use color_eyre::config::{HookBuilder, Theme};
 use color_eyre::Result;
 
+mod abi;
 mod explorer;
-mod utils;
-mod rpc;
+mod pager;
+mod price;
+mod selectors;
+#[cfg(test)]
+mod test_support;
 
-use explorer::BlockExplorer;
+use eth_data_extractor::{error, labels, output, progress, rpc, utils};
+
+use abi::AbiRegistry;
+use error::ExtractorError;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use explorer::{BlockExplorer, BlockOptions, GasWindow, LogsOptions};
+use output::{ColorChoice, OutputFile, OutputFormat};
+use pager::{Pager, PagerMode};
+use price::{PriceOracle, PriceSourceKind};
+use rpc::{CacheConfig, CallRequest, LogFilter, LogSort, RethClient, RetryConfig};
+use selectors::SelectorLookup;
+use utils::{BlockBound, ErrorPolicy, GasFormat};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "eth_data_extractor")]
 #[command(about = "A CLI Ethereum data extraction tool built with Reth")]
 struct Cli {
-    /// RPC URL for the Ethereum node
-    #[arg(short, long, default_value = "http://localhost:8545")]
-    rpc_url: String,
+    /// RPC URL for the Ethereum node (http://, https://, ws:// or wss://),
+    /// or the path of its IPC socket, optionally as ipc://path. Repeat it
+    /// or give a comma-separated list to add fallback endpoints, used in
+    /// order when the current one stops responding
+    #[arg(short, long, default_value = "http://localhost:8545", value_delimiter = ',')]
+    rpc_url: Vec<String>,
+
+    /// Seconds to wait for each RPC request, e.g. longer for heavy archive
+    /// queries or shorter for liveness checks
+    #[arg(long, global = true, default_value_t = rpc::DEFAULT_RPC_TIMEOUT.as_secs(), value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: u64,
+
+    /// Seconds to keep retrying a failing request before giving up on the
+    /// endpoint (or moving on to the next one)
+    #[arg(long, global = true, default_value_t = rpc::DEFAULT_RETRY_WINDOW.as_secs(), value_parser = clap::value_parser!(u64).range(1..=3600))]
+    max_retry_secs: u64,
+
+    /// Milliseconds to wait before the first retry; later waits grow
+    /// exponentially
+    #[arg(long, global = true, default_value_t = rpc::DEFAULT_RETRY_INITIAL_INTERVAL.as_millis() as u64, value_parser = clap::value_parser!(u64).range(1..))]
+    retry_initial_ms: u64,
+
+    /// Try each request once, for scripts that would rather fail fast
+    #[arg(long, global = true, conflicts_with_all = ["max_retry_secs", "retry_initial_ms"])]
+    no_retry: bool,
+
+    /// Directory of JSON ABIs used to decode calldata and event logs
+    #[arg(long, global = true)]
+    abi_dir: Option<PathBuf>,
+
+    /// A JSON ABI file to decode with, e.g. the event fields listed by `logs`
+    /// or the return values of `call`; repeatable, and combines with --abi-dir
+    #[arg(long, global = true)]
+    abi: Vec<PathBuf>,
+
+    /// Address labels to show alongside addresses (`.csv` or TOML)
+    #[arg(long, global = true)]
+    address_book: Option<PathBuf>,
+
+    /// Look up function selectors missing from the built-in table on
+    /// 4byte.directory
+    #[arg(long, global = true)]
+    online_4byte: bool,
+
+    /// How gas amounts are shown in tables (default: per table)
+    #[arg(long, global = true, value_enum)]
+    gas_format: Option<GasFormat>,
+
+    /// Output format for command results
+    #[arg(long, global = true, value_enum, default_value = "table", alias = "output")]
+    format: OutputFormat,
+
+    /// Write command results to this file instead of stdout; status and
+    /// progress lines stay on the terminal
+    #[arg(long, global = true)]
+    output_file: Option<PathBuf>,
+
+    /// Leave out the connection message, section banners and progress
+    /// lines, keeping only the data; warnings and errors still show
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Print the detected chain id and native currency before the output,
+    /// even with --quiet
+    #[arg(long, global = true)]
+    show_chain: bool,
+
+    /// What range scans do when a single block or chunk fails
+    #[arg(long, global = true, value_enum, default_value = "continue")]
+    on_error: ErrorPolicy,
+
+    /// Show USD values next to ETH amounts in transaction and account details
+    #[arg(long, global = true)]
+    fiat: bool,
+
+    /// Preferred ETH/USD price source for --fiat; the others are tried if it fails
+    #[arg(long, global = true, value_enum, default_value = "coingecko")]
+    eth_price_source: PriceSourceKind,
+
+    /// Endpoint for `--eth-price-source custom-url`, returning the price as
+    /// a JSON number or under a `usd` or `price` key
+    #[arg(long, global = true)]
+    eth_price_url: Option<String>,
+
+    /// Send table output through `$PAGER` (default `less`) when it is
+    /// longer than the terminal
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pager: PagerMode,
+
+    /// Color statuses in tables: `auto` colors a terminal unless NO_COLOR
+    /// is set
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    color: ColorChoice,
+
+    /// Keep finalized blocks, transactions and receipts in this directory so
+    /// later runs don't fetch them again
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>,
+
+    /// Don't cache anything, in memory or on disk
+    #[arg(long, global = true, conflicts_with = "cache_dir")]
+    no_cache: bool,
+
+    /// Entries the in-memory caches hold between them; 0 disables
+    /// caching like --no-cache
+    #[arg(long, global = true, default_value_t = rpc::DEFAULT_CACHE_CAPACITY)]
+    cache_size: u64,
+
+    /// How long in-memory cache entries live, e.g. 90s, 5m, 1h; 0 means
+    /// they never expire
+    #[arg(long, global = true, default_value = "5m", value_parser = utils::parse_duration)]
+    cache_ttl: Duration,
+
+    /// Print cache hits and misses and the time spent parsing node
+    /// responses to stderr when the command finishes
+    #[arg(long, global = true)]
+    stats: bool,
     
     #[command(subcommand)]
     command: Commands,
@@ -23,17 +156,163 @@ struct Cli {
 enum Commands {
     /// Get information about a block
     Block {
-        /// Block number or hash
+        /// Block number, hash, or tag (latest, earliest, pending, safe, finalized)
         block_id: String,
+        /// Only list transactions moving at least this many ETH
+        #[arg(long, value_parser = utils::parse_eth)]
+        min_value: Option<U256>,
+        /// Print the total ETH moved and the number of contract creations
+        #[arg(long)]
+        tx_value_sum: bool,
+        /// Print the block's logs bloom filter
+        #[arg(long)]
+        show_logs_bloom: bool,
+        /// Compare with the parent block: time, base fee, gas used, tx count
+        #[arg(long)]
+        diff_parent: bool,
+        /// Also list the block's uncles with their number and miner
+        #[arg(short, long)]
+        verbose: bool,
+        /// List the N transactions using the most gas, with their share of
+        /// the block's gas
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+        top_gas: Option<usize>,
+        /// Print the node's JSON response as-is instead of the parsed table
+        #[arg(long, conflicts_with_all = ["min_value", "tx_value_sum", "show_logs_bloom", "diff_parent", "verbose", "top_gas"])]
+        raw_json: bool,
     },
+    /// Compare two blocks side by side, including the transactions only
+    /// one of them has
+    Diff {
+        /// First block: number, hash, or tag
+        #[arg(long)]
+        a: String,
+        /// Second block: number, hash, or tag
+        #[arg(long)]
+        b: String,
+    },
+    /// Show the genesis block and identify the chain it belongs to
+    Genesis,
     /// Get transaction details
     Transaction {
         /// Transaction hash
         tx_hash: String,
+        /// Print the raw signed transaction bytes instead of the details table
+        #[arg(long)]
+        raw_tx: bool,
+        /// For failed transactions, replay the call to decode its revert reason
+        #[arg(long)]
+        explain_revert: bool,
+        /// Also show the transactions just before and after it in its block
+        #[arg(long)]
+        position_context: bool,
+        /// Print the transaction, receipt, block header and decoded logs as
+        /// one JSON document
+        #[arg(long, conflicts_with = "raw_tx")]
+        summary_json: bool,
+        /// Print the node's JSON response as-is instead of the parsed table
+        #[arg(long, conflicts_with_all = ["raw_tx", "summary_json", "explain_revert", "position_context"])]
+        raw_json: bool,
+    },
+    /// Show a transaction's full receipt, including its logs and any
+    /// contract it created
+    Receipt {
+        /// Transaction hash
+        tx_hash: String,
+        /// Print the node's JSON response as-is instead of the parsed table
+        #[arg(long)]
+        raw_json: bool,
     },
     /// Get account balance and info
     Account {
-        /// Account address
+        /// Account address or ENS name (e.g. vitalik.eth)
+        #[arg(required_unless_present_any = ["addresses", "addresses_file"])]
+        address: Option<String>,
+        /// More accounts to list together, with their total balance
+        #[arg(long = "address")]
+        addresses: Vec<String>,
+        /// File of accounts to list, one address or ENS name per line
+        #[arg(long)]
+        addresses_file: Option<PathBuf>,
+        /// Optional block number (default: latest)
+        #[arg(short, long)]
+        block: Option<u64>,
+        /// Read the state at the block with this hash instead of a number
+        #[arg(long, conflicts_with = "block")]
+        block_hash: Option<B256>,
+        /// Show the bytecode hash and match it against known implementations
+        #[arg(long)]
+        code_hash: bool,
+        /// Find the block where the account first sent a transaction or got
+        /// code (binary search; requires an archive node)
+        #[arg(long)]
+        first_tx: bool,
+        /// Compare the latest and pending nonces to spot transactions that
+        /// are waiting to be mined
+        #[arg(long, conflicts_with_all = ["block", "block_hash"])]
+        pending: bool,
+    },
+    /// Call any contract function with eth_call and show what it returns
+    Call {
+        /// Contract address
+        #[arg(long)]
+        to: Address,
+        /// Calldata in hex: the function selector and ABI-encoded arguments
+        #[arg(long, conflicts_with = "data_file")]
+        data: Option<Bytes>,
+        /// File holding the calldata in hex
+        #[arg(long)]
+        data_file: Option<PathBuf>,
+        /// Sender, for functions that depend on msg.sender
+        #[arg(long)]
+        from: Option<Address>,
+        /// ETH sent with the call (e.g. 0.5)
+        #[arg(long, value_parser = utils::parse_eth)]
+        value: Option<U256>,
+        /// Optional block number (default: latest)
+        #[arg(short, long)]
+        block: Option<u64>,
+    },
+    /// Read a contract storage slot
+    Storage {
+        /// Contract address
+        #[arg(long)]
+        address: Address,
+        /// Slot index in hex (0x-prefixed) or decimal, or an EIP-1967 proxy
+        /// slot by name: impl, admin or beacon
+        #[arg(long, value_parser = utils::parse_storage_slot)]
+        slot: B256,
+        /// Optional block number (default: latest)
+        #[arg(short, long)]
+        block: Option<u64>,
+    },
+    /// Show the bytecode deployed at an address, with its size and hash
+    Code {
+        /// Contract address
+        #[arg(long)]
+        address: Address,
+        /// Optional block number (default: latest)
+        #[arg(short, long)]
+        block: Option<u64>,
+        /// Write the bytecode as hex to this file instead of printing it
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Find the transaction that deployed a contract, with its block and
+    /// deployer
+    #[command(alias = "contract-creation")]
+    Creation {
+        /// Contract address
+        #[arg(long)]
+        address: Address,
+    },
+    /// Get an account's ERC-20 token balance
+    TokenBalance {
+        /// Token contract address
+        #[arg(long)]
+        token: Address,
+        /// Holder address or ENS name
+        #[arg(long)]
         address: String,
         /// Optional block number (default: latest)
         #[arg(short, long)]
@@ -44,38 +323,422 @@ enum Commands {
         /// Number of blocks to show (default: 10)
         #[arg(short, long, default_value = "10")]
         count: usize,
+        /// Show every block mined within this long instead, e.g. `10m`, `2h`
+        #[arg(long, value_parser = utils::parse_duration, conflicts_with = "count")]
+        since: Option<Duration>,
+        /// Show each block's top transaction by value
+        #[arg(long)]
+        tx_detail: bool,
+        /// Prepend the pending block being built by the node
+        #[arg(long)]
+        include_pending: bool,
+    },
+    /// Write a range of blocks to a file as newline-delimited JSON
+    Export {
+        /// First block of the range
+        #[arg(long)]
+        from: u64,
+        /// Last block of the range (inclusive)
+        #[arg(long)]
+        to: u64,
+        /// File to write, one block per line
+        #[arg(long)]
+        out: PathBuf,
+        /// Include full transaction objects rather than hashes
+        #[arg(long)]
+        with_transactions: bool,
+    },
+    /// Scan a block range for transactions sent by an address
+    TxsFrom {
+        /// Sender address or ENS name
+        address: String,
+        /// First block of the scan
+        #[arg(long)]
+        from_block: u64,
+        /// Last block of the scan (default: latest)
+        #[arg(long)]
+        to_block: Option<u64>,
+        /// Flag missing or duplicate (replaced) nonces in the results
+        #[arg(long)]
+        detect_gaps: bool,
+    },
+    /// Query event logs over a block range
+    Logs {
+        /// Only include logs emitted by this contract
+        #[arg(long)]
+        address: Option<Address>,
+        /// First block of the range, or `latest`
+        #[arg(long, value_parser = utils::parse_block_bound)]
+        from_block: BlockBound,
+        /// Last block of the range, or `latest` (default: latest)
+        #[arg(long, value_parser = utils::parse_block_bound)]
+        to_block: Option<BlockBound>,
+        /// Positional topic filter; repeat for topic1, topic2, ... ("any" matches all)
+        #[arg(long)]
+        topic: Vec<String>,
+        /// Blocks per eth_getLogs request
+        #[arg(long, default_value = "2000")]
+        chunk_size: u64,
+        /// Only count matching logs instead of listing them
+        #[arg(long)]
+        count_only: bool,
+        /// With --count-only, also show the count for each chunk
+        #[arg(long, requires = "count_only")]
+        per_chunk: bool,
+        /// Order of the listed logs
+        #[arg(long, value_enum, default_value = "block")]
+        sort_logs_by: LogSort,
+        /// Reverse the listing order
+        #[arg(long)]
+        reverse: bool,
+        /// Collect every recipient of the token at --address from its
+        /// Transfer logs and list their current balances, largest first
+        #[arg(long, requires = "address", conflicts_with_all = ["topic", "count_only", "sort_logs_by", "reverse"])]
+        batch_accounts_from_logs: bool,
+        /// Test each block's logs bloom first and only query blocks that may
+        /// match; faster for rare events, slower for common ones
+        #[arg(long, conflicts_with = "count_only")]
+        bloom_prefilter: bool,
+    },
+    /// Follow new blocks as they arrive
+    Watch {
+        /// Alert on transfers of at least this many ETH
+        #[arg(long, value_parser = utils::parse_eth)]
+        value_threshold: Option<U256>,
+        /// Only print header fields of the head block, one request per tick
+        #[arg(long, conflicts_with = "value_threshold")]
+        poll_head_only: bool,
+        /// How often to poll for new blocks over HTTP, e.g. `12s`, `1m`.
+        /// WebSocket and IPC endpoints push new heads instead.
+        #[arg(long, default_value = "12s", value_parser = utils::parse_duration)]
+        interval: Duration,
+    },
+    /// Re-execute a mined transaction with eth_call on the state before its block
+    ReplayTx {
+        /// Transaction hash
+        tx_hash: String,
+    },
+    /// Trace a transaction's execution with debug_traceTransaction
+    Trace {
+        /// Transaction hash
+        #[arg(long)]
+        tx_hash: String,
+        /// Tracer to run; `callTracer` output is drawn as a call tree
+        #[arg(long, default_value = "callTracer")]
+        tracer: String,
+    },
+    /// Trace every transaction in a block and summarize internal calls
+    TraceBlock {
+        /// Block number or hash
+        block_id: String,
+        /// Tracer to run; only `callTracer` output is summarized
+        #[arg(long, default_value = "callTracer")]
+        tracer: String,
+    },
+    /// Rank block producers (fee recipients) over a block range
+    MinerStats {
+        /// First block of the range
+        #[arg(long)]
+        from_block: u64,
+        /// Last block of the range (default: latest)
+        #[arg(long)]
+        to_block: Option<u64>,
+        /// Number of producers to list
+        #[arg(long, default_value = "10")]
+        top: usize,
+        /// Show the listed producers' primary ENS names
+        #[arg(long)]
+        resolve_names: bool,
+    },
+    /// Send any JSON-RPC method and print the raw result
+    Raw {
+        /// Method name, e.g. `eth_getStorageAt`
+        method: String,
+        /// Positional params, each parsed as JSON or else passed as a string
+        #[arg(allow_hyphen_values = true)]
+        params: Vec<String>,
+    },
+    /// Show base fees and priority-fee percentiles of recent blocks, with
+    /// suggested EIP-1559 fee caps
+    #[command(alias = "feehistory")]
+    FeeHistory {
+        /// Number of recent blocks to include
+        #[arg(short, long, default_value = "20", value_parser = clap::value_parser!(u64).range(1..=1024))]
+        blocks: u64,
+        /// Priority-fee percentiles to report, ascending and comma-separated
+        #[arg(long, value_delimiter = ',', default_value = "25,50,75")]
+        percentiles: Vec<f64>,
+    },
+    /// Snapshot of the node's transaction pool (needs the txpool namespace)
+    Mempool {
+        /// Number of senders to list by pending transactions
+        #[arg(long, default_value = "10")]
+        top: usize,
     },
     /// Show gas statistics for recent blocks
     Gas {
         /// Number of blocks to analyze (default: 100)
         #[arg(short, long, default_value = "100")]
         blocks: usize,
+        /// Analyze an explicit range starting at this block instead of the
+        /// latest blocks
+        #[arg(long, visible_alias = "from", conflicts_with = "blocks")]
+        from_block: Option<u64>,
+        /// Last block of the range (default: latest)
+        #[arg(long, visible_alias = "to", requires = "from_block")]
+        to_block: Option<u64>,
+        /// Print only the statistics table, without the trend visualization
+        #[arg(long)]
+        summary_only: bool,
+        /// Print one CSV row per block to stdout instead of the tables
+        #[arg(long, conflicts_with = "summary_only")]
+        csv_stdout: bool,
+        /// Aggregate over individual transactions instead of blocks
+        #[arg(long, conflicts_with_all = ["summary_only", "csv_stdout"])]
+        per_tx: bool,
+        /// With --per-tx, how many of the largest transactions to list
+        #[arg(long, default_value = "10", requires = "per_tx")]
+        top: usize,
+        /// How many blocks' receipts to fetch at once
+        #[arg(long, default_value = "16", value_parser = clap::value_parser!(u16).range(1..))]
+        concurrency: u16,
     },
 }
 
+impl Commands {
+    /// Whether the command's results are flat rows that `--format csv` can
+    /// print
+    fn supports_csv(&self) -> bool {
+        matches!(self, Commands::Latest { .. } | Commands::Gas { .. })
+    }
+
+    /// Output meant for other programs, or a stream that never ends; neither
+    /// should go through a pager
+    fn is_machine_output(&self) -> bool {
+        match self {
+            Commands::Transaction { raw_tx, summary_json, raw_json, .. } => *raw_tx || *summary_json || *raw_json,
+            Commands::Block { raw_json, .. } | Commands::Receipt { raw_json, .. } => *raw_json,
+            Commands::Gas { csv_stdout, .. } => *csv_stdout,
+            Commands::Watch { .. } | Commands::Raw { .. } => true,
+            _ => false,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    color_eyre::install()?;
-    
     let cli = Cli::parse();
-    let explorer = BlockExplorer::new(&cli.rpc_url).await?;
+    // Error reports go to stderr, so --color is weighed against that here
+    let color_errors = cli.color.enabled(std::io::stderr().is_terminal(), std::env::var_os("NO_COLOR").as_deref());
+    let hooks = HookBuilder::default();
+    if color_errors { hooks } else { hooks.theme(Theme::new()) }.install()?;
+
+    // Dropping `run` on Ctrl-C cancels any request or retry wait in flight
+    // and still restores stdout from the pager or output file. `watch`
+    // handles Ctrl-C itself between blocks, so `run` is polled first.
+    let outcome = tokio::select! {
+        biased;
+        outcome = run(cli) => outcome,
+        _ = tokio::signal::ctrl_c() => Err(ExtractorError::Interrupted.into()),
+    };
+    if let Err(report) = outcome {
+        // Scripts rely on the exit code to tell failures apart (see README)
+        eprintln!("Error: {:?}", report);
+        std::process::exit(error::exit_code(&report));
+    }
+    Ok(())
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    if cli.format == OutputFormat::Csv && !cli.command.supports_csv() {
+        return Err(ExtractorError::InvalidInput("--format csv is only supported by `latest` and `gas`".into()).into());
+    }
+    if cli.format == OutputFormat::Csv && matches!(cli.command, Commands::Gas { csv_stdout: true, .. }) {
+        return Err(ExtractorError::InvalidInput("--csv-stdout already prints CSV; drop --format csv".into()).into());
+    }
+    let mut abis = match &cli.abi_dir {
+        Some(dir) => AbiRegistry::load_dir(dir)?,
+        None => AbiRegistry::default(),
+    };
+    for path in &cli.abi {
+        abis.load_file(path)?;
+    }
+    if let Some(path) = &cli.address_book {
+        labels::load_address_book(path)?;
+    }
+    let prices = if cli.fiat {
+        Some(PriceOracle::new(cli.eth_price_source, cli.eth_price_url.clone())?)
+    } else {
+        None
+    };
+    let cache = CacheConfig {
+        disabled: cli.no_cache,
+        dir: cli.cache_dir.clone(),
+        capacity: cli.cache_size,
+        ttl: cli.cache_ttl,
+    };
+    let retry = if cli.no_retry {
+        RetryConfig::NONE
+    } else {
+        RetryConfig::new(Duration::from_secs(cli.max_retry_secs), Duration::from_millis(cli.retry_initial_ms))?
+    };
+    let client = RethClient::new(&cli.rpc_url, &cache, Duration::from_secs(cli.timeout), cli.quiet).await?.with_retry(retry);
+    utils::install_native_currency(client.native_currency());
+    if cli.show_chain {
+        eprintln!("🔗 Chain ID {}, native currency {}", client.chain_id(), client.native_currency().symbol);
+    }
+    let mut explorer = BlockExplorer::new(client, abis, cli.gas_format, cli.on_error, cli.format, prices, cli.quiet);
+    if cli.online_4byte {
+        explorer = explorer.with_selectors(SelectorLookup::online()?);
+    }
+    let machine_output = cli.format != OutputFormat::Table || cli.command.is_machine_output();
+    // Before the pager, which then sees stdout isn't a terminal and stays out
+    let _output_file = cli.output_file.as_deref().map(|path| OutputFile::create(path, cli.quiet)).transpose()?;
+    // After --output-file, whose file shouldn't get colors, but before the
+    // pager, which passes them through
+    output::install_color(cli.color.enabled(std::io::stdout().is_terminal(), std::env::var_os("NO_COLOR").as_deref()));
+    let _pager = Pager::start(cli.pager, machine_output)?;
+    let show_stats = cli.stats;
     
     match cli.command {
-        Commands::Block { block_id } => {
-            explorer.show_block(&block_id).await?;
+        Commands::Block { block_id, raw_json: true, .. } => {
+            explorer.show_raw_block(&block_id).await?;
+        }
+        Commands::Block { block_id, min_value, tx_value_sum, show_logs_bloom, diff_parent, verbose, top_gas, raw_json: false } => {
+            let options = BlockOptions { min_value, value_sum: tx_value_sum, show_logs_bloom, diff_parent, verbose, top_gas };
+            explorer.show_block(&block_id, &options).await?;
+        }
+        Commands::Diff { a, b } => {
+            explorer.show_block_diff(&a, &b).await?;
+        }
+        Commands::Genesis => {
+            explorer.show_genesis().await?;
+        }
+        Commands::Transaction { tx_hash, raw_tx, explain_revert, position_context, summary_json, raw_json } => {
+            if raw_json {
+                explorer.show_raw_tx_json(&tx_hash, false).await?;
+            } else if raw_tx {
+                explorer.show_raw_transaction(&tx_hash).await?;
+            } else if summary_json {
+                explorer.show_transaction_summary(&tx_hash).await?;
+            } else {
+                explorer.show_transaction(&tx_hash, explain_revert, position_context).await?;
+            }
+        }
+        Commands::Receipt { tx_hash, raw_json } => {
+            if raw_json {
+                explorer.show_raw_tx_json(&tx_hash, true).await?;
+            } else {
+                explorer.show_receipt(&tx_hash).await?;
+            }
+        }
+        Commands::Account { address, addresses, addresses_file, block, block_hash, code_hash, first_tx, pending } => {
+            let block = match block_hash {
+                Some(hash) => Some(explorer.resolve_block_hash(hash).await?),
+                None => block,
+            };
+            let mut all: Vec<String> = address.into_iter().chain(addresses).collect();
+            if let Some(path) = &addresses_file {
+                let contents = std::fs::read_to_string(path)
+                    .map_err(|e| ExtractorError::InvalidInput(format!("Failed to read {}: {}", path.display(), e)))?;
+                all.extend(utils::parse_address_list(&contents));
+            }
+            match all.as_slice() {
+                [single] => explorer.show_account(single, block, code_hash, first_tx, pending).await?,
+                _ if code_hash || first_tx || pending => {
+                    return Err(ExtractorError::InvalidInput("--code-hash, --first-tx and --pending take a single account".into()).into());
+                }
+                _ => explorer.show_accounts(&all, block).await?,
+            }
+        }
+        Commands::Call { to, data, data_file, from, value, block } => {
+            let data = match (data, data_file) {
+                (_, Some(path)) => {
+                    let contents = std::fs::read_to_string(&path)
+                        .map_err(|e| ExtractorError::InvalidInput(format!("Failed to read {}: {}", path.display(), e)))?;
+                    contents.trim().parse::<Bytes>()
+                        .map_err(|e| ExtractorError::InvalidInput(format!("Invalid calldata in {}: {}", path.display(), e)))?
+                }
+                (data, None) => data.unwrap_or_default(),
+            };
+            explorer.show_call(&CallRequest { to, from, value, data }, block).await?;
+        }
+        Commands::Storage { address, slot, block } => {
+            explorer.show_storage(address, slot, block).await?;
+        }
+        Commands::Code { address, block, out } => {
+            explorer.show_code(address, block, out.as_deref()).await?;
         }
-        Commands::Transaction { tx_hash } => {
-            explorer.show_transaction(&tx_hash).await?;
+        Commands::Creation { address } => {
+            explorer.show_creation(address).await?;
         }
-        Commands::Account { address, block } => {
-            explorer.show_account(&address, block).await?;
+        Commands::TokenBalance { token, address, block } => {
+            explorer.show_token_balance(token, &address, block).await?;
         }
-        Commands::Latest { count } => {
-            explorer.show_latest_blocks(count).await?;
+        Commands::Latest { count, since, tx_detail, include_pending } => {
+            explorer.show_latest_blocks(count, since, tx_detail, include_pending).await?;
         }
-        Commands::Gas { blocks } => {
-            explorer.show_gas_statistics(blocks).await?;
+        Commands::Export { from, to, out, with_transactions } => {
+            explorer.export_blocks(from, to, &out, with_transactions).await?;
         }
+        Commands::TxsFrom { address, from_block, to_block, detect_gaps } => {
+            explorer.show_transactions_from(&address, from_block, to_block, detect_gaps).await?;
+        }
+        Commands::Logs { address: Some(token), from_block, to_block, chunk_size, batch_accounts_from_logs: true, bloom_prefilter, .. } => {
+            explorer.show_holder_snapshot(token, from_block, to_block, chunk_size, bloom_prefilter).await?;
+        }
+        Commands::Logs { address, from_block, to_block, topic, chunk_size, count_only, per_chunk, sort_logs_by, reverse, bloom_prefilter, .. } => {
+            let topics = topic.iter()
+                .map(|t| match t.as_str() {
+                    "any" => Ok(None),
+                    t => t.parse::<B256>().map(Some)
+                        .map_err(|e| ExtractorError::InvalidInput(format!("Invalid topic {}: {}", t, e)).into()),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let filter = LogFilter { address, topics };
+            let options = LogsOptions { chunk_size, count_only, per_chunk, sort_by: sort_logs_by, reverse, bloom_prefilter };
+            explorer.show_logs(&filter, from_block, to_block, &options).await?;
+        }
+        Commands::Watch { value_threshold, poll_head_only, interval } => {
+            explorer.watch(value_threshold, poll_head_only, interval).await?;
+        }
+        Commands::ReplayTx { tx_hash } => {
+            explorer.show_replay(&tx_hash).await?;
+        }
+        Commands::Trace { tx_hash, tracer } => {
+            explorer.show_transaction_trace(&tx_hash, &tracer).await?;
+        }
+        Commands::TraceBlock { block_id, tracer } => {
+            explorer.show_block_trace(&block_id, &tracer).await?;
+        }
+        Commands::MinerStats { from_block, to_block, top, resolve_names } => {
+            explorer.show_miner_stats(from_block, to_block, top, resolve_names).await?;
+        }
+        Commands::Raw { method, params } => {
+            let params = params.iter().map(|p| utils::parse_raw_param(p)).collect();
+            explorer.show_raw(&method, params).await?;
+        }
+        Commands::FeeHistory { blocks, percentiles } => {
+            explorer.show_fee_history(blocks, &percentiles).await?;
+        }
+        Commands::Mempool { top } => {
+            explorer.show_mempool(top).await?;
+        }
+        Commands::Gas { blocks, from_block, to_block, summary_only, csv_stdout, per_tx, top, concurrency } => {
+            let concurrency = usize::from(concurrency);
+            let window = match from_block {
+                Some(from_block) => GasWindow::Range { from_block, to_block },
+                None => GasWindow::Latest(blocks),
+            };
+            if per_tx {
+                explorer.show_tx_gas_statistics(window, top, concurrency).await?;
+            } else {
+                explorer.show_gas_statistics(window, summary_only, csv_stdout, concurrency).await?;
+            }
+        }
+    }
+    if show_stats {
+        eprintln!("{}", explorer.client().stats());
     }
     
     Ok(())
@@ -0,0 +1,305 @@
+use alloy_primitives::U256;
+use color_eyre::{eyre::eyre, Result};
+use comfy_table::{Cell, Color, Table};
+use serde::{Serialize, Serializer};
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// How commands render their results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables
+    Table,
+    /// Pretty-printed JSON
+    Json,
+    /// YAML, easier to eyeball than JSON
+    Yaml,
+    /// One compact JSON document per line. `watch` streams an object per
+    /// event; other commands print their single document on one line.
+    Ndjson,
+    /// Comma-separated rows under a header row, for spreadsheets. Only
+    /// `latest` and `gas` support it.
+    Csv,
+}
+
+/// When table output is colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and `NO_COLOR` isn't set
+    Auto,
+    /// Always color, e.g. when piping into `less -R`
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color, given where stdout goes and the `NO_COLOR`
+    /// variable, which only counts when set to something
+    pub fn enabled(self, stdout_is_tty: bool, no_color: Option<&OsStr>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => stdout_is_tty && no_color.is_none_or(OsStr::is_empty),
+        }
+    }
+}
+
+/// Whether to color, decided once at startup
+static COLOR: OnceLock<bool> = OnceLock::new();
+
+/// Turn coloring on or off for the rest of the run. Only the first call
+/// takes effect.
+pub fn install_color(enabled: bool) {
+    let _ = COLOR.set(enabled);
+}
+
+/// Make `table` follow the color setting. Left alone, comfy-table would
+/// only color a terminal, which a pager or `--color always` pipe isn't.
+pub fn apply_color(table: &mut Table) {
+    if COLOR.get().copied().unwrap_or(false) {
+        table.enforce_styling();
+    } else {
+        table.force_no_tty();
+    }
+}
+
+/// A cell for a status shown with ✅, ❌ or ⏳, in green, red or yellow
+/// respectively when the table is colored
+pub fn status_cell(text: impl Into<String>) -> Cell {
+    let text = text.into();
+    let color = match text.chars().next() {
+        Some('✅') => Some(Color::Green),
+        Some('❌') => Some(Color::Red),
+        Some('⏳') => Some(Color::Yellow),
+        _ => None,
+    };
+    let cell = Cell::new(text);
+    match color {
+        Some(color) => cell.fg(color),
+        None => cell,
+    }
+}
+
+/// Serialize a result in a structured format. Both formats share one
+/// contract: hashes, addresses and bytes are `0x` strings and integers are
+/// decimal strings.
+pub fn render<T: Serialize>(value: &T, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(value)?),
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(value)?),
+        OutputFormat::Ndjson => Ok(serde_json::to_string(value)?),
+        OutputFormat::Table => Err(eyre!("Table output is rendered by each command")),
+        OutputFormat::Csv => Err(eyre!("CSV output is rendered with render_csv")),
+    }
+}
+
+/// Serialize flat rows as CSV with a header row taken from the field
+/// names, quoting fields as needed. Integers follow the same decimal
+/// string contract as `render`; absent values are empty fields.
+pub fn render_csv<T: Serialize + Default>(rows: &[T]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    if rows.is_empty() {
+        // The header comes from a row's field names, so render a
+        // placeholder and keep only its header line
+        writer.serialize(T::default())?;
+    }
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| eyre!("Failed to write CSV: {}", e))?;
+    let mut csv = String::from_utf8(bytes)?;
+    if rows.is_empty() {
+        csv.truncate(csv.find('\n').map_or(csv.len(), |end| end + 1));
+    }
+    Ok(csv)
+}
+
+/// This process's stdout redirected into a file for `--output-file`, so
+/// everything a command renders lands there while status lines stay on
+/// stderr. Dropping it flushes the file and restores stdout.
+pub struct OutputFile {
+    path: PathBuf,
+    saved_stdout: i32,
+    /// Skip the closing "written to" line
+    quiet: bool,
+}
+
+impl OutputFile {
+    /// Create `path` (and any missing parent directories) and send stdout
+    /// into it
+    #[cfg(unix)]
+    pub fn create(path: &Path, quiet: bool) -> Result<Self> {
+        use std::os::fd::AsRawFd;
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| eyre!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        let file = std::fs::File::create(path)
+            .map_err(|e| eyre!("Failed to create {}: {}", path.display(), e))?;
+
+        std::io::stdout().flush()?;
+        // SAFETY: plain descriptor juggling on fds this process owns, as for
+        // the pager. The file stays open through the duplicate on stdout.
+        let saved_stdout = unsafe {
+            let saved = libc::dup(libc::STDOUT_FILENO);
+            if saved < 0 || libc::dup2(file.as_raw_fd(), libc::STDOUT_FILENO) < 0 {
+                return Err(eyre!("Failed to redirect output to {}: {}", path.display(), std::io::Error::last_os_error()));
+            }
+            saved
+        };
+        Ok(Self { path: path.to_path_buf(), saved_stdout, quiet })
+    }
+
+    #[cfg(not(unix))]
+    pub fn create(_path: &Path, _quiet: bool) -> Result<Self> {
+        Err(eyre!("--output-file is only supported on Unix; redirect stdout instead"))
+    }
+}
+
+impl Drop for OutputFile {
+    fn drop(&mut self) {
+        let _ = std::io::stdout().flush();
+        #[cfg(unix)]
+        unsafe {
+            libc::dup2(self.saved_stdout, libc::STDOUT_FILENO);
+            libc::close(self.saved_stdout);
+        }
+        if !self.quiet {
+            eprintln!("💾 Output written to {}", self.path.display());
+        }
+    }
+}
+
+/// Serialize a U256 as a decimal string rather than alloy's default hex,
+/// so amounts read naturally. Deserializing accepts either form.
+pub fn serialize_u256<S: Serializer>(value: &U256, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+/// `serialize_u256` for optional fields
+pub fn serialize_opt_u256<S: Serializer>(value: &Option<U256>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serialize_u256(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// `serialize_u256` for each element of a list
+pub fn serialize_u256_seq<S: Serializer>(values: &[U256], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(values.iter().map(|value| value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{Block, Transaction};
+    use alloy_primitives::{Address, Bytes, B256};
+
+    fn sample_transaction() -> Transaction {
+        Transaction {
+            hash: B256::with_last_byte(1),
+            block_number: Some(U256::from(18_000_000)),
+            transaction_index: Some(U256::from(3)),
+            from: Address::with_last_byte(2),
+            to: None,
+            nonce: U256::from(7),
+            value: U256::from(1_500_000_000_000_000_000u128),
+            gas: U256::from(21_000),
+            gas_price: U256::from(20_000_000_000u64),
+            tx_type: Some(2),
+            max_fee_per_gas: Some(U256::from(30_000_000_000u64)),
+            max_priority_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: Vec::new(),
+            gas_used: Some(U256::from(21_000)),
+            status: Some(U256::from(1)),
+            input: Bytes::from(vec![0xde, 0xad]),
+            access_list: None,
+            logs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_color_choice() {
+        assert!(ColorChoice::Auto.enabled(true, None));
+        assert!(!ColorChoice::Auto.enabled(false, None));
+        assert!(!ColorChoice::Auto.enabled(true, Some(OsStr::new("1"))));
+        // An empty NO_COLOR doesn't count as set
+        assert!(ColorChoice::Auto.enabled(true, Some(OsStr::new(""))));
+        assert!(ColorChoice::Always.enabled(false, Some(OsStr::new("1"))));
+        assert!(!ColorChoice::Never.enabled(true, None));
+    }
+
+    #[test]
+    fn test_yaml_transaction_contract() {
+        let tx = sample_transaction();
+        let yaml = render(&tx, OutputFormat::Yaml).unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(value["value"].as_str(), Some("1500000000000000000"));
+        assert_eq!(value["block_number"].as_str(), Some("18000000"));
+        assert_eq!(value["hash"].as_str(), Some(format!("{:?}", tx.hash).as_str()));
+        assert_eq!(value["input"].as_str(), Some("0xdead"));
+        assert!(value["to"].is_null());
+
+        let round_trip: Transaction = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(round_trip.value, tx.value);
+        assert_eq!(round_trip.hash, tx.hash);
+    }
+
+    #[test]
+    fn test_yaml_block_contract() {
+        let block = Block {
+            number: U256::from(18_000_000),
+            hash: Some(B256::with_last_byte(9)),
+            parent_hash: B256::with_last_byte(8),
+            timestamp: U256::from(1_693_066_895),
+            gas_used: U256::from(15_000_000),
+            gas_limit: U256::from(30_000_000),
+            base_fee_per_gas: Some(U256::from(20_000_000_000u64)),
+            transactions: vec![B256::with_last_byte(1)],
+            full_transactions: vec![sample_transaction()],
+            miner: Address::with_last_byte(3),
+            size: U256::from(544),
+            ..Default::default()
+        };
+        let yaml = render(&block, OutputFormat::Yaml).unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(value["gas_used"].as_str(), Some("15000000"));
+        assert_eq!(value["base_fee_per_gas"].as_str(), Some("20000000000"));
+        assert_eq!(value["miner"].as_str(), Some(block.miner.to_string().as_str()));
+        assert_eq!(value["full_transactions"][0]["nonce"].as_str(), Some("7"));
+
+        let round_trip: Block = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(round_trip.gas_used, block.gas_used);
+        assert_eq!(round_trip.hash, block.hash);
+    }
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        #[derive(Serialize, Default)]
+        struct Row {
+            #[serde(serialize_with = "serialize_u256")]
+            number: U256,
+            hash: Option<B256>,
+            label: &'static str,
+        }
+        let rows = [
+            Row { number: U256::from(18_000_000), hash: Some(B256::with_last_byte(1)), label: "plain" },
+            Row { number: U256::MAX, hash: None, label: "has, comma" },
+        ];
+        let csv = render_csv(&rows).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "number,hash,label");
+        assert_eq!(lines[1], format!("18000000,{:?},plain", B256::with_last_byte(1)));
+        assert_eq!(lines[2], format!("{},,\"has, comma\"", U256::MAX));
+
+        // No rows still gets a header
+        assert_eq!(render_csv::<Row>(&[]).unwrap(), "number,hash,label\n");
+    }
+}
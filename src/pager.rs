@@ -0,0 +1,134 @@
+use color_eyre::{eyre::eyre, Result};
+use std::io::{IsTerminal, Write};
+use std::process::{Child, Command, Stdio};
+
+/// When long output is sent through a pager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PagerMode {
+    /// Page only when the output doesn't fit on one screen. The check is
+    /// left to `less -F`, so another `$PAGER`, or a `$LESS` without `F`,
+    /// pages short output too.
+    Auto,
+    /// Always page, even short output
+    Always,
+    /// Never page
+    Never,
+}
+
+/// Pager used when `$PAGER` isn't set
+const DEFAULT_PAGER: &str = "less";
+
+/// `less` options when `$LESS` isn't set: pass colors through, don't clear
+/// the screen on exit, and with `auto` quit straight away if the output
+/// fits on one screen
+const LESS_AUTO: &str = "FRX";
+const LESS_ALWAYS: &str = "RX";
+
+/// Whether to page at all. Output that isn't going to a terminal, or that
+/// is meant for other programs, is never paged.
+pub fn should_page(mode: PagerMode, stdout_is_tty: bool, machine_output: bool) -> bool {
+    mode != PagerMode::Never && stdout_is_tty && !machine_output
+}
+
+/// The pager command to run, or `None` if paging was turned off through
+/// `$PAGER` (empty or `cat`)
+fn pager_command(env_pager: Option<String>) -> Option<String> {
+    let pager = env_pager.unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let pager = pager.trim();
+    (!pager.is_empty() && pager != "cat").then(|| pager.to_string())
+}
+
+/// A running pager with this process's stdout redirected into it. Dropping
+/// it flushes stdout, restores the terminal and waits for the user to quit.
+pub struct Pager {
+    child: Child,
+    saved_stdout: i32,
+    /// SIGPIPE handler to put back once the pager is gone
+    #[cfg(unix)]
+    saved_sigpipe: libc::sighandler_t,
+}
+
+impl Pager {
+    /// Start the pager if `mode` calls for it. The caller keeps the result
+    /// alive until all output has been written.
+    pub fn start(mode: PagerMode, machine_output: bool) -> Result<Option<Pager>> {
+        if !should_page(mode, std::io::stdout().is_terminal(), machine_output) {
+            return Ok(None);
+        }
+        let Some(command) = pager_command(std::env::var("PAGER").ok()) else {
+            return Ok(None);
+        };
+        Self::spawn(&command, mode)
+    }
+
+    #[cfg(unix)]
+    fn spawn(command: &str, mode: PagerMode) -> Result<Option<Pager>> {
+        use std::os::fd::AsRawFd;
+
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command).stdin(Stdio::piped());
+        if std::env::var_os("LESS").is_none() {
+            process.env("LESS", if mode == PagerMode::Always { LESS_ALWAYS } else { LESS_AUTO });
+        }
+        let mut child = process
+            .spawn()
+            .map_err(|e| eyre!("Failed to start pager `{}`: {}", command, e))?;
+        let stdin = child.stdin.take().ok_or_else(|| eyre!("Pager has no stdin"))?;
+
+        std::io::stdout().flush()?;
+        // SAFETY: plain descriptor juggling on fds this process owns. Once the
+        // pager quits, writes should end the process quietly as they would
+        // for any pipe, rather than panic in `println!`. The default SIGPIPE
+        // action only lasts while the pager runs.
+        let (saved_stdout, saved_sigpipe) = unsafe {
+            let saved = libc::dup(libc::STDOUT_FILENO);
+            if saved < 0 || libc::dup2(stdin.as_raw_fd(), libc::STDOUT_FILENO) < 0 {
+                return Err(eyre!("Failed to redirect output to the pager: {}", std::io::Error::last_os_error()));
+            }
+            (saved, libc::signal(libc::SIGPIPE, libc::SIG_DFL))
+        };
+        drop(stdin);
+        Ok(Some(Pager { child, saved_stdout, saved_sigpipe }))
+    }
+
+    #[cfg(not(unix))]
+    fn spawn(_command: &str, _mode: PagerMode) -> Result<Option<Pager>> {
+        Ok(None)
+    }
+}
+
+impl Drop for Pager {
+    fn drop(&mut self) {
+        let _ = std::io::stdout().flush();
+        // Restoring stdout closes the pager's input, which lets it see the end
+        #[cfg(unix)]
+        unsafe {
+            libc::dup2(self.saved_stdout, libc::STDOUT_FILENO);
+            libc::close(self.saved_stdout);
+            libc::signal(libc::SIGPIPE, self.saved_sigpipe);
+        }
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_page() {
+        assert!(should_page(PagerMode::Auto, true, false));
+        assert!(should_page(PagerMode::Always, true, false));
+        assert!(!should_page(PagerMode::Never, true, false));
+        assert!(!should_page(PagerMode::Always, false, false));
+        assert!(!should_page(PagerMode::Auto, true, true));
+    }
+
+    #[test]
+    fn test_pager_command() {
+        assert_eq!(pager_command(None).as_deref(), Some("less"));
+        assert_eq!(pager_command(Some("most -s".into())).as_deref(), Some("most -s"));
+        assert_eq!(pager_command(Some("cat".into())), None);
+        assert_eq!(pager_command(Some("  ".into())), None);
+    }
+}
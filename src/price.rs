@@ -0,0 +1,278 @@
+use alloy_dyn_abi::DynSolType;
+use alloy_primitives::{address, Address, I256};
+use async_trait::async_trait;
+use color_eyre::{eyre::eyre, Result};
+use moka::future::Cache;
+use std::time::Duration;
+
+use crate::rpc::RethClient;
+
+/// Chainlink's ETH/USD aggregator proxy on mainnet
+const CHAINLINK_ETH_USD: Address = address!("5f4eC3Df9cbd43714FE2740f5E3616155c5b8419");
+/// `latestRoundData()`; the feed answers with 8 decimals
+const LATEST_ROUND_DATA_SELECTOR: [u8; 4] = [0xfe, 0xaf, 0x96, 0x8c];
+const CHAINLINK_DECIMALS: i32 = 8;
+/// Oldest round still trusted: twice the feed's one-hour heartbeat
+const CHAINLINK_MAX_AGE: Duration = Duration::from_secs(2 * 60 * 60);
+
+const COINGECKO_URL: &str = "https://api.coingecko.com/api/v3/simple/price?ids=ethereum&vs_currencies=usd";
+
+/// How long a fetched price is reused before asking again
+const PRICE_TTL: Duration = Duration::from_secs(60);
+/// How long a failure of every source is reported without asking again, so
+/// a table of fiat values doesn't wait out each source's timeout per row
+const PRICE_FAILURE_TTL: Duration = Duration::from_secs(10);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where the ETH/USD price for fiat annotations comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PriceSourceKind {
+    /// CoinGecko's public API
+    Coingecko,
+    /// The Chainlink ETH/USD feed, read through the node; no external API
+    Chainlink,
+    /// A URL given with `--eth-price-url`
+    CustomUrl,
+}
+
+/// One way of finding out the current ETH/USD price
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    fn name(&self) -> &str;
+    async fn eth_usd(&self, client: &RethClient) -> Result<f64>;
+}
+
+struct CoinGecko {
+    http: reqwest::Client,
+}
+
+#[async_trait]
+impl PriceSource for CoinGecko {
+    fn name(&self) -> &str {
+        "coingecko"
+    }
+
+    async fn eth_usd(&self, _client: &RethClient) -> Result<f64> {
+        let body: serde_json::Value = self.http.get(COINGECKO_URL).send().await?.error_for_status()?.json().await?;
+        body["ethereum"]["usd"].as_f64().ok_or_else(|| eyre!("Unexpected CoinGecko response: {}", body))
+    }
+}
+
+struct Chainlink;
+
+#[async_trait]
+impl PriceSource for Chainlink {
+    fn name(&self) -> &str {
+        "chainlink"
+    }
+
+    async fn eth_usd(&self, client: &RethClient) -> Result<f64> {
+        // The aggregator address is mainnet's; elsewhere it is another contract or nothing
+        if client.chain_id() != 1 {
+            return Err(eyre!("the ETH/USD feed is only read on Ethereum mainnet"));
+        }
+        let output = client.call(CHAINLINK_ETH_USD, LATEST_ROUND_DATA_SELECTOR.to_vec()).await?;
+        let round = DynSolType::Tuple(vec![
+            DynSolType::Uint(80),
+            DynSolType::Int(256),
+            DynSolType::Uint(256),
+            DynSolType::Uint(256),
+            DynSolType::Uint(80),
+        ])
+        .abi_decode_params(&output)
+        .map_err(|e| eyre!("Invalid Chainlink response: {}", e))?;
+        let fields = round.as_tuple().unwrap_or_default();
+        let answer = fields.get(1)
+            .and_then(|answer| answer.as_int())
+            .map(|(answer, _)| answer)
+            .ok_or_else(|| eyre!("Chainlink response has no answer"))?;
+        let updated_at = fields.get(3)
+            .and_then(|updated_at| updated_at.as_uint())
+            .map(|(updated_at, _)| updated_at.saturating_to::<u64>())
+            .ok_or_else(|| eyre!("Chainlink response has no update time"))?;
+        check_round_age(updated_at, chrono::Utc::now().timestamp() as u64)?;
+        chainlink_answer_to_usd(answer)
+    }
+}
+
+/// Reject a round not updated within `CHAINLINK_MAX_AGE` of `now`, which
+/// means the feed has stopped
+fn check_round_age(updated_at: u64, now: u64) -> Result<()> {
+    let age = now.saturating_sub(updated_at);
+    if age > CHAINLINK_MAX_AGE.as_secs() {
+        return Err(eyre!("Chainlink price is stale (last updated {}s ago)", age));
+    }
+    Ok(())
+}
+
+/// Scale a Chainlink answer to dollars, rejecting the nonsensical
+fn chainlink_answer_to_usd(answer: I256) -> Result<f64> {
+    if answer <= I256::ZERO {
+        return Err(eyre!("Chainlink reported a non-positive price"));
+    }
+    let answer: f64 = answer.to_string().parse()?;
+    Ok(answer / 10f64.powi(CHAINLINK_DECIMALS))
+}
+
+/// Any endpoint returning the price as a bare JSON number or under a
+/// `usd` or `price` key
+struct CustomUrl {
+    http: reqwest::Client,
+    url: String,
+}
+
+#[async_trait]
+impl PriceSource for CustomUrl {
+    fn name(&self) -> &str {
+        &self.url
+    }
+
+    async fn eth_usd(&self, _client: &RethClient) -> Result<f64> {
+        let body: serde_json::Value = self.http.get(&self.url).send().await?.error_for_status()?.json().await?;
+        body.as_f64()
+            .or_else(|| body["usd"].as_f64())
+            .or_else(|| body["price"].as_f64())
+            .ok_or_else(|| eyre!("No price in response from {}: {}", self.url, body))
+    }
+}
+
+/// Price lookups with fallback: the chosen source first, then the others,
+/// with the answer cached for `PRICE_TTL` and a total failure for
+/// `PRICE_FAILURE_TTL`
+pub struct PriceOracle {
+    sources: Vec<Box<dyn PriceSource>>,
+    cache: Cache<(), f64>,
+    failure: Cache<(), String>,
+}
+
+impl PriceOracle {
+    pub fn new(preferred: PriceSourceKind, custom_url: Option<String>) -> Result<Self> {
+        let http = reqwest::Client::builder().timeout(HTTP_TIMEOUT).build()?;
+        let mut order = vec![preferred];
+        order.extend([PriceSourceKind::Chainlink, PriceSourceKind::Coingecko].into_iter().filter(|kind| *kind != preferred));
+
+        let sources = order.into_iter()
+            .map(|kind| -> Result<Box<dyn PriceSource>> {
+                Ok(match kind {
+                    PriceSourceKind::Coingecko => Box::new(CoinGecko { http: http.clone() }),
+                    PriceSourceKind::Chainlink => Box::new(Chainlink),
+                    PriceSourceKind::CustomUrl => Box::new(CustomUrl {
+                        http: http.clone(),
+                        url: custom_url.clone().ok_or_else(|| eyre!("--eth-price-source custom-url needs --eth-price-url"))?,
+                    }),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            sources,
+            cache: Cache::builder().time_to_live(PRICE_TTL).build(),
+            failure: Cache::builder().time_to_live(PRICE_FAILURE_TTL).build(),
+        })
+    }
+
+    /// Current ETH/USD price from the first source that answers
+    pub async fn eth_usd(&self, client: &RethClient) -> Result<f64> {
+        if let Some(price) = self.cache.get(&()).await {
+            return Ok(price);
+        }
+        if let Some(failure) = self.failure.get(&()).await {
+            return Err(eyre!(failure));
+        }
+
+        let mut failures = Vec::new();
+        for source in &self.sources {
+            match source.eth_usd(client).await {
+                Ok(price) => {
+                    self.cache.insert((), price).await;
+                    return Ok(price);
+                }
+                Err(e) => failures.push(format!("{}: {}", source.name(), e)),
+            }
+        }
+        let failure = format!("No ETH price source answered ({})", failures.join("; "));
+        self.failure.insert((), failure.clone()).await;
+        Err(eyre!(failure))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{CacheConfig, DEFAULT_RPC_TIMEOUT};
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_primitives::{Bytes, U256};
+    use serde_json::json;
+    use crate::test_support::{self, rpc, rpc_result};
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_chainlink_answer_to_usd() {
+        let answer = I256::try_from(312_345_000_000i64).unwrap();
+        assert_eq!(chainlink_answer_to_usd(answer).unwrap(), 3123.45);
+        assert!(chainlink_answer_to_usd(I256::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_check_round_age() {
+        let now = 1_700_000_000;
+        assert!(check_round_age(now - 60, now).is_ok());
+        assert!(check_round_age(now - CHAINLINK_MAX_AGE.as_secs(), now).is_ok());
+        assert!(check_round_age(now - CHAINLINK_MAX_AGE.as_secs() - 1, now).is_err());
+        assert!(check_round_age(0, now).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_chainlink() {
+        let server = test_support::mock_node().await;
+        Mock::given(path("/price"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        rpc("eth_call", |_| {
+            let round = DynSolValue::Tuple(vec![
+                DynSolValue::Uint(U256::from(1), 80),
+                DynSolValue::Int(I256::try_from(250_000_000_000i64).unwrap(), 256),
+                DynSolValue::Uint(U256::ZERO, 256),
+                DynSolValue::Uint(U256::from(chrono::Utc::now().timestamp() - 60), 256),
+                DynSolValue::Uint(U256::from(1), 80),
+            ]);
+            json!({ "result": Bytes::from(round.abi_encode_params()) })
+        })
+        .mount(&server)
+        .await;
+
+        let client = RethClient::new(&[server.uri()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        let oracle = PriceOracle::new(PriceSourceKind::CustomUrl, Some(format!("{}/price", server.uri()))).unwrap();
+        assert_eq!(oracle.eth_usd(&client).await.unwrap(), 2500.0);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_cached() {
+        let server = MockServer::start().await;
+        Mock::given(path("/price"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        // Not mainnet, so the Chainlink feed isn't even asked
+        rpc_result("eth_chainId", json!("0xaa36a7")).mount(&server).await;
+        rpc_result("eth_call", json!("0x")).expect(0).mount(&server).await;
+
+        let client = RethClient::new(&[server.uri()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        let mut oracle = PriceOracle::new(PriceSourceKind::CustomUrl, Some(format!("{}/price", server.uri()))).unwrap();
+        // The custom URL and Chainlink only, so the test never reaches CoinGecko
+        oracle.sources.truncate(2);
+        let first = oracle.eth_usd(&client).await.unwrap_err().to_string();
+        assert!(first.contains("mainnet"));
+        assert_eq!(oracle.eth_usd(&client).await.unwrap_err().to_string(), first);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.iter().filter(|request| request.url.path() == "/price").count(), 1);
+    }
+
+    #[test]
+    fn test_custom_url_required() {
+        assert!(PriceOracle::new(PriceSourceKind::CustomUrl, None).is_err());
+    }
+}
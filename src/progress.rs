@@ -0,0 +1,102 @@
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::utils::format_number;
+
+/// How often the bar is redrawn at most; every block would flood a slow
+/// terminal on fast ranges
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 20;
+
+/// A one-line progress bar on stderr for long block scans, with rate and
+/// ETA. Safe to advance from concurrent tasks.
+pub struct Progress {
+    label: String,
+    total: u64,
+    done: AtomicU64,
+    started: Instant,
+    last_draw: Mutex<Option<Instant>>,
+    /// Off under `--quiet` and when stderr isn't a terminal, when nothing is
+    /// drawn at all
+    enabled: bool,
+}
+
+impl Progress {
+    /// A bar drawn only if `enabled` and stderr is a terminal; redirected
+    /// into a log, every redraw would be one more long line
+    pub fn new(label: impl Into<String>, total: u64, enabled: bool) -> Self {
+        Self {
+            label: label.into(),
+            total,
+            done: AtomicU64::new(0),
+            started: Instant::now(),
+            last_draw: Mutex::new(None),
+            enabled: enabled && std::io::stderr().is_terminal(),
+        }
+    }
+
+    /// Count `n` more blocks done
+    pub fn inc(&self, n: u64) {
+        let done = self.done.fetch_add(n, Ordering::Relaxed) + n;
+        if !self.enabled {
+            return;
+        }
+        let mut last_draw = self.last_draw.lock().unwrap_or_else(|e| e.into_inner());
+        if last_draw.is_some_and(|at| at.elapsed() < REDRAW_INTERVAL) && done < self.total {
+            return;
+        }
+        *last_draw = Some(Instant::now());
+        eprint!("\r{}", render(&self.label, done, self.total, self.started.elapsed()));
+        let _ = std::io::stderr().flush();
+    }
+
+    /// Draw the final state and end the line
+    pub fn finish(&self) {
+        if self.enabled {
+            let done = self.done.load(Ordering::Relaxed);
+            eprintln!("\r{}", render(&self.label, done, self.total, self.started.elapsed()));
+        }
+    }
+}
+
+/// The bar's text for `done` of `total` after `elapsed`
+fn render(label: &str, done: u64, total: u64, elapsed: Duration) -> String {
+    let fraction = if total == 0 { 1.0 } else { (done as f64 / total as f64).min(1.0) };
+    let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled));
+    let rate = done as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    let eta = match total.saturating_sub(done) {
+        0 => "done".to_string(),
+        _ if done == 0 => "ETA --".to_string(),
+        left => format!("ETA {}", format_eta(left as f64 / rate)),
+    };
+    format!(
+        "{} [{}] {}/{} blocks ({:.1} blocks/s, {})",
+        label, bar, format_number(done), format_number(total), rate, eta
+    )
+}
+
+fn format_eta(seconds: f64) -> String {
+    let seconds = seconds.ceil() as u64;
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let line = render("📊 Gas", 250, 1_000, Duration::from_secs(5));
+        assert_eq!(line, "📊 Gas [█████░░░░░░░░░░░░░░░] 250/1,000 blocks (50.0 blocks/s, ETA 15s)");
+        assert!(render("📊 Gas", 0, 1_000, Duration::ZERO).ends_with("ETA --)"));
+        assert!(render("📊 Gas", 1_000, 1_000, Duration::from_secs(1)).ends_with("done)"));
+        assert_eq!(format_eta(125.0), "2m 05s");
+    }
+}
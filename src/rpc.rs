@@ -1,51 +1,510 @@
-use alloy_primitives::{Address, B256, U256};
+use alloy_consensus::TxEnvelope;
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_eips::eip2930::AccessList;
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{b256, keccak256, Address, Bloom, Bytes, B256, U256};
 use backoff::ExponentialBackoffBuilder;
-use color_eyre::{eyre::eyre, Result};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
 use jsonrpsee::{
     http_client::{HttpClient, HttpClientBuilder},
-    core::client::ClientT,
+    ws_client::{WsClient, WsClientBuilder},
+    core::{client::{Client, ClientT, SubscriptionClientT}, params::{ArrayParams, BatchRequestBuilder}},
     rpc_params,
+    types::error::{INVALID_PARAMS_CODE, INVALID_REQUEST_CODE, METHOD_NOT_FOUND_CODE},
 };
 use moka::future::Cache;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
 use std::time::Duration;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::output::{serialize_opt_u256, serialize_u256, serialize_u256_seq};
+use crate::disk_cache::{DiskCache, EndpointRecord};
+use crate::error::RethClientError;
+use crate::progress::Progress;
+use crate::utils::{
+    block_chunks, block_runs, bloom_might_contain, format_number, hex_to_decimal, namehash, native_currency_for_chain, parse_status, ErrorTally,
+    NativeCurrency, EIP1967_IMPLEMENTATION_SLOT,
+};
+
+/// What the client's methods return: every failure is a `RethClientError`
+pub type Result<T, E = RethClientError> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Block {
+    #[serde(serialize_with = "serialize_u256")]
     pub number: U256,
-    pub hash: B256,
+    /// `None` for the pending block, which isn't sealed yet
+    pub hash: Option<B256>,
     pub parent_hash: B256,
+    #[serde(serialize_with = "serialize_u256")]
     pub timestamp: U256,
+    #[serde(serialize_with = "serialize_u256")]
     pub gas_used: U256,
+    #[serde(serialize_with = "serialize_u256")]
     pub gas_limit: U256,
+    /// EIP-1559 base fee, absent before London
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub base_fee_per_gas: Option<U256>,
     pub transactions: Vec<B256>,
+    /// Full transaction objects, present when the block was fetched hydrated
+    #[serde(default)]
+    pub full_transactions: Vec<Transaction>,
     pub miner: Address,
+    #[serde(serialize_with = "serialize_u256")]
     pub difficulty: U256,
+    #[serde(serialize_with = "serialize_u256")]
     pub size: U256,
+    #[serde(default)]
+    pub extra_data: Bytes,
+    /// Bloom filter over the addresses and topics of the block's logs
+    #[serde(default)]
+    pub logs_bloom: Option<Bloom>,
+    /// Hashes of the uncle (ommer) blocks it includes; always empty after
+    /// the merge
+    #[serde(default)]
+    pub uncles: Vec<B256>,
+    /// Validator withdrawals, present from Shanghai on
+    #[serde(default)]
+    pub withdrawals: Vec<Withdrawal>,
+    /// Gas used by the blobs of the block's blob transactions (EIP-4844),
+    /// absent before Cancun
+    #[serde(default)]
+    pub blob_gas_used: Option<u64>,
+    /// Blob gas above the target carried over from earlier blocks, which
+    /// sets the blob base fee
+    #[serde(default)]
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl Block {
+    /// Price of one unit of blob gas in this block, in wei, derived from
+    /// `excess_blob_gas`. Only known on mainnet (chain id 1): other chains
+    /// run their own fork schedules and blob parameters.
+    pub fn blob_base_fee(&self, chain_id: u64) -> Option<U256> {
+        if chain_id != 1 {
+            return None;
+        }
+        let excess = self.excess_blob_gas?;
+        let timestamp = self.timestamp.saturating_to::<u64>();
+        let fraction = BLOB_BASE_FEE_UPDATE_FRACTIONS.iter()
+            .rev()
+            .find(|(activated, _)| *activated <= timestamp)
+            .unwrap_or(&BLOB_BASE_FEE_UPDATE_FRACTIONS[0])
+            .1;
+        Some(fake_exponential(U256::from(MIN_BLOB_BASE_FEE), U256::from(excess), U256::from(fraction)))
+    }
+
+    /// Number of blobs included, from `blob_gas_used`
+    pub fn blob_count(&self) -> Option<u64> {
+        self.blob_gas_used.map(|gas| gas / GAS_PER_BLOB)
+    }
+
+    /// The transaction moving the most ETH in this block, if any
+    pub fn top_transaction_by_value(&self) -> Option<&Transaction> {
+        self.full_transactions.iter().max_by_key(|tx| tx.value)
+    }
+}
+
+/// Blob gas each blob consumes (EIP-4844)
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+const MIN_BLOB_BASE_FEE: u64 = 1;
+/// `BLOB_BASE_FEE_UPDATE_FRACTION` by the mainnet timestamp it took effect:
+/// Cancun, Prague (EIP-7691), then the two blob-parameter-only forks after
+/// Osaka. Testnets upgraded earlier, so their fees are only exact from the
+/// mainnet activation on.
+const BLOB_BASE_FEE_UPDATE_FRACTIONS: [(u64, u64); 4] = [
+    (1_710_338_135, 3_338_477),
+    (1_746_612_311, 5_007_716),
+    (1_765_290_071, 8_346_193),
+    (1_767_747_671, 11_684_671),
+];
+
+/// `factor * e ** (numerator / denominator)` in integers, as the EIP-4844
+/// spec computes it
+fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> U256 {
+    let mut output = U256::ZERO;
+    let mut accum = factor * denominator;
+    let mut i = U256::from(1);
+    while !accum.is_zero() {
+        output = output.saturating_add(accum);
+        // Absurd excess values would otherwise take forever to converge
+        let Some(next) = accum.checked_mul(numerator) else {
+            return U256::MAX;
+        };
+        accum = next / (denominator * i);
+        i += U256::from(1);
+    }
+    output / denominator
+}
+
+/// A validator withdrawal (EIP-4895) paid out by a block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Withdrawal {
+    pub index: u64,
+    pub validator_index: u64,
+    pub address: Address,
+    /// Amount in Gwei
+    pub amount: u64,
+}
+
+impl Withdrawal {
+    pub fn amount_wei(&self) -> U256 {
+        U256::from(self.amount) * U256::from(1_000_000_000u64)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub hash: B256,
+    #[serde(serialize_with = "serialize_opt_u256")]
     pub block_number: Option<U256>,
+    /// Position within the block, `None` while pending
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub transaction_index: Option<U256>,
     pub from: Address,
     pub to: Option<Address>,
+    #[serde(serialize_with = "serialize_u256")]
+    pub nonce: U256,
+    #[serde(serialize_with = "serialize_u256")]
     pub value: U256,
+    #[serde(serialize_with = "serialize_u256")]
     pub gas: U256,
+    /// For mined transactions nodes report the effective price paid, which
+    /// for EIP-1559 transactions is base fee plus the capped priority fee
+    #[serde(serialize_with = "serialize_u256")]
     pub gas_price: U256,
+    /// EIP-2718 type: 0 legacy, 1 access list, 2 EIP-1559, 3 blob, 4 set code
+    #[serde(default)]
+    pub tx_type: Option<u8>,
+    /// EIP-1559 fee cap and tip cap, `None` for earlier transaction types
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Blob gas fee cap (EIP-4844), `None` unless this is a blob transaction
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// Versioned hashes of the blobs a blob transaction carries
+    #[serde(default)]
+    pub blob_versioned_hashes: Vec<B256>,
+    #[serde(serialize_with = "serialize_opt_u256")]
     pub gas_used: Option<U256>,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    pub status: Option<U256>,
+    #[serde(default)]
+    pub input: Bytes,
+    /// Pre-declared addresses and storage keys (EIP-2930), `None` for legacy
+    /// transactions
+    #[serde(default)]
+    pub access_list: Option<AccessList>,
+    /// Logs emitted by the transaction, filled in from its receipt
+    #[serde(default)]
+    pub logs: Vec<Log>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Log {
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    pub block_number: Option<U256>,
+    pub transaction_hash: Option<B256>,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    pub log_index: Option<U256>,
+}
+
+/// A transaction's receipt: the outcome of executing it, including the
+/// logs it emitted and, for contract creations, the deployed address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Receipt {
+    pub transaction_hash: B256,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    pub block_number: Option<U256>,
+    pub block_hash: Option<B256>,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    pub transaction_index: Option<U256>,
+    pub from: Address,
+    pub to: Option<Address>,
+    #[serde(serialize_with = "serialize_opt_u256")]
     pub status: Option<U256>,
+    #[serde(serialize_with = "serialize_u256")]
+    pub gas_used: U256,
+    /// Gas used by this and every earlier transaction in the block
+    #[serde(serialize_with = "serialize_u256")]
+    pub cumulative_gas_used: U256,
+    #[serde(serialize_with = "serialize_u256")]
+    pub effective_gas_price: U256,
+    /// Address of the contract created by the transaction, if any
+    pub contract_address: Option<Address>,
+    pub logs_bloom: Option<Bloom>,
+    pub logs: Vec<Log>,
+}
+
+/// A signed transaction in its network (EIP-2718) encoding
+#[derive(Debug, Clone, Serialize)]
+pub struct RawTransaction {
+    pub bytes: Bytes,
+    /// Sender recovered from the signature in `bytes`
+    pub signer: Address,
+    /// Whether the bytes were re-encoded locally because the node doesn't
+    /// serve `eth_getRawTransactionByHash`
+    pub reconstructed: bool,
+}
+
+/// Result of executing a call with `eth_call`
+#[derive(Debug, Clone)]
+pub enum CallOutcome {
+    /// The call succeeded and returned this data
+    Success(Bytes),
+    /// The call reverted with this revert data (possibly empty)
+    Revert(Bytes),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pub address: Address,
+    #[serde(serialize_with = "serialize_u256")]
     pub balance: U256,
+    #[serde(serialize_with = "serialize_u256")]
     pub nonce: U256,
+    #[serde(serialize_with = "serialize_u256")]
     pub code_size: U256,
+    /// keccak256 of the deployed bytecode (the empty-code hash for EOAs)
+    pub code_hash: B256,
+}
+
+/// A contract call for `eth_call`; unset fields are left to the node
+#[derive(Debug, Clone, Default)]
+pub struct CallRequest {
+    pub to: Address,
+    pub from: Option<Address>,
+    pub value: Option<U256>,
+    pub data: Bytes,
+}
+
+/// Named blocks `eth_getBlockByNumber` accepts in place of a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockTag {
+    Latest,
+    Earliest,
+    Pending,
+    Safe,
+    Finalized,
+}
+
+impl BlockTag {
+    pub fn parse(input: &str) -> Option<BlockTag> {
+        match input {
+            "latest" => Some(BlockTag::Latest),
+            "earliest" => Some(BlockTag::Earliest),
+            "pending" => Some(BlockTag::Pending),
+            "safe" => Some(BlockTag::Safe),
+            "finalized" => Some(BlockTag::Finalized),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockTag::Latest => "latest",
+            BlockTag::Earliest => "earliest",
+            BlockTag::Pending => "pending",
+            BlockTag::Safe => "safe",
+            BlockTag::Finalized => "finalized",
+        }
+    }
+}
+
+/// Display details of an ERC-20 token. Both are optional in the standard,
+/// so either may be missing.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenMetadata {
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// Transaction counts from `txpool_status`
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TxPoolStatus {
+    /// Ready to be included in the next block
+    pub pending: u64,
+    /// Waiting on an earlier nonce from the same sender
+    pub queued: u64,
+}
+
+#[derive(Deserialize)]
+struct RawTxPoolStatus {
+    pending: U256,
+    queued: U256,
+}
+
+/// A transaction waiting in the pool, with what pool statistics need
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolTransaction {
+    pub hash: B256,
+    pub from: Address,
+    pub nonce: u64,
+    /// `maxFeePerGas`, or `gasPrice` for legacy transactions: the most
+    /// the sender will pay per gas
+    #[serde(serialize_with = "serialize_u256")]
+    pub gas_price: U256,
+}
+
+/// The pool's transactions from `txpool_content`, split like `TxPoolStatus`
+#[derive(Debug, Clone, Default)]
+pub struct TxPoolContent {
+    pub pending: Vec<PoolTransaction>,
+    pub queued: Vec<PoolTransaction>,
+}
+
+/// `txpool_content` nests transactions by sender, then by nonce
+#[derive(Deserialize)]
+struct RawTxPoolContent {
+    #[serde(default)]
+    pending: HashMap<Address, HashMap<String, RawPoolTransaction>>,
+    #[serde(default)]
+    queued: HashMap<Address, HashMap<String, RawPoolTransaction>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPoolTransaction {
+    hash: B256,
+    from: Address,
+    nonce: U256,
+    #[serde(default)]
+    gas_price: Option<U256>,
+    #[serde(default)]
+    max_fee_per_gas: Option<U256>,
+}
+
+/// One frame of a `callTracer` trace, with its nested sub-calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub call_type: String,
+    pub from: Address,
+    #[serde(default)]
+    pub to: Option<Address>,
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub value: Option<U256>,
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub gas: Option<U256>,
+    #[serde(default, serialize_with = "serialize_opt_u256")]
+    pub gas_used: Option<U256>,
+    /// Return data, or revert data when the frame failed
+    #[serde(default)]
+    pub output: Option<Bytes>,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// The node's decoding of `Error(string)` revert data, when it does that
+    #[serde(default)]
+    pub revert_reason: Option<String>,
+    #[serde(default)]
+    pub calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    /// Every frame in the tree with its depth (0 for this frame), in
+    /// execution order
+    pub fn flatten(&self) -> Vec<(usize, &CallFrame)> {
+        let mut frames = vec![(0, self)];
+        for call in &self.calls {
+            frames.extend(call.flatten().into_iter().map(|(depth, frame)| (depth + 1, frame)));
+        }
+        frames
+    }
+}
+
+/// Where and by whom a contract was deployed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractCreation {
+    pub address: Address,
+    pub block_number: u64,
+    pub transaction_hash: B256,
+    /// Sender of the creating transaction
+    pub deployer: Address,
+    /// The contract whose CREATE or CREATE2 deployed this one, when the
+    /// transaction didn't deploy it directly
+    pub factory: Option<Address>,
+}
+
+/// `ots_getContractCreator` result; `creator` is whoever ran the CREATE
+#[derive(Debug, Deserialize)]
+struct OtsContractCreator {
+    hash: B256,
+    creator: Address,
+}
+
+/// A transaction's trace from `debug_traceBlockBy*`; older nodes omit the hash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    #[serde(default)]
+    pub tx_hash: Option<B256>,
+    pub result: serde_json::Value,
 }
 
+/// Filter for `eth_getLogs`; `topics` are positional, `None` matching anything
 #[derive(Debug, Clone)]
+pub struct LogFilter {
+    pub address: Option<Address>,
+    pub topics: Vec<Option<B256>>,
+}
+
+impl LogFilter {
+    fn to_params(&self, from_block: u64, to_block: u64) -> serde_json::Value {
+        let mut filter = serde_json::json!({
+            "fromBlock": format!("0x{:x}", from_block),
+            "toBlock": format!("0x{:x}", to_block),
+            "topics": self.topics,
+        });
+        if let Some(address) = &self.address {
+            filter["address"] = serde_json::json!(address);
+        }
+        filter
+    }
+
+    /// Whether a block with this logs bloom may contain a matching log.
+    /// False positives are possible, false negatives are not.
+    pub fn bloom_matches(&self, bloom: &Bloom) -> bool {
+        self.address.is_none_or(|address| bloom_might_contain(bloom, address.as_slice()))
+            && self.topics.iter().flatten().all(|topic| bloom_might_contain(bloom, topic.as_slice()))
+    }
+}
+
+/// Order for listing fetched logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogSort {
+    /// Chronological, by block then log index
+    Block,
+    /// Grouped by emitting contract
+    Address,
+    /// Grouped by event signature (topic0)
+    Event,
+}
+
+/// Sort logs in place. Address and event sorts keep each group
+/// chronological so runs from the same contract or event stay readable.
+pub fn sort_logs(logs: &mut [Log], by: LogSort, reverse: bool) {
+    let position = |log: &Log| (log.block_number, log.log_index);
+    match by {
+        LogSort::Block => logs.sort_by_key(position),
+        LogSort::Address => logs.sort_by_key(|log| (log.address, position(log))),
+        LogSort::Event => logs.sort_by_key(|log| (log.topics.first().copied(), position(log))),
+    }
+    if reverse {
+        logs.reverse();
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct GasStatistics {
     pub avg_gas_used: u64,
     pub avg_gas_price: u64,
@@ -53,284 +512,4116 @@ pub struct GasStatistics {
     pub min_gas_used: u64,
     pub gas_utilization: f64,
     pub blocks_analyzed: usize,
+    /// Per-block figures the averages were computed from, oldest first
+    pub blocks: Vec<BlockGasSample>,
 }
 
-pub struct RethClient {
-    client: HttpClient,
-    cache: Cache<String, serde_json::Value>,
-    rpc_url: String,
+/// Gas figures for a single transaction, from its receipt
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TxGasSample {
+    pub hash: B256,
+    pub block_number: u64,
+    pub gas_used: u64,
+    /// Price actually paid per unit of gas (`effectiveGasPrice`)
+    #[serde(serialize_with = "serialize_u256")]
+    pub gas_price: U256,
 }
 
-impl RethClient {
-    pub async fn new(rpc_url: &str) -> Result<Self> {
-        let client = HttpClientBuilder::default()
-            .request_timeout(Duration::from_secs(60))
-            .build(rpc_url)?;
+/// Base fees and priority-fee percentiles of recent blocks, as reported
+/// by `eth_feeHistory`
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeHistory {
+    /// Reward percentiles requested, in the order of each block's `rewards`
+    pub percentiles: Vec<f64>,
+    pub blocks: Vec<FeeHistoryBlock>,
+    /// Base fee of the block after the newest, which is known in advance
+    #[serde(serialize_with = "serialize_u256")]
+    pub next_base_fee: U256,
+}
 
-        // Test connection
-        let _chain_id: String = client
-            .request("eth_chainId", rpc_params![])
-            .await
-            .map_err(|e| eyre!("Failed to connect to Reth node: {}", e))?;
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeHistoryBlock {
+    pub number: u64,
+    #[serde(serialize_with = "serialize_u256")]
+    pub base_fee: U256,
+    /// Gas used as a fraction of the gas limit
+    pub gas_used_ratio: f64,
+    /// Priority fee paid at each requested percentile of the block's gas
+    #[serde(serialize_with = "serialize_u256_seq")]
+    pub rewards: Vec<U256>,
+}
 
-        println!("✅ Connected to Reth node at {}", rpc_url);
+impl FeeHistory {
+    /// Suggested `(maxFeePerGas, maxPriorityFeePerGas)`: the median across
+    /// blocks of the middle requested percentile as the tip, on top of
+    /// twice the next base fee so the cap survives several full blocks.
+    /// `None` without percentiles or blocks.
+    pub fn suggested_fees(&self) -> Option<(U256, U256)> {
+        let middle = self.percentiles.len().checked_sub(1)? / 2;
+        let mut tips: Vec<U256> = self.blocks.iter().filter_map(|block| block.rewards.get(middle).copied()).collect();
+        if tips.is_empty() {
+            return None;
+        }
+        tips.sort();
+        let tip = tips[tips.len() / 2];
+        Some((self.next_base_fee.saturating_mul(U256::from(2)).saturating_add(tip), tip))
+    }
+}
 
-        // Create cache with 1000 entries, 5 minute TTL
-        let cache = Cache::builder()
-            .max_capacity(1000)
-            .time_to_live(Duration::from_secs(300))
-            .build();
+/// Most blocks `eth_feeHistory` serves in one call on common nodes
+pub const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
 
-        Ok(Self {
-            client,
-            cache,
-            rpc_url: rpc_url.to_string(),
-        })
-    }
+/// Gas figures for a single block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockGasSample {
+    pub number: u64,
+    pub timestamp: u64,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    #[serde(serialize_with = "serialize_opt_u256")]
+    pub base_fee: Option<U256>,
+    pub tx_count: usize,
+}
 
-    pub async fn get_block_by_number(&self, block_number: u64) -> Result<Block> {
-        let cache_key = format!("block_{}", block_number);
-        
-        if let Some(cached) = self.cache.get(&cache_key).await {
-            if let Ok(block) = serde_json::from_value(cached) {
-                return Ok(block);
-            }
+impl BlockGasSample {
+    fn from_block(block: &Block) -> Self {
+        Self {
+            number: block.number.saturating_to::<u64>(),
+            timestamp: block.timestamp.saturating_to::<u64>(),
+            gas_used: block.gas_used.saturating_to::<u64>(),
+            gas_limit: block.gas_limit.saturating_to::<u64>(),
+            base_fee: block.base_fee_per_gas,
+            tx_count: block.transactions.len(),
         }
+    }
 
-        let block_hex = format!("0x{:x}", block_number);
-        let result: serde_json::Value = self
-            .retry_rpc_call("eth_getBlockByNumber", rpc_params![block_hex, true])
-            .await?;
-
-        let block = self.parse_block(result.clone())?;
-        self.cache.insert(cache_key, result).await;
-        
-        Ok(block)
+    /// Wei burned by the base fee (EIP-1559), `None` before London
+    pub fn burned(&self) -> Option<U256> {
+        self.base_fee.map(|fee| fee * U256::from(self.gas_used))
     }
+}
 
-    pub async fn get_block_by_hash(&self, block_hash: &str) -> Result<Block> {
-        let cache_key = format!("block_hash_{}", block_hash);
-        
-        if let Some(cached) = self.cache.get(&cache_key).await {
-            if let Ok(block) = serde_json::from_value(cached) {
-                return Ok(block);
-            }
-        }
+/// Largest block range a linear scan command may cover
+pub const MAX_SCAN_BLOCKS: u64 = 10_000;
 
-        let result: serde_json::Value = self
-            .retry_rpc_call("eth_getBlockByHash", rpc_params![block_hash, true])
-            .await?;
+/// Largest block range a `logs` query may cover
+pub const MAX_LOG_SCAN_BLOCKS: u64 = 1_000_000;
 
-        let block = self.parse_block(result.clone())?;
-        self.cache.insert(cache_key, result).await;
-        
-        Ok(block)
-    }
+/// Storage slots proxies keep their implementation address in: EIP-1967
+/// (`keccak256("eip1967.proxy.implementation") - 1`) and the older
+/// OpenZeppelin (`keccak256("org.zeppelinos.proxy.implementation")`) layout
+const PROXY_IMPLEMENTATION_SLOTS: [B256; 2] = [
+    EIP1967_IMPLEMENTATION_SLOT,
+    b256!("7050c9e0f4ca769c69bd3a8ef740bc37934f8e2c036e5a723fd8ee048ed3f8c3"),
+];
 
-    pub async fn get_latest_block_number(&self) -> Result<u64> {
-        let result: String = self
-            .retry_rpc_call("eth_blockNumber", rpc_params![])
-            .await?;
+/// ENS registry, deployed at the same address on mainnet and testnets
+const ENS_REGISTRY: Address = alloy_primitives::address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+/// `resolver(bytes32)` on the registry, and `name(bytes32)` and
+/// `addr(bytes32)` on a resolver
+const ENS_RESOLVER_SELECTOR: [u8; 4] = [0x01, 0x78, 0xb8, 0xbf];
+const ENS_NAME_SELECTOR: [u8; 4] = [0x69, 0x1f, 0x34, 0x31];
+const ENS_ADDR_SELECTOR: [u8; 4] = [0x3b, 0x3b, 0x57, 0xde];
 
-        let block_number = u64::from_str_radix(&result[2..], 16)
-            .map_err(|e| eyre!("Failed to parse block number: {}", e))?;
+/// Multicall3, deployed at the same address on mainnet and most other chains
+const MULTICALL3: Address = alloy_primitives::address!("cA11bde05977b3631167028862bE2a173976CA11");
+/// `aggregate3((address,bool,bytes)[])` on Multicall3 and ERC-20 `balanceOf(address)`
+const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+const SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+/// Calls bundled into one `aggregate3`, kept well under node gas caps
+const MULTICALL_BATCH_SIZE: usize = 500;
 
-        Ok(block_number)
-    }
+/// Calls sent in one JSON-RPC batch request
+const RPC_BATCH_SIZE: usize = 100;
 
-    pub async fn get_transaction(&self, tx_hash: &str) -> Result<Transaction> {
-        let cache_key = format!("tx_{}", tx_hash);
-        
-        if let Some(cached) = self.cache.get(&cache_key).await {
-            if let Ok(tx) = serde_json::from_value(cached) {
-                return Ok(tx);
-            }
-        }
+/// Largest response accepted over HTTP and WebSocket. jsonrpsee's 10 MB
+/// default is less than a batch of full mainnet blocks.
+const MAX_RESPONSE_SIZE: u32 = 256 * 1024 * 1024;
 
-        let tx_result: serde_json::Value = self
-            .retry_rpc_call("eth_getTransactionByHash", rpc_params![tx_hash])
-            .await?;
+/// Requests kept in flight when fetching a range of blocks
+pub const BLOCK_FETCH_CONCURRENCY: usize = 16;
 
-        let receipt_result: serde_json::Value = self
-            .retry_rpc_call("eth_getTransactionReceipt", rpc_params![tx_hash])
-            .await?;
+/// Blocks behind the head treated as final on chains without a
+/// `finalized` tag
+const FINALITY_DEPTH: u64 = 64;
 
-        let transaction = self.parse_transaction(tx_result.clone(), receipt_result)?;
-        self.cache.insert(cache_key, tx_result).await;
-        
-        Ok(transaction)
+/// Time allowed for a single RPC request unless `--timeout` says otherwise
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Blocks `get_gas_statistics` fetches per batch
+const GAS_STATS_CHUNK_BLOCKS: usize = 100;
+
+/// How long a failing request is retried against one endpoint before
+/// moving on to the next, unless `--max-retry-secs` says otherwise
+pub const DEFAULT_RETRY_WINDOW: Duration = Duration::from_secs(30);
+/// Wait before the first retry, doubling (with jitter) after each one
+pub const DEFAULT_RETRY_INITIAL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub const DEFAULT_CACHE_CAPACITY: u64 = 1000;
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Sort a client error into what went wrong: the node couldn't be
+/// reached or was too slow, answered with an error, or answered with
+/// something unreadable. JSON-RPC errors keep the node's code and message.
+fn classify_rpc_error(e: &jsonrpsee::core::Error, context: &str) -> RethClientError {
+    let message = format!("{}: {}", context, e);
+    match e {
+        jsonrpsee::core::Error::Transport(_) | jsonrpsee::core::Error::RestartNeeded(_) => RethClientError::Connection(message),
+        jsonrpsee::core::Error::RequestTimeout => RethClientError::Timeout(message),
+        jsonrpsee::core::Error::Call(err) => RethClientError::RpcError { code: err.code(), message: err.message().to_string() },
+        _ => RethClientError::Parse(message),
     }
+}
 
-    pub async fn get_account_balance(&self, address: &str, block: Option<u64>) -> Result<Account> {
-        let block_param = match block {
-            Some(n) => format!("0x{:x}", n),
-            None => "latest".to_string(),
-        };
+/// Whether a failed batch means the node doesn't take batches at all: an
+/// HTTP 4xx other than a timeout or rate limit, an invalid-request error,
+/// or a single error object where the array of responses should be.
+/// Timeouts, dropped connections and oversized responses are not.
+fn is_batch_rejection(e: &jsonrpsee::core::Error) -> bool {
+    use jsonrpsee::http_client::transport::Error as HttpError;
+    match e {
+        jsonrpsee::core::Error::Transport(inner) => match inner.downcast_ref::<HttpError>() {
+            Some(HttpError::RequestFailure { status_code: 408 | 429 }) => false,
+            Some(HttpError::RequestFailure { status_code }) => (400..500).contains(status_code),
+            _ => false,
+        },
+        jsonrpsee::core::Error::Call(err) => matches!(err.code(), INVALID_REQUEST_CODE | METHOD_NOT_FOUND_CODE),
+        jsonrpsee::core::Error::ParseError(_) => true,
+        _ => false,
+    }
+}
 
-        let cache_key = format!("balance_{}_{}", address, block_param);
-        
-        if let Some(cached) = self.cache.get(&cache_key).await {
-            if let Ok(account) = serde_json::from_value(cached.clone()) {
-                return Ok(account);
-            }
-        }
+/// Whether the node's answer was over `MAX_RESPONSE_SIZE`
+fn is_response_too_large(e: &jsonrpsee::core::Error) -> bool {
+    use jsonrpsee::http_client::transport::Error as HttpError;
+    matches!(e, jsonrpsee::core::Error::Transport(inner) if matches!(inner.downcast_ref::<HttpError>(), Some(HttpError::RequestTooLarge)))
+}
 
-        let balance: String = self
-            .retry_rpc_call("eth_getBalance", rpc_params![address, &block_param])
-            .await?;
+/// Whether an error can only repeat on retry: the node rejected the
+/// request itself (unknown method, malformed request or params) rather
+/// than failing to serve it
+fn is_permanent_rpc_error(e: &jsonrpsee::core::Error) -> bool {
+    matches!(
+        e,
+        jsonrpsee::core::Error::Call(err)
+            if matches!(err.code(), METHOD_NOT_FOUND_CODE | INVALID_PARAMS_CODE | INVALID_REQUEST_CODE)
+    )
+}
 
-        let nonce: String = self
-            .retry_rpc_call("eth_getTransactionCount", rpc_params![address, &block_param])
-            .await?;
+/// Validate an inclusive block range against `MAX_SCAN_BLOCKS`
+pub fn check_scan_range(from_block: u64, to_block: u64) -> Result<()> {
+    if from_block > to_block {
+        return Err(RethClientError::InvalidInput(format!("Invalid block range: {} is after {}", from_block, to_block)));
+    }
+    if to_block - from_block >= MAX_SCAN_BLOCKS {
+        return Err(RethClientError::InvalidInput(format!(
+            "Block range {}..={} exceeds the scan limit of {} blocks",
+            from_block, to_block, MAX_SCAN_BLOCKS
+        )));
+    }
+    Ok(())
+}
 
-        let code: String = self
-            .retry_rpc_call("eth_getCode", rpc_params![address, &block_param])
-            .await?;
+/// Validate a gas statistics window and trim one longer than
+/// `MAX_SCAN_BLOCKS` to its most recent blocks. Returns the start to use.
+pub fn cap_gas_range(from_block: u64, to_block: u64) -> Result<u64> {
+    if from_block > to_block {
+        return Err(RethClientError::InvalidInput(format!("Invalid block range: {} is after {}", from_block, to_block)));
+    }
+    Ok(from_block.max(to_block.saturating_sub(MAX_SCAN_BLOCKS - 1)))
+}
 
-        let account = Account {
-            address: address.parse()
-                .map_err(|e| eyre!("Invalid address format: {}", e))?,
-            balance: U256::from_str_radix(&balance[2..], 16)
-                .map_err(|e| eyre!("Failed to parse balance: {}", e))?,
-            nonce: U256::from_str_radix(&nonce[2..], 16)
-                .map_err(|e| eyre!("Failed to parse nonce: {}", e))?,
-            code_size: U256::from((code.len() - 2) / 2), // -2 for "0x", /2 for hex pairs
-        };
+/// Validate the range of a `logs` query against `MAX_LOG_SCAN_BLOCKS`.
+/// eth_getLogs is chunked, so this is far larger than `MAX_SCAN_BLOCKS`,
+/// but still stops a typo like `--from-block 0` from running for hours.
+pub fn check_log_range(from_block: u64, to_block: u64) -> Result<()> {
+    if from_block > to_block {
+        return Err(RethClientError::InvalidInput(format!("Invalid block range: {} is after {}", from_block, to_block)));
+    }
+    if to_block - from_block >= MAX_LOG_SCAN_BLOCKS {
+        return Err(RethClientError::InvalidInput(format!(
+            "Block range {}..={} exceeds the logs limit of {} blocks; narrow --from-block/--to-block",
+            from_block, to_block, MAX_LOG_SCAN_BLOCKS
+        )));
+    }
+    Ok(())
+}
 
-        let cached_value = serde_json::to_value(&account)?;
-        self.cache.insert(cache_key, cached_value).await;
-        
-        Ok(account)
+/// Decode a `symbol()` result: an ABI string, or the `bytes32` that some
+/// early tokens (MKR, SAI) return instead
+fn decode_token_symbol(output: &[u8]) -> Option<String> {
+    // An ABI string takes at least two words, so one word must be bytes32
+    if output.len() == 32 {
+        let end = output.iter().position(|byte| *byte == 0).unwrap_or(32);
+        return std::str::from_utf8(&output[..end]).ok().filter(|s| !s.is_empty()).map(str::to_string);
     }
+    match DynSolType::String.abi_decode(output) {
+        Ok(DynSolValue::String(symbol)) => Some(symbol),
+        _ => None,
+    }
+}
 
-    pub async fn get_gas_statistics(&self, block_count: usize) -> Result<GasStatistics> {
-        let latest_block = self.get_latest_block_number().await?;
-        let start_block = latest_block.saturating_sub(block_count as u64);
+/// Read a transaction's gas figures out of its receipt
+fn parse_tx_gas_sample(receipt: &serde_json::Value, block_number: u64) -> Result<TxGasSample> {
+    let field = |name: &str| -> Result<&str> {
+        receipt.get(name).and_then(|v| v.as_str())
+            .ok_or_else(|| RethClientError::Parse(format!("Receipt in block {} is missing {}", block_number, name)))
+    };
+    Ok(TxGasSample {
+        hash: field("transactionHash")?.parse()?,
+        block_number,
+        gas_used: hex_to_decimal(field("gasUsed")?)?,
+        gas_price: U256::from_str_radix(field("effectiveGasPrice")?.trim_start_matches("0x"), 16)?,
+    })
+}
 
-        let mut total_gas_used = 0u64;
-        let mut total_gas_price = 0u64;
-        let mut max_gas_used = 0u64;
-        let mut min_gas_used = u64::MAX;
-        let mut blocks_processed = 0usize;
+/// Total fees paid (effective gas price times gas used) and total gas used
+/// by a block's transactions, for a gas-weighted average price. Both come
+/// from the block's receipts, matched by transaction hash: the transaction's
+/// own `gasPrice` may be the EIP-1559 fee cap rather than the price paid.
+fn block_fee_totals(transactions: &[Transaction], receipts: &[serde_json::Value]) -> Result<(U256, u64)> {
+    let mut samples_by_tx = HashMap::new();
+    for receipt in receipts {
+        let sample = parse_tx_gas_sample(receipt, 0)?;
+        samples_by_tx.insert(sample.hash, sample);
+    }
 
-        println!("📊 Analyzing gas statistics for {} blocks...", block_count);
+    let mut fees = U256::ZERO;
+    let mut gas = 0u64;
+    for tx in transactions {
+        let sample = samples_by_tx.get(&tx.hash)
+            .ok_or_else(|| RethClientError::NotFound(format!("No receipt for transaction {:?}", tx.hash)))?;
+        fees = fees.saturating_add(sample.gas_price.saturating_mul(U256::from(sample.gas_used)));
+        gas += sample.gas_used;
+    }
+    Ok((fees, gas))
+}
 
-        for block_num in start_block..=latest_block {
-            if let Ok(block) = self.get_block_by_number(block_num).await {
-                let gas_used = block.gas_used.to::<u64>();
-                total_gas_used += gas_used;
-                max_gas_used = max_gas_used.max(gas_used);
-                min_gas_used = min_gas_used.min(gas_used);
-                blocks_processed += 1;
+/// Parse an `eth_feeHistory` result. `baseFeePerGas` has one more entry
+/// than there are blocks, for the block after the newest.
+fn parse_fee_history(result: &serde_json::Value, percentiles: &[f64]) -> Result<FeeHistory> {
+    let quantity = |value: &serde_json::Value| -> Result<U256> {
+        let hex = value.as_str().ok_or_else(|| RethClientError::Parse(format!("Invalid fee history quantity: {}", value)))?;
+        Ok(U256::from_str_radix(hex.trim_start_matches("0x"), 16)?)
+    };
+    let array = |name: &str| {
+        result[name].as_array().ok_or_else(|| RethClientError::Parse(format!("Fee history is missing {}", name)))
+    };
 
-                // For gas price, we'd need to analyze transactions in the block
-                // For simplicity, we'll estimate based on current gas price
-                total_gas_price += 25_000_000_000u64; // ~25 Gwei estimate
-            }
-        }
+    let oldest_block = quantity(&result["oldestBlock"])?.saturating_to::<u64>();
+    let base_fees = array("baseFeePerGas")?.iter().map(quantity).collect::<Result<Vec<_>>>()?;
+    let ratios = array("gasUsedRatio")?;
+    // Absent when no percentiles were asked for
+    let rewards = result["reward"].as_array().cloned().unwrap_or_default();
 
-        if blocks_processed == 0 {
-            return Err(eyre!("No blocks found for gas statistics"));
+    let blocks = ratios.iter().enumerate()
+        .map(|(i, ratio)| {
+            Ok(FeeHistoryBlock {
+                number: oldest_block + i as u64,
+                base_fee: *base_fees.get(i).ok_or_else(|| RethClientError::Parse("Fee history is missing a base fee".into()))?,
+                gas_used_ratio: ratio.as_f64().unwrap_or_default(),
+                rewards: match rewards.get(i).and_then(|r| r.as_array()) {
+                    Some(rewards) => rewards.iter().map(quantity).collect::<Result<_>>()?,
+                    None => Vec::new(),
+                },
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(FeeHistory {
+        percentiles: percentiles.to_vec(),
+        next_base_fee: base_fees.get(blocks.len()).copied().unwrap_or_default(),
+        blocks,
+    })
+}
+
+/// Parse one entry of a block's `withdrawals`
+fn parse_withdrawal(value: &serde_json::Value) -> Result<Withdrawal> {
+    let quantity = |name: &str| -> Result<u64> {
+        let hex = value[name].as_str().ok_or_else(|| RethClientError::Parse(format!("Withdrawal is missing {}", name)))?;
+        hex_to_decimal(hex).map_err(|e| RethClientError::Parse(format!("Invalid withdrawal {} {}: {}", name, hex, e)))
+    };
+    Ok(Withdrawal {
+        index: quantity("index")?,
+        validator_index: quantity("validatorIndex")?,
+        address: value["address"].as_str().ok_or_else(|| RethClientError::Parse("Withdrawal is missing address".into()))?.parse()?,
+        amount: quantity("amount")?,
+    })
+}
+
+/// Connection to the node over whichever transport its URL names. Only
+/// `request` and `batch_request` are needed, so they're forwarded rather
+/// than boxing `ClientT`, whose generic methods make it unusable as a
+/// trait object. Any of the clients converts into one, for
+/// `RethClient::with_client`.
+pub enum RpcTransport {
+    Http(Box<HttpClient>),
+    Ws(WsClient),
+    Ipc(Client),
+}
+
+impl From<HttpClient> for RpcTransport {
+    fn from(client: HttpClient) -> Self {
+        RpcTransport::Http(Box::new(client))
+    }
+}
+
+/// `WsClient` is the same async `Client` the IPC transport uses, so this
+/// covers both
+impl From<WsClient> for RpcTransport {
+    fn from(client: WsClient) -> Self {
+        RpcTransport::Ws(client)
+    }
+}
+
+impl RpcTransport {
+    async fn connect(rpc_url: &str, timeout: Duration) -> Result<Self> {
+        let scheme = rpc_url.split_once("://").map(|(scheme, _)| scheme.to_ascii_lowercase());
+        match scheme.as_deref() {
+            Some("ipc") => Self::connect_ipc(&rpc_url["ipc://".len()..], timeout).await,
+            None if is_socket_path(rpc_url) => Self::connect_ipc(rpc_url, timeout).await,
+            Some("http" | "https") => Ok(RpcTransport::Http(Box::new(
+                HttpClientBuilder::default()
+                    .request_timeout(timeout)
+                    .max_response_size(MAX_RESPONSE_SIZE)
+                    .build(rpc_url)
+                    .map_err(|e| RethClientError::InvalidInput(format!("Invalid RPC URL {}: {}", rpc_url, e)))?,
+            ))),
+            Some("ws" | "wss") => Ok(RpcTransport::Ws(
+                WsClientBuilder::default()
+                    .request_timeout(timeout)
+                    .max_response_size(MAX_RESPONSE_SIZE)
+                    .build(rpc_url)
+                    .await
+                    .map_err(|e| classify_rpc_error(&e, "Failed to connect to Reth node"))?,
+            )),
+            _ => Err(RethClientError::InvalidInput(format!(
+                "Unsupported RPC URL {} (expected http://, https://, ws://, wss:// or an IPC socket path)", rpc_url
+            ))),
         }
+    }
 
-        let avg_gas_used = total_gas_used / blocks_processed as u64;
-        let avg_gas_price = total_gas_price / blocks_processed as u64;
-        let gas_utilization = (avg_gas_used as f64 / 30_000_000.0) * 100.0;
+    /// Connect to a node's IPC socket, such as Reth's `/tmp/reth.ipc`
+    #[cfg(unix)]
+    async fn connect_ipc(path: &str, timeout: Duration) -> Result<Self> {
+        let path = Path::new(path);
+        if !path.exists() {
+            return Err(RethClientError::InvalidInput(format!("IPC socket {} does not exist", path.display())));
+        }
+        let (sender, receiver) = crate::ipc::connect(path).await.map_err(|e| {
+            RethClientError::Connection(format!("Failed to connect to IPC socket {}: {}", path.display(), e))
+        })?;
+        Ok(RpcTransport::Ipc(
+            jsonrpsee::core::client::ClientBuilder::default()
+                .request_timeout(timeout)
+                .build_with_tokio(sender, receiver),
+        ))
+    }
 
-        Ok(GasStatistics {
-            avg_gas_used,
-            avg_gas_price,
-            max_gas_used,
-            min_gas_used,
-            gas_utilization,
-            blocks_analyzed: blocks_processed,
-        })
+    #[cfg(not(unix))]
+    async fn connect_ipc(path: &str, _timeout: Duration) -> Result<Self> {
+        Err(RethClientError::InvalidInput(format!("IPC sockets are only supported on Unix ({})", path)).into())
     }
 
-    async fn retry_rpc_call<T>(&self, method: &str, params: jsonrpsee::core::params::ArrayParams) -> Result<T>
+    async fn request<R>(&self, method: &str, params: ArrayParams) -> std::result::Result<R, jsonrpsee::core::Error>
     where
-        T: serde::de::DeserializeOwned,
+        R: serde::de::DeserializeOwned,
     {
-        let backoff = ExponentialBackoffBuilder::new()
-            .with_max_elapsed_time(Some(Duration::from_secs(30)))
-            .build();
-
-        backoff::future::retry(backoff, || async {
-            self.client
-                .request(method, params.clone())
-                .await
-                .map_err(|e| {
-                    println!("⚠️  RPC call failed, retrying: {}", e);
-                    backoff::Error::transient(eyre!("RPC call failed: {}", e))
-                })
-        })
-        .await
+        match self {
+            RpcTransport::Http(client) => client.request(method, params).await,
+            RpcTransport::Ws(client) => client.request(method, params).await,
+            RpcTransport::Ipc(client) => client.request(method, params).await,
+        }
     }
 
-    fn parse_block(&self, value: serde_json::Value) -> Result<Block> {
-        let obj = value.as_object().ok_or_else(|| eyre!("Invalid block format"))?;
-        
-        Ok(Block {
-            number: U256::from_str_radix(
-                obj.get("number").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            hash: obj.get("hash").and_then(|v| v.as_str()).unwrap_or("0x0").parse()?,
-            parent_hash: obj.get("parentHash").and_then(|v| v.as_str()).unwrap_or("0x0").parse()?,
-            timestamp: U256::from_str_radix(
-                obj.get("timestamp").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            gas_used: U256::from_str_radix(
-                obj.get("gasUsed").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            gas_limit: U256::from_str_radix(
-                obj.get("gasLimit").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            transactions: obj.get("transactions")
-                .and_then(|v| v.as_array())
-                .map(|arr| arr.iter().filter_map(|tx| {
-                    if let Some(hash_str) = tx.as_str() {
-                        hash_str.parse().ok()
-                    } else {
-                        tx.get("hash").and_then(|h| h.as_str()?.parse().ok())
+    /// Send every call in `batch` as one JSON-RPC batch. Each entry of the
+    /// result is the matching call's result or its error message, in the
+    /// order the calls were added.
+    async fn batch_request(&self, batch: BatchRequestBuilder<'_>) -> std::result::Result<Vec<std::result::Result<serde_json::Value, RethClientError>>, jsonrpsee::core::Error> {
+        let response = match self {
+            RpcTransport::Http(client) => client.batch_request::<serde_json::Value>(batch).await?,
+            RpcTransport::Ws(client) => client.batch_request::<serde_json::Value>(batch).await?,
+            RpcTransport::Ipc(client) => client.batch_request::<serde_json::Value>(batch).await?,
+        };
+        Ok(response.into_iter()
+            .map(|entry| entry.map_err(|e| RethClientError::RpcError { code: e.code(), message: e.message().to_string() }))
+            .collect())
+    }
+}
+
+/// Whether a scheme-less `--rpc-url` names a filesystem path rather than
+/// a host
+fn is_socket_path(rpc_url: &str) -> bool {
+    rpc_url.starts_with('/') || rpc_url.starts_with('.') || rpc_url.ends_with(".ipc")
+}
+
+/// A node the client can send requests to
+struct Endpoint {
+    url: String,
+    transport: RpcTransport,
+}
+
+/// What connecting to an endpoint found out
+#[derive(Debug)]
+struct Handshake {
+    chain_id: u64,
+    /// The latest block, asked for only when there's a record to keep it in
+    head: Option<u64>,
+    /// The endpoint's record from an earlier run on the same chain, for
+    /// reporting how far the head moved
+    remembered: Option<EndpointRecord>,
+}
+
+impl Endpoint {
+    /// Connect and check the node answers with its chain id. `want_head`
+    /// also asks for the latest block, and a `record` of an earlier run on
+    /// the same chain is kept to say how far the head moved since. A head
+    /// that went backwards, as when a dev node is restarted, drops it.
+    async fn probe(rpc_url: &str, timeout: Duration, record: Option<EndpointRecord>, want_head: bool) -> Result<(Self, Handshake)> {
+        let transport = RpcTransport::connect(rpc_url, timeout).await?;
+        let chain_id: String = transport
+            .request("eth_chainId", rpc_params![])
+            .await
+            .map_err(|e| classify_rpc_error(&e, "Failed to connect to Reth node"))?;
+        let chain_id = hex_to_decimal(&chain_id)
+            .map_err(|e| RethClientError::Parse(format!("Invalid chain id {}: {}", chain_id, e)))?;
+        let head = if want_head {
+            let head: String = transport
+                .request("eth_blockNumber", rpc_params![])
+                .await
+                .map_err(|e| classify_rpc_error(&e, "Failed to connect to Reth node"))?;
+            Some(hex_to_decimal(&head).map_err(|e| RethClientError::Parse(format!("Invalid block number {}: {}", head, e)))?)
+        } else {
+            None
+        };
+        let remembered = record.filter(|record| record.chain_id == chain_id && head.is_some_and(|head| head >= record.head));
+        Ok((Self { url: rpc_url.to_string(), transport }, Handshake { chain_id, head, remembered }))
+    }
+}
+
+/// The part of the connection message after the URL: the head, and how
+/// far it moved since an earlier run
+fn handshake_note(handshake: &Handshake) -> String {
+    match (handshake.head, handshake.remembered) {
+        (Some(head), Some(record)) => format!(
+            " (head {}, {} new blocks since last run)",
+            format_number(head), format_number(head - record.head)
+        ),
+        (Some(head), None) => format!(" (head {})", format_number(head)),
+        (None, _) => String::new(),
+    }
+}
+
+/// How `RethClient` caches what it fetches
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// Skip both the in-memory and the on-disk cache
+    pub disabled: bool,
+    /// Also keep finalized blocks, transactions and receipts here, so later
+    /// runs don't fetch them again
+    pub dir: Option<PathBuf>,
+    /// Entries across all in-memory caches, split evenly between them;
+    /// zero disables caching like `disabled`
+    pub capacity: u64,
+    /// How long in-memory entries live; zero means they never expire
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { disabled: false, dir: None, capacity: DEFAULT_CACHE_CAPACITY, ttl: DEFAULT_CACHE_TTL }
+    }
+}
+
+/// How `RethClient` retries requests that failed for a reason that may
+/// pass: a timeout, a dropped connection, a server error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Give up on an endpoint once retrying has taken this long; zero
+    /// means a single attempt
+    pub max_elapsed: Duration,
+    /// Wait before the first retry
+    pub initial_interval: Duration,
+}
+
+impl RetryConfig {
+    /// One attempt per endpoint, as with `--no-retry`
+    pub const NONE: Self = Self { max_elapsed: Duration::ZERO, initial_interval: DEFAULT_RETRY_INITIAL_INTERVAL };
+
+    /// A retry policy, rejecting a first wait longer than the whole window
+    pub fn new(max_elapsed: Duration, initial_interval: Duration) -> Result<Self> {
+        if initial_interval.is_zero() || initial_interval > max_elapsed {
+            return Err(RethClientError::InvalidInput(format!(
+                "--retry-initial-ms {} must be positive and within --max-retry-secs {}",
+                initial_interval.as_millis(), max_elapsed.as_secs()
+            )));
+        }
+        Ok(Self { max_elapsed, initial_interval })
+    }
+
+    fn backoff(&self) -> ExponentialBackoffBuilder {
+        let mut builder = ExponentialBackoffBuilder::new();
+        builder
+            .with_initial_interval(self.initial_interval)
+            .with_max_elapsed_time(Some(self.max_elapsed));
+        builder
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self { max_elapsed: DEFAULT_RETRY_WINDOW, initial_interval: DEFAULT_RETRY_INITIAL_INTERVAL }
+    }
+}
+
+/// In-memory caches `CacheConfig::capacity` is shared between: raw
+/// lookups, blocks, transactions, block receipts and token metadata
+const CACHE_COUNT: u64 = 5;
+
+/// Counters behind `--stats`, updated from concurrent requests
+#[derive(Debug, Default)]
+struct ClientStats {
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    parsed: AtomicU64,
+    parse_nanos: AtomicU64,
+}
+
+/// How well the block and transaction caches did over a run, and how long
+/// was spent parsing what they missed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsReport {
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// Blocks and transactions parsed from node responses
+    pub parsed: u64,
+    pub parse_time: Duration,
+}
+
+impl std::fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lookups = self.cache_hits + self.cache_misses;
+        let hit_rate = if lookups == 0 { 0.0 } else { self.cache_hits as f64 / lookups as f64 * 100.0 };
+        let per_parse = self.parse_time.as_secs_f64() * 1e6 / self.parsed.max(1) as f64;
+        write!(
+            f,
+            "📈 Cache: {} hits, {} misses ({:.1}% hit rate); parsed {} objects in {:.1} ms ({:.1} µs each)",
+            format_number(self.cache_hits), format_number(self.cache_misses), hit_rate,
+            format_number(self.parsed), self.parse_time.as_secs_f64() * 1e3, per_parse
+        )
+    }
+}
+
+/// An in-memory cache sized and aged as configured
+fn build_cache<K, V>(capacity: u64, ttl: Duration) -> Cache<K, V>
+where
+    K: std::hash::Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    let mut builder = Cache::builder().max_capacity(capacity);
+    if !ttl.is_zero() {
+        builder = builder.time_to_live(ttl);
+    }
+    builder.build()
+}
+
+pub struct RethClient {
+    /// Endpoints in the order given; requests go to `active` and move on
+    /// to the next when it stops responding
+    endpoints: Vec<Endpoint>,
+    active: AtomicUsize,
+    /// Retry policy for each request, built fresh per request
+    backoff: ExponentialBackoffBuilder,
+    chain_id: u64,
+    /// Looked up from `chain_id`, for labelling amounts
+    native_currency: NativeCurrency,
+    disk: Option<DiskCache>,
+    /// Highest block treated as final, looked up once and only when a disk
+    /// cache is in use
+    finalized: OnceCell<u64>,
+    cache: Cache<String, serde_json::Value>,
+    // Parsed structs, so hot paths skip re-parsing the raw JSON on every hit
+    blocks: Cache<String, Block>,
+    transactions: Cache<String, Transaction>,
+    /// Receipts of whole blocks, keyed by block number
+    receipts: Cache<u64, Arc<Vec<serde_json::Value>>>,
+    /// Token symbol and decimals never change, so these don't expire
+    token_metadata: Cache<Address, TokenMetadata>,
+    /// Set once the node has refused a batch request, after which blocks
+    /// are fetched one call at a time
+    batch_unsupported: AtomicBool,
+    /// Set once the node has refused `eth_getBlockReceipts`, after which
+    /// receipts are fetched one transaction at a time
+    block_receipts_unsupported: AtomicBool,
+    /// Leave out progress messages
+    quiet: bool,
+    /// Print warnings about retries and fallbacks to stderr; off for a
+    /// client built by `with_client`, which prints nothing
+    warnings: bool,
+    stats: ClientStats,
+}
+
+impl RethClient {
+    /// Connect to every reachable endpoint in `rpc_urls`. Fails only if none
+    /// answers, or if they disagree about the chain. Each request may take
+    /// up to `request_timeout`. `quiet` leaves out the connection and
+    /// progress messages, but not warnings.
+    pub async fn new(rpc_urls: &[String], cache_config: &CacheConfig, request_timeout: Duration, quiet: bool) -> Result<Self> {
+        // A capacity of zero keeps nothing, which is how --no-cache and
+        // --cache-size 0 bypass the caches
+        let disabled = cache_config.disabled || cache_config.capacity == 0;
+        let records = match &cache_config.dir {
+            Some(dir) if !disabled => Some(DiskCache::endpoints(dir).map_err(|e| RethClientError::InvalidInput(e.to_string()))?),
+            _ => None,
+        };
+
+        let mut endpoints: Vec<Endpoint> = Vec::new();
+        let mut chain_id = None;
+        let mut last_error = None;
+        for rpc_url in rpc_urls {
+            let record = records.as_ref().and_then(|records| records.get::<EndpointRecord>(rpc_url));
+            match Endpoint::probe(rpc_url, request_timeout, record, records.is_some()).await {
+                Ok((endpoint, handshake)) => {
+                    let id = handshake.chain_id;
+                    if let Some(expected) = chain_id.filter(|expected| *expected != id) {
+                        return Err(RethClientError::InvalidInput(format!(
+                            "{} is on chain {}, but {} is on chain {}", rpc_url, id, endpoints[0].url, expected
+                        )));
                     }
-                }).collect())
-                .unwrap_or_default(),
-            miner: obj.get("miner").and_then(|v| v.as_str()).unwrap_or("0x0000000000000000000000000000000000000000").parse()?,
-            difficulty: U256::from_str_radix(
-                obj.get("difficulty").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            size: U256::from_str_radix(
-                obj.get("size").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
+                    chain_id = Some(id);
+                    if let (Some(records), Some(head)) = (&records, handshake.head) {
+                        records.put(rpc_url, &EndpointRecord { chain_id: id, head });
+                    }
+                    if !quiet {
+                        eprintln!("✅ Connected to Reth node at {}{}", rpc_url, handshake_note(&handshake));
+                    }
+                    endpoints.push(endpoint);
+                }
+                // A malformed URL is a mistake to fix, not an outage to ride out
+                Err(e @ RethClientError::InvalidInput(_)) => return Err(e),
+                Err(e) => {
+                    if rpc_urls.len() > 1 {
+                        eprintln!("⚠️  Skipping {}: {}", rpc_url, e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+        let Some(chain_id) = chain_id else {
+            return Err(last_error.unwrap_or_else(|| RethClientError::InvalidInput("No RPC URL given".into())));
+        };
+        Self::assemble(endpoints, chain_id, cache_config, quiet)
+    }
+
+    /// Wrap a client the caller has already built and connected, for
+    /// embedding in a larger program. Nothing is sent to the node and
+    /// nothing printed, neither progress nor warnings. The chain id is taken
+    /// on trust, since it picks the disk cache and currency labels. The CLI
+    /// itself always goes through `new`.
+    pub fn with_client(client: impl Into<RpcTransport>, cache_config: &CacheConfig, chain_id: u64) -> Result<Self> {
+        let endpoint = Endpoint { url: "caller-provided client".to_string(), transport: client.into() };
+        let mut client = Self::assemble(vec![endpoint], chain_id, cache_config, true)?;
+        client.warnings = false;
+        Ok(client)
+    }
+
+    /// Print a warning to stderr unless warnings are off
+    fn warn(&self, message: impl std::fmt::Display) {
+        if self.warnings {
+            eprintln!("⚠️  {}", message);
+        }
+    }
+
+    /// A client over connected `endpoints`, with caches built as configured
+    fn assemble(endpoints: Vec<Endpoint>, chain_id: u64, cache_config: &CacheConfig, quiet: bool) -> Result<Self> {
+        let disabled = cache_config.disabled || cache_config.capacity == 0;
+        // The configured capacity bounds all caches together
+        let capacity = if disabled { 0 } else { (cache_config.capacity / CACHE_COUNT).max(1) };
+        let cache = build_cache(capacity, cache_config.ttl);
+        let blocks = build_cache(capacity, cache_config.ttl);
+        let transactions = build_cache(capacity, cache_config.ttl);
+        let receipts = build_cache(capacity, cache_config.ttl);
+        let token_metadata = build_cache(capacity, Duration::ZERO);
+
+        let disk = match &cache_config.dir {
+            Some(dir) if !disabled => Some(DiskCache::open(dir, chain_id).map_err(|e| RethClientError::InvalidInput(e.to_string()))?),
+            _ => None,
+        };
+
+        Ok(Self {
+            endpoints,
+            active: AtomicUsize::new(0),
+            backoff: RetryConfig::default().backoff(),
+            chain_id,
+            native_currency: native_currency_for_chain(chain_id),
+            disk,
+            finalized: OnceCell::new(),
+            cache,
+            blocks,
+            transactions,
+            receipts,
+            token_metadata,
+            batch_unsupported: AtomicBool::new(false),
+            block_receipts_unsupported: AtomicBool::new(false),
+            quiet,
+            warnings: true,
+            stats: ClientStats::default(),
         })
     }
 
-    fn parse_transaction(&self, tx_value: serde_json::Value, receipt_value: serde_json::Value) -> Result<Transaction> {
-        let tx_obj = tx_value.as_object().ok_or_else(|| eyre!("Invalid transaction format"))?;
-        let receipt_obj = receipt_value.as_object().ok_or_else(|| eyre!("Invalid receipt format"))?;
+    /// Retry failed requests as `retry` says instead of the default policy
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.backoff = retry.backoff();
+        self
+    }
+
+    /// Send any JSON-RPC method with the given positional params and return
+    /// the raw result, for methods without a dedicated command
+    pub async fn raw_request(&self, method: &str, params: Vec<serde_json::Value>) -> Result<serde_json::Value> {
+        let mut array = jsonrpsee::core::params::ArrayParams::new();
+        for param in params {
+            array.insert(param)?;
+        }
+        self.retry_rpc_call(method, array).await
+    }
+
+    /// Cache and parsing counters so far, for `--stats`
+    pub fn stats(&self) -> StatsReport {
+        StatsReport {
+            cache_hits: self.stats.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.stats.cache_misses.load(Ordering::Relaxed),
+            parsed: self.stats.parsed.load(Ordering::Relaxed),
+            parse_time: Duration::from_nanos(self.stats.parse_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Count a block or transaction cache lookup, passing its result on
+    fn record_lookup<T>(&self, cached: Option<T>) -> Option<T> {
+        let counter = if cached.is_some() { &self.stats.cache_hits } else { &self.stats.cache_misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    /// Run a parser, timing it for `--stats`
+    fn timed_parse<T>(&self, parse: impl FnOnce() -> Result<T>) -> Result<T> {
+        let started = std::time::Instant::now();
+        let parsed = parse();
+        self.stats.parse_nanos.fetch_add(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        self.stats.parsed.fetch_add(1, Ordering::Relaxed);
+        parsed
+    }
+
+    /// Chain id reported by the node at connection time
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// The chain's native currency, ether unless the chain id is known to
+    /// use another
+    pub fn native_currency(&self) -> NativeCurrency {
+        self.native_currency
+    }
+
+    pub async fn get_block_by_number(&self, block_number: u64) -> Result<Block> {
+        self.fetch_block_by_number(block_number, true).await
+    }
+
+    /// Fetch a block with transaction hashes only (`full_transactions` is
+    /// left empty), for callers that only need header fields
+    pub async fn get_block_header_by_number(&self, block_number: u64) -> Result<Block> {
+        self.fetch_block_by_number(block_number, false).await
+    }
+
+    /// The first block at or after `timestamp` among blocks `0..=head`, or
+    /// `None` if `head` itself is older. Each step guesses from the average
+    /// block time between the bounds, alternating with plain bisection so
+    /// irregular block times still take only logarithmically many requests.
+    pub async fn find_block_at_timestamp(&self, timestamp: u64, head: u64) -> Result<Option<u64>> {
+        let (mut low, mut high) = (0, head);
+        let mut low_time = self.block_timestamp(low).await?;
+        let mut high_time = self.block_timestamp(high).await?;
+        if high_time < timestamp {
+            return Ok(None);
+        }
+        if low_time >= timestamp {
+            return Ok(Some(low));
+        }
+
+        // From here on low is before the timestamp and high at or after it
+        let mut bisect = false;
+        while high - low > 1 {
+            let guess = if bisect {
+                low + (high - low) / 2
+            } else {
+                let elapsed = (timestamp - low_time) as u128;
+                let span = (high_time - low_time).max(1) as u128;
+                low + (elapsed * (high - low) as u128 / span) as u64
+            };
+            let guess = guess.clamp(low + 1, high - 1);
+            let guess_time = self.block_timestamp(guess).await?;
+            if guess_time < timestamp {
+                (low, low_time) = (guess, guess_time);
+            } else {
+                (high, high_time) = (guess, guess_time);
+            }
+            bisect = !bisect;
+        }
+        Ok(Some(high))
+    }
+
+    async fn block_timestamp(&self, block_number: u64) -> Result<u64> {
+        Ok(self.get_block_header_by_number(block_number).await?.timestamp.saturating_to())
+    }
+
+    /// Fetch the header of the `index`th uncle included in the block with
+    /// hash `block_hash`. Asking by hash rather than number means a reorg
+    /// can't pair the uncle with a different block at the same height.
+    pub async fn get_uncle_by_block_hash_and_index(&self, block_hash: B256, index: usize) -> Result<Block> {
+        let result: serde_json::Value = self
+            .retry_rpc_call("eth_getUncleByBlockHashAndIndex", rpc_params![block_hash, format!("0x{:x}", index)])
+            .await?;
+        if result.is_null() {
+            return Err(RethClientError::NotFound(format!("Block {:?} has no uncle {}", block_hash, index)));
+        }
+        self.parse_block(result)
+    }
+
+    /// Fetch the current head's header in a single round-trip
+    pub async fn get_latest_block_header(&self) -> Result<Block> {
+        self.get_block_by_tag(BlockTag::Latest, false).await
+    }
+
+    /// Fetch a block by tag, passed to the node as is. Not cached, since
+    /// what most tags point at moves with every block.
+    pub async fn get_block_by_tag(&self, tag: BlockTag, full_transactions: bool) -> Result<Block> {
+        let mut result: serde_json::Value = self
+            .retry_rpc_call("eth_getBlockByNumber", rpc_params![tag.as_str(), full_transactions])
+            .await?;
+        if result.is_null() {
+            return Err(RethClientError::NotFound(format!("No {} block", tag.as_str())));
+        }
+        // Some nodes leave the pending block unnumbered; it will be the
+        // block after the head
+        if tag == BlockTag::Pending && result["number"].is_null() {
+            let next = self.get_latest_block_number().await? + 1;
+            result["number"] = serde_json::json!(format!("0x{:x}", next));
+        }
+        self.parse_block(result)
+    }
+
+    /// The block currently being built, hashes only. `None` when the node
+    /// doesn't expose one. Never cached, since its contents keep changing.
+    pub async fn get_pending_block(&self) -> Result<Option<Block>> {
+        let result: serde_json::Value = self
+            .retry_rpc_call("eth_getBlockByNumber", rpc_params!["pending", false])
+            .await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        self.parse_block(result).map(Some)
+    }
+
+    async fn fetch_block_by_number(&self, block_number: u64, full_transactions: bool) -> Result<Block> {
+        if let Some(block) = self.cached_block(block_number, full_transactions).await {
+            return Ok(block);
+        }
+
+        let block_hex = format!("0x{:x}", block_number);
+        let result: serde_json::Value = self
+            .retry_rpc_call("eth_getBlockByNumber", rpc_params![block_hex, full_transactions])
+            .await?;
+        let block = self.block_from_response(block_number, result)?;
+        self.store_block(block_number, full_transactions, &block).await;
         
-        Ok(Transaction {
-            hash: tx_obj.get("hash").and_then(|v| v.as_str()).unwrap_or("0x0").parse()?,
-            block_number: tx_obj.get("blockNumber").and_then(|v| v.as_str()).map(|s| {
-                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
-            }).flatten(),
-            from: tx_obj.get("from").and_then(|v| v.as_str()).unwrap_or("0x0000000000000000000000000000000000000000").parse()?,
-            to: tx_obj.get("to").and_then(|v| v.as_str()).map(|s| s.parse().ok()).flatten(),
-            value: U256::from_str_radix(
-                tx_obj.get("value").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            gas: U256::from_str_radix(
-                tx_obj.get("gas").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            gas_price: U256::from_str_radix(
-                tx_obj.get("gasPrice").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
-            )?,
-            gas_used: receipt_obj.get("gasUsed").and_then(|v| v.as_str()).map(|s| {
-                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
-            }).flatten(),
-            status: receipt_obj.get("status").and_then(|v| v.as_str()).map(|s| {
-                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
-            }).flatten(),
-        })
+        Ok(block)
+    }
+
+    /// Fetch many blocks with as few round-trips as possible: cached blocks
+    /// are served locally and the rest are requested in JSON-RPC batches of
+    /// `RPC_BATCH_SIZE`. Nodes that refuse batches get one call per block.
+    /// Results come back in the order of `block_numbers`.
+    pub async fn get_blocks(&self, block_numbers: &[u64], full_transactions: bool) -> Vec<(u64, Result<Block>)> {
+        let mut blocks = Vec::with_capacity(block_numbers.len());
+        let mut missing = Vec::new();
+        for (i, &n) in block_numbers.iter().enumerate() {
+            let cached = self.cached_block(n, full_transactions).await;
+            if cached.is_none() {
+                missing.push(i);
+            }
+            blocks.push(cached.map(Ok));
+        }
+
+        for chunk in missing.chunks(RPC_BATCH_SIZE) {
+            let numbers: Vec<u64> = chunk.iter().map(|&i| block_numbers[i]).collect();
+            let fetched = match self.batch_fetch_blocks(&numbers, full_transactions).await {
+                Some(fetched) => fetched,
+                None => {
+                    stream::iter(&numbers)
+                        .map(|&n| self.fetch_block_by_number(n, full_transactions))
+                        .buffered(BLOCK_FETCH_CONCURRENCY)
+                        .collect()
+                        .await
+                }
+            };
+            for (&i, block) in chunk.iter().zip(fetched) {
+                blocks[i] = Some(block);
+            }
+        }
+
+        block_numbers.iter().copied()
+            .zip(blocks.into_iter().map(|block| block.unwrap_or_else(|| Err(RethClientError::NotFound("Block was not fetched".into())))))
+            .collect()
+    }
+
+    /// One batch of `eth_getBlockByNumber` calls, or `None` if the batch
+    /// failed as a whole and the blocks should be fetched one at a time.
+    /// Only a node that rejects batches is sent no more of them; calls the
+    /// node answered with an error are retried on their own.
+    async fn batch_fetch_blocks(&self, block_numbers: &[u64], full_transactions: bool) -> Option<Vec<Result<Block>>> {
+        if self.batch_unsupported.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut batch = BatchRequestBuilder::new();
+        for n in block_numbers {
+            batch.insert("eth_getBlockByNumber", rpc_params![format!("0x{:x}", n), full_transactions]).ok()?;
+        }
+        let responses = match self.transport().batch_request(batch).await {
+            Ok(responses) if responses.len() == block_numbers.len() => responses,
+            Ok(_) => return None,
+            Err(e) => {
+                if is_batch_rejection(&e) && !self.batch_unsupported.swap(true, Ordering::Relaxed) {
+                    self.warn(format_args!("Node did not accept a batch request ({}), fetching blocks one at a time", e));
+                }
+                return None;
+            }
+        };
+
+        let mut blocks = Vec::with_capacity(block_numbers.len());
+        let mut failed = Vec::new();
+        for (i, (&n, response)) in block_numbers.iter().zip(responses).enumerate() {
+            match response {
+                Ok(result) => {
+                    let block = self.block_from_response(n, result);
+                    if let Ok(block) = &block {
+                        self.store_block(n, full_transactions, block).await;
+                    }
+                    blocks.push(block);
+                }
+                Err(e) => {
+                    failed.push(i);
+                    blocks.push(Err(e));
+                }
+            }
+        }
+
+        let retried: Vec<_> = stream::iter(&failed)
+            .map(|&i| self.fetch_block_by_number(block_numbers[i], full_transactions))
+            .buffered(BLOCK_FETCH_CONCURRENCY)
+            .collect()
+            .await;
+        for (i, block) in failed.into_iter().zip(retried) {
+            blocks[i] = block;
+        }
+        Some(blocks)
+    }
+
+    fn block_cache_key(block_number: u64, full_transactions: bool) -> String {
+        if full_transactions {
+            format!("block_{}", block_number)
+        } else {
+            format!("block_header_{}", block_number)
+        }
+    }
+
+    async fn cached_block(&self, block_number: u64, full_transactions: bool) -> Option<Block> {
+        let cached = self.lookup_block(block_number, full_transactions).await;
+        self.record_lookup(cached)
+    }
+
+    async fn lookup_block(&self, block_number: u64, full_transactions: bool) -> Option<Block> {
+        let cache_key = Self::block_cache_key(block_number, full_transactions);
+        if let Some(block) = self.blocks.get(&cache_key).await {
+            return Some(block);
+        }
+        // A cached full block answers header requests just as well
+        if !full_transactions {
+            if let Some(block) = self.blocks.get(&Self::block_cache_key(block_number, true)).await {
+                return Some(block);
+            }
+        }
+        let block = self.disk_get::<Block>(&cache_key)?;
+        self.blocks.insert(cache_key, block.clone()).await;
+        Some(block)
+    }
+
+    async fn store_block(&self, block_number: u64, full_transactions: bool, block: &Block) {
+        let cache_key = Self::block_cache_key(block_number, full_transactions);
+        self.disk_put_if_final(&cache_key, block_number, block).await;
+        self.blocks.insert(cache_key, block.clone()).await;
+    }
+
+    fn block_from_response(&self, block_number: u64, result: serde_json::Value) -> Result<Block> {
+        if result.is_null() {
+            return Err(RethClientError::NotFound(format!("Block {} not found", block_number)));
+        }
+        self.parse_block(result)
+    }
+
+    pub async fn get_block_by_hash(&self, block_hash: &str) -> Result<Block> {
+        let cache_key = format!("block_hash_{}", block_hash.to_lowercase());
+        
+        if let Some(block) = self.record_lookup(self.blocks.get(&cache_key).await) {
+            return Ok(block);
+        }
+        if let Some(block) = self.disk_get::<Block>(&cache_key) {
+            self.blocks.insert(cache_key, block.clone()).await;
+            return Ok(block);
+        }
+
+        let result: serde_json::Value = self
+            .retry_rpc_call("eth_getBlockByHash", rpc_params![block_hash, true])
+            .await?;
+        if result.is_null() {
+            return Err(RethClientError::NotFound(format!("Block {} not found", block_hash)));
+        }
+
+        let block = self.parse_block(result)?;
+        // A hash names one block forever, final or not
+        if let Some(disk) = &self.disk {
+            disk.put(&cache_key, &block);
+        }
+        self.blocks.insert(cache_key, block.clone()).await;
+        
+        Ok(block)
+    }
+
+    pub async fn get_latest_block_number(&self) -> Result<u64> {
+        let result: String = self
+            .retry_rpc_call("eth_blockNumber", rpc_params![])
+            .await?;
+
+        let block_number = hex_to_decimal(&result)
+            .map_err(|e| RethClientError::Parse(format!("Failed to parse block number: {}", e)))?;
+
+        Ok(block_number)
+    }
+
+    pub async fn get_transaction(&self, tx_hash: &str) -> Result<Transaction> {
+        let cache_key = format!("tx_{}", tx_hash.to_lowercase());
+        
+        if let Some(tx) = self.record_lookup(self.transactions.get(&cache_key).await) {
+            return Ok(tx);
+        }
+        if let Some(tx) = self.disk_get::<Transaction>(&cache_key) {
+            self.transactions.insert(cache_key, tx.clone()).await;
+            return Ok(tx);
+        }
+
+        let tx_result: serde_json::Value = self
+            .retry_rpc_call("eth_getTransactionByHash", rpc_params![tx_hash])
+            .await?;
+        if tx_result.is_null() {
+            return Err(RethClientError::NotFound(format!("Transaction {} not found", tx_hash)));
+        }
+
+        let block_number = tx_result.get("blockNumber")
+            .and_then(|v| v.as_str())
+            .and_then(|s| hex_to_decimal(s).ok());
+        let receipt_result = self.get_receipt_json(tx_hash, block_number).await?;
+
+        let transaction = self.parse_transaction(tx_result, receipt_result)?;
+        // Until its block is final a transaction can still move to another,
+        // and a pending one is fetched again to pick up its receipt
+        if let Some(block_number) = block_number {
+            self.disk_put_if_final(&cache_key, block_number, &transaction).await;
+            self.transactions.insert(cache_key, transaction.clone()).await;
+        }
+        
+        Ok(transaction)
+    }
+
+    /// Fetch all receipts of a block with one `eth_getBlockReceipts` call,
+    /// caching them so later per-transaction lookups in the block are free.
+    /// Nodes without that method are asked for each receipt instead.
+    pub async fn get_block_receipts(&self, block_number: u64) -> Result<Arc<Vec<serde_json::Value>>> {
+        if let Some(receipts) = self.receipts.get(&block_number).await {
+            return Ok(receipts);
+        }
+        let cache_key = format!("receipts_{}", block_number);
+        if let Some(receipts) = self.disk_get::<Vec<serde_json::Value>>(&cache_key) {
+            let receipts = Arc::new(receipts);
+            self.receipts.insert(block_number, receipts.clone()).await;
+            return Ok(receipts);
+        }
+
+        let receipts = if self.block_receipts_unsupported.load(Ordering::Relaxed) {
+            self.fetch_receipts_individually(block_number).await?
+        } else {
+            match self.retry_rpc_call("eth_getBlockReceipts", rpc_params![format!("0x{:x}", block_number)]).await {
+                Ok(receipts) => receipts,
+                // The node doesn't have this method. Any other error, such
+                // as a block it can't serve, would only repeat per receipt.
+                Err(e @ RethClientError::RpcError { code: METHOD_NOT_FOUND_CODE, .. }) => {
+                    if !self.block_receipts_unsupported.swap(true, Ordering::Relaxed) {
+                        self.warn(format_args!("Node did not answer eth_getBlockReceipts ({}), fetching receipts one transaction at a time", e));
+                    }
+                    self.fetch_receipts_individually(block_number).await?
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        self.disk_put_if_final(&cache_key, block_number, &receipts).await;
+        let receipts = Arc::new(receipts);
+        self.receipts.insert(block_number, receipts.clone()).await;
+
+        Ok(receipts)
+    }
+
+    /// Every receipt of a block, parsed, in transaction order
+    pub async fn get_receipts(&self, block_number: u64) -> Result<Vec<Receipt>> {
+        self.get_block_receipts(block_number).await?
+            .iter()
+            .map(|receipt| self.parse_receipt(receipt))
+            .collect()
+    }
+
+    /// A block's receipts with one `eth_getTransactionReceipt` per
+    /// transaction, a few at a time
+    async fn fetch_receipts_individually(&self, block_number: u64) -> Result<Vec<serde_json::Value>> {
+        let block = self.get_block_header_by_number(block_number).await?;
+        stream::iter(&block.transactions)
+            .map(|hash| self.retry_rpc_call::<serde_json::Value>("eth_getTransactionReceipt", rpc_params![hash]))
+            .buffered(BLOCK_FETCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// A transaction's receipt, parsed. Fails with `NotFound` for unknown
+    /// and still pending transactions.
+    pub async fn get_receipt(&self, tx_hash: &str) -> Result<Receipt> {
+        let receipt = self.get_receipt_json(tx_hash, None).await?;
+        if receipt.is_null() {
+            return Err(RethClientError::NotFound(format!("No receipt for transaction {}", tx_hash)));
+        }
+        self.parse_receipt(&receipt)
+    }
+
+    /// A transaction's raw receipt, taken from the block receipts cache when
+    /// the containing block's receipts were already fetched
+    pub async fn get_receipt_json(&self, tx_hash: &str, block_number: Option<u64>) -> Result<serde_json::Value> {
+        if let Some(block_number) = block_number {
+            if let Some(receipts) = self.receipts.get(&block_number).await {
+                let cached = receipts.iter().find(|receipt| {
+                    receipt.get("transactionHash")
+                        .and_then(|v| v.as_str())
+                        .is_some_and(|hash| hash.eq_ignore_ascii_case(tx_hash))
+                });
+                if let Some(receipt) = cached {
+                    return Ok(receipt.clone());
+                }
+            }
+        }
+
+        self.retry_rpc_call("eth_getTransactionReceipt", rpc_params![tx_hash])
+            .await
+    }
+
+    /// Fetch the raw signed transaction, falling back to re-encoding it from
+    /// its fields, and verify it by hash and by recovering the signer
+    pub async fn get_raw_transaction(&self, tx_hash: &str) -> Result<RawTransaction> {
+        let (bytes, reconstructed) = match self
+            .retry_rpc_call::<Option<Bytes>>("eth_getRawTransactionByHash", rpc_params![tx_hash])
+            .await
+        {
+            Ok(Some(bytes)) => (bytes, false),
+            Ok(None) => return Err(RethClientError::NotFound(format!("Transaction {} not found", tx_hash))),
+            // Only a node without the method gets a re-encoded transaction;
+            // any other failure is the answer
+            Err(RethClientError::RpcError { code: METHOD_NOT_FOUND_CODE, .. }) => (self.reconstruct_raw_transaction(tx_hash).await?, true),
+            Err(e) => return Err(e),
+        };
+
+        let envelope = TxEnvelope::decode_2718(&mut bytes.as_ref())
+            .map_err(|e| RethClientError::Parse(format!("Failed to decode raw transaction: {}", e)))?;
+        let expected_hash: B256 = tx_hash.parse()
+            .map_err(|e| RethClientError::InvalidInput(format!("Invalid transaction hash: {}", e)))?;
+        if keccak256(&bytes) != expected_hash {
+            return Err(RethClientError::Parse(format!("Raw transaction does not hash to {}", tx_hash)));
+        }
+        let signer = envelope.recover_signer()
+            .map_err(|e| RethClientError::Parse(format!("Failed to recover signer: {}", e)))?;
+
+        Ok(RawTransaction { bytes, signer, reconstructed })
+    }
+
+    async fn reconstruct_raw_transaction(&self, tx_hash: &str) -> Result<Bytes> {
+        let tx: Option<alloy_rpc_types_eth::Transaction> = self
+            .retry_rpc_call("eth_getTransactionByHash", rpc_params![tx_hash])
+            .await?;
+        let tx = tx.ok_or_else(|| RethClientError::NotFound(format!("Transaction {} not found", tx_hash)))?;
+        let envelope = TxEnvelope::try_from(tx)
+            .map_err(|e| RethClientError::Parse(format!("Cannot re-encode transaction: {}", e)))?;
+        Ok(envelope.encoded_2718().into())
+    }
+
+    /// Re-execute a transaction's call with `eth_call` against the state at
+    /// `block`, reporting whether it succeeds or reverts
+    pub async fn simulate_transaction(&self, transaction: &Transaction, block: u64) -> Result<CallOutcome> {
+        let call = serde_json::json!({
+            "from": transaction.from,
+            "to": transaction.to,
+            "gas": format!("0x{:x}", transaction.gas),
+            "value": format!("0x{:x}", transaction.value),
+            "data": transaction.input,
+        });
+        self.call_outcome(call, format!("0x{:x}", block)).await
+    }
+
+    /// `eth_call` with any sender, value and calldata against the state at
+    /// `block` (default: latest). Reverts come back as `CallOutcome::Revert`.
+    pub async fn eth_call(&self, request: &CallRequest, block: Option<u64>) -> Result<CallOutcome> {
+        let mut call = serde_json::json!({ "to": request.to, "data": request.data });
+        if let Some(from) = request.from {
+            call["from"] = serde_json::json!(from);
+        }
+        if let Some(value) = request.value {
+            call["value"] = serde_json::json!(format!("0x{:x}", value));
+        }
+        let block_param = block.map_or("latest".to_string(), |n| format!("0x{:x}", n));
+        self.call_outcome(call, block_param).await
+    }
+
+    /// Send an `eth_call`, telling a revert apart from a failed request
+    async fn call_outcome(&self, call: serde_json::Value, block_param: String) -> Result<CallOutcome> {
+        // Called directly rather than through retry_rpc_call: a revert is an
+        // answer, not a failure, and its data lives in the error object
+        match self.transport()
+            .request::<Bytes>("eth_call", rpc_params![call, block_param])
+            .await
+        {
+            Ok(output) => Ok(CallOutcome::Success(output)),
+            Err(jsonrpsee::core::Error::Call(err)) if err.message().contains("revert") => {
+                let data = err.data()
+                    .and_then(|raw| serde_json::from_str::<String>(raw.get()).ok())
+                    .and_then(|hex| hex.parse().ok())
+                    .unwrap_or_default();
+                Ok(CallOutcome::Revert(data))
+            }
+            Err(e) => Err(classify_rpc_error(&e, "eth_call failed")),
+        }
+    }
+
+    /// Check that the node still has state for `block`, which replaying
+    /// historical calls depends on. Full nodes prune old state; only an
+    /// archive node keeps every block's.
+    pub async fn check_historical_state(&self, block: u64) -> Result<()> {
+        self.transport()
+            .request::<U256>("eth_getBalance", rpc_params![Address::ZERO, format!("0x{:x}", block)])
+            .await
+            .map(|_| ())
+            .map_err(|e| classify_rpc_error(&e, "eth_getBalance").context(format!("State at block {} is unavailable; an archive node is required", block)))
+    }
+
+    /// Earliest block in which `address` had sent a transaction or held
+    /// code, found by binary search over historical state, so every block
+    /// back to genesis must be available (archive node). `None` when the
+    /// account shows no activity at `latest`. A contract that later
+    /// self-destructed breaks the search's monotonicity assumption.
+    pub async fn first_active_block(&self, address: &Address, latest: u64) -> Result<Option<u64>> {
+        if !self.is_active_at(address, latest).await? {
+            return Ok(None);
+        }
+
+        let (mut low, mut high) = (0, latest);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.is_active_at(address, mid).await? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(Some(low))
+    }
+
+    /// The transaction count of `address` at a block tag. At `pending` it
+    /// includes transactions the node holds but hasn't mined yet, so it's
+    /// never cached.
+    pub async fn get_nonce_at_tag(&self, address: &Address, tag: BlockTag) -> Result<u64> {
+        let nonce: U256 = self
+            .retry_rpc_call("eth_getTransactionCount", rpc_params![address, tag.as_str()])
+            .await?;
+        Ok(nonce.saturating_to())
+    }
+
+    /// Whether `address` had a nonzero nonce or deployed code at `block`
+    async fn is_active_at(&self, address: &Address, block: u64) -> Result<bool> {
+        let block_param = format!("0x{:x}", block);
+        let nonce: U256 = self
+            .retry_rpc_call("eth_getTransactionCount", rpc_params![address, &block_param])
+            .await?;
+        if !nonce.is_zero() {
+            return Ok(true);
+        }
+        Ok(!self.get_code(address, Some(block)).await?.is_empty())
+    }
+
+    /// Bytecode deployed at `address` as of `block` (latest if `None`);
+    /// empty for externally owned accounts
+    pub async fn get_code(&self, address: &Address, block: Option<u64>) -> Result<Bytes> {
+        let block_param = match block {
+            Some(n) => format!("0x{:x}", n),
+            None => "latest".to_string(),
+        };
+        self.retry_rpc_call("eth_getCode", rpc_params![address, block_param]).await
+    }
+
+    /// The transaction that deployed the contract at `address`. Asks the
+    /// node's Otterscan API first; without it, bisects history for the
+    /// first block with code at the address (which needs archive state),
+    /// then finds the transaction among that block's receipts or, for
+    /// contracts deployed by a factory, its call traces.
+    pub async fn get_contract_creation(&self, address: Address) -> Result<ContractCreation> {
+        let cache_key = format!("creation_{:?}", address);
+        if let Some(creation) = self.disk_get::<ContractCreation>(&cache_key) {
+            return Ok(creation);
+        }
+        if let Some(cached) = self.cache.get(&cache_key).await {
+            if let Ok(creation) = serde_json::from_value(cached) {
+                return Ok(creation);
+            }
+        }
+
+        let creation = match self.namespace_request::<Option<OtsContractCreator>>("ots_getContractCreator", rpc_params![address]).await {
+            Ok(Some(found)) => {
+                let tx = self.get_transaction(&format!("{:?}", found.hash)).await?;
+                let block_number = tx.block_number
+                    .ok_or_else(|| RethClientError::NotFound(format!("Creation transaction {:?} is not in a block", found.hash)))?
+                    .saturating_to();
+                let factory = (found.creator != tx.from).then_some(found.creator);
+                ContractCreation { address, block_number, transaction_hash: found.hash, deployer: tx.from, factory }
+            }
+            Ok(None) => return Err(RethClientError::NotFound(format!("No contract was deployed at {:?}", address))),
+            // No Otterscan namespace: search for the deployment instead
+            Err(RethClientError::RpcError { code: METHOD_NOT_FOUND_CODE, .. }) => {
+                self.find_contract_creation(address).await?
+            }
+            Err(e) => return Err(e),
+        };
+
+        self.cache.insert(cache_key.clone(), serde_json::to_value(&creation)?).await;
+        self.disk_put_if_final(&cache_key, creation.block_number, &creation).await;
+        Ok(creation)
+    }
+
+    /// `get_contract_creation` without the Otterscan API. Assumes code,
+    /// once deployed, stays; a contract self-destructed and redeployed
+    /// since is found at its latest deployment or not at all.
+    async fn find_contract_creation(&self, address: Address) -> Result<ContractCreation> {
+        let head = self.get_latest_block_number().await?;
+        if self.get_code(&address, Some(head)).await?.is_empty() {
+            return Err(RethClientError::NotFound(format!(
+                "No code at {:?}: an externally owned account, or a self-destructed contract", address
+            )));
+        }
+
+        // The first block with code; `high` always has code
+        let (mut low, mut high) = (0, head);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.get_code(&address, Some(mid)).await?.is_empty() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        let block_number = high;
+        if block_number == 0 {
+            return Err(RethClientError::NotFound(format!(
+                "{:?} is part of the genesis state and has no creation transaction", address
+            )));
+        }
+
+        let receipts = self.get_receipts(block_number).await?;
+        if let Some(receipt) = receipts.iter().find(|receipt| receipt.contract_address == Some(address)) {
+            return Ok(ContractCreation {
+                address,
+                block_number,
+                transaction_hash: receipt.transaction_hash,
+                deployer: receipt.from,
+                factory: None,
+            });
+        }
+
+        // Deployed from inside a call; only a trace shows which one. Traces
+        // come in transaction order, like the receipts.
+        let traces = self.trace_block(&block_number.to_string(), "callTracer").await
+            .map_err(|e| e.context(format!("{:?} was deployed by another contract in block {}, and finding the transaction needs traces", address, block_number)))?;
+        for (trace, receipt) in traces.into_iter().zip(&receipts) {
+            let Ok(frame) = serde_json::from_value::<CallFrame>(trace.result) else {
+                continue;
+            };
+            let create = frame.flatten().into_iter()
+                .find(|(_, call)| call.call_type.starts_with("CREATE") && call.to == Some(address));
+            if let Some((_, create)) = create {
+                return Ok(ContractCreation {
+                    address,
+                    block_number,
+                    transaction_hash: receipt.transaction_hash,
+                    deployer: receipt.from,
+                    factory: Some(create.from),
+                });
+            }
+        }
+        Err(RethClientError::NotFound(format!("Code appeared at {:?} in block {}, but no transaction in that block created it", address, block_number)))
+    }
+
+    pub async fn get_account_balance(&self, address: &str, block: Option<u64>) -> Result<Account> {
+        let block_param = match block {
+            Some(n) => format!("0x{:x}", n),
+            None => "latest".to_string(),
+        };
+
+        let cache_key = format!("balance_{}_{}", address, block_param);
+        
+        if let Some(cached) = self.cache.get(&cache_key).await {
+            if let Ok(account) = serde_json::from_value(cached.clone()) {
+                return Ok(account);
+            }
+        }
+
+        let balance: String = self
+            .retry_rpc_call("eth_getBalance", rpc_params![address, &block_param])
+            .await?;
+
+        let nonce: String = self
+            .retry_rpc_call("eth_getTransactionCount", rpc_params![address, &block_param])
+            .await?;
+
+        let code: Bytes = self
+            .retry_rpc_call("eth_getCode", rpc_params![address, &block_param])
+            .await?;
+
+        let account = Account {
+            address: address.parse()
+                .map_err(|e| RethClientError::Parse(format!("Invalid address format: {}", e)))?,
+            balance: U256::from_str_radix(&balance[2..], 16)
+                .map_err(|e| RethClientError::Parse(format!("Failed to parse balance: {}", e)))?,
+            nonce: U256::from_str_radix(&nonce[2..], 16)
+                .map_err(|e| RethClientError::Parse(format!("Failed to parse nonce: {}", e)))?,
+            code_size: U256::from(code.len()),
+            code_hash: keccak256(&code),
+        };
+
+        let cached_value = serde_json::to_value(&account)?;
+        self.cache.insert(cache_key, cached_value).await;
+        
+        Ok(account)
+    }
+
+    /// The 32-byte word in storage `slot` of `address` as of `block`
+    /// (latest if `None`)
+    pub async fn get_storage_at(&self, address: &Address, slot: B256, block: Option<u64>) -> Result<B256> {
+        let block_param = match block {
+            Some(n) => format!("0x{:x}", n),
+            None => "latest".to_string(),
+        };
+
+        let value: String = self
+            .retry_rpc_call("eth_getStorageAt", rpc_params![address, slot, &block_param])
+            .await?;
+
+        // Some nodes return the minimal hex encoding rather than a full word
+        let value = U256::from_str_radix(value.trim_start_matches("0x"), 16)
+            .map_err(|e| RethClientError::Parse(format!("Failed to parse storage value: {}", e)))?;
+        Ok(B256::from(value))
+    }
+
+    /// Resolve the implementation behind a proxy by reading the standard
+    /// implementation storage slots. Returns `None` for non-proxy contracts.
+    pub async fn get_proxy_implementation(&self, address: &Address, block: Option<u64>) -> Result<Option<Address>> {
+        let cache_key = format!("proxy_{}_{:?}", address, block);
+
+        if let Some(cached) = self.cache.get(&cache_key).await {
+            if let Ok(implementation) = serde_json::from_value(cached) {
+                return Ok(implementation);
+            }
+        }
+
+        let mut implementation = None;
+        for slot in PROXY_IMPLEMENTATION_SLOTS {
+            let word = self.get_storage_at(address, slot, block).await?;
+            let candidate = Address::from_word(word);
+            if !candidate.is_zero() {
+                implementation = Some(candidate);
+                break;
+            }
+        }
+
+        self.cache.insert(cache_key, serde_json::to_value(implementation)?).await;
+        Ok(implementation)
+    }
+
+    /// Reverse-resolve an address to its primary ENS name. The name is what
+    /// the address's owner claims; it isn't checked against forward resolution.
+    pub async fn lookup_ens_name(&self, address: &Address) -> Result<Option<String>> {
+        let cache_key = format!("ens_name_{:?}", address);
+
+        if let Some(cached) = self.cache.get(&cache_key).await {
+            if let Ok(name) = serde_json::from_value(cached) {
+                return Ok(name);
+            }
+        }
+
+        let node = namehash(&format!("{:x}.addr.reverse", address));
+        let name = match self.ens_resolver(node).await? {
+            None => None,
+            Some(resolver) => {
+                let data = self.call(resolver, [&ENS_NAME_SELECTOR[..], node.as_slice()].concat()).await?;
+                DynSolType::String.abi_decode(&data).ok()
+                    .and_then(|value| value.as_str().map(str::to_string))
+                    .filter(|name| !name.is_empty())
+            }
+        };
+
+        self.cache.insert(cache_key, serde_json::to_value(&name)?).await;
+        Ok(name)
+    }
+
+    /// Forward-resolve an ENS name such as `vitalik.eth` to the address its
+    /// resolver reports
+    pub async fn resolve_ens(&self, name: &str) -> Result<Address> {
+        // Lowercasing covers the normalization ordinary names need
+        let name = name.to_lowercase();
+        let cache_key = format!("ens_addr_{}", name);
+
+        if let Some(cached) = self.cache.get(&cache_key).await {
+            if let Ok(address) = serde_json::from_value(cached) {
+                return Ok(address);
+            }
+        }
+
+        let node = namehash(&name);
+        let resolver = self.ens_resolver(node).await?
+            .ok_or_else(|| RethClientError::NotFound(format!("ENS name {} has no resolver", name)))?;
+        let data = self.call(resolver, [&ENS_ADDR_SELECTOR[..], node.as_slice()].concat()).await?;
+        let address = data.get(..32)
+            .map(|word| Address::from_word(B256::from_slice(word)))
+            .filter(|address| !address.is_zero())
+            .ok_or_else(|| RethClientError::NotFound(format!("ENS name {} doesn't resolve to an address", name)))?;
+
+        self.cache.insert(cache_key, serde_json::to_value(address)?).await;
+        Ok(address)
+    }
+
+    /// The resolver the ENS registry lists for `node`, if any
+    async fn ens_resolver(&self, node: B256) -> Result<Option<Address>> {
+        let data = self.call(ENS_REGISTRY, [&ENS_RESOLVER_SELECTOR[..], node.as_slice()].concat()).await?;
+        Ok(data.get(..32)
+            .map(|word| Address::from_word(B256::from_slice(word)))
+            .filter(|resolver| !resolver.is_zero()))
+    }
+
+    /// Trace every transaction in a block with the given tracer. `block_id`
+    /// is a block number or a 0x-prefixed hash.
+    pub async fn trace_block(&self, block_id: &str, tracer: &str) -> Result<Vec<TransactionTrace>> {
+        let (method, block_param) = if block_id.starts_with("0x") {
+            ("debug_traceBlockByHash", block_id.to_string())
+        } else {
+            let number = block_id.parse::<u64>()
+                .map_err(|_| RethClientError::InvalidInput("Invalid block number".to_string()))?;
+            ("debug_traceBlockByNumber", format!("0x{:x}", number))
+        };
+
+        self.namespace_request(method, rpc_params![block_param, serde_json::json!({ "tracer": tracer })]).await
+    }
+
+    /// Trace one transaction with the given tracer. The result's shape
+    /// depends on the tracer; `callTracer` returns a `CallFrame`.
+    pub async fn trace_transaction(&self, tx_hash: B256, tracer: &str) -> Result<serde_json::Value> {
+        let trace: serde_json::Value = self
+            .namespace_request("debug_traceTransaction", rpc_params![tx_hash, serde_json::json!({ "tracer": tracer })])
+            .await?;
+        if trace.is_null() {
+            return Err(RethClientError::NotFound(format!("Transaction {:?} not found", tx_hash)));
+        }
+        Ok(trace)
+    }
+
+    /// Counts of executable (pending) and nonce-gapped (queued)
+    /// transactions in the node's pool
+    pub async fn get_txpool_status(&self) -> Result<TxPoolStatus> {
+        let status: RawTxPoolStatus = self.namespace_request("txpool_status", rpc_params![]).await?;
+        Ok(TxPoolStatus { pending: status.pending.saturating_to(), queued: status.queued.saturating_to() })
+    }
+
+    /// Every transaction in the node's pool
+    pub async fn get_txpool_content(&self) -> Result<TxPoolContent> {
+        let content: RawTxPoolContent = self.namespace_request("txpool_content", rpc_params![]).await?;
+        let flatten = |section: HashMap<Address, HashMap<String, RawPoolTransaction>>| {
+            section.into_values()
+                .flat_map(|by_nonce| by_nonce.into_values())
+                .map(|tx| PoolTransaction {
+                    hash: tx.hash,
+                    from: tx.from,
+                    nonce: tx.nonce.saturating_to(),
+                    gas_price: tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default(),
+                })
+                .collect()
+        };
+        Ok(TxPoolContent { pending: flatten(content.pending), queued: flatten(content.queued) })
+    }
+
+    /// Call a method from a namespace nodes may leave unexposed (`debug_*`,
+    /// `txpool_*`). Called directly rather than through retry_rpc_call:
+    /// traces are expensive, and a missing namespace deserves its own
+    /// message.
+    async fn namespace_request<R>(&self, method: &str, params: ArrayParams) -> Result<R>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let namespace = method.split('_').next().unwrap_or(method);
+        self.transport()
+            .request(method, params)
+            .await
+            .map_err(|e| match e {
+                jsonrpsee::core::Error::Call(ref err) if err.code() == METHOD_NOT_FOUND_CODE => {
+                    RethClientError::RpcError {
+                        code: err.code(),
+                        message: format!("Node does not support {} (is the {} namespace enabled?)", method, namespace),
+                    }
+                }
+                // Retrying or moving to another endpoint won't make it smaller
+                e if is_response_too_large(&e) => RethClientError::Parse(
+                    format!("{} returned more than the {} MB response limit", method, MAX_RESPONSE_SIZE / (1024 * 1024))
+                ),
+                e => classify_rpc_error(&e, &format!("{} failed", method)),
+            })
+    }
+
+    /// Plain `eth_call` against the latest state
+    pub async fn call(&self, to: Address, data: Vec<u8>) -> Result<Bytes> {
+        self.call_contract(to, data, None).await
+    }
+
+    /// Plain `eth_call` against the state at `block` (default: latest)
+    pub async fn call_contract(&self, to: Address, data: Vec<u8>, block: Option<u64>) -> Result<Bytes> {
+        let block_param = block.map_or("latest".to_string(), |n| format!("0x{:x}", n));
+        let call = serde_json::json!({ "to": to, "data": Bytes::from(data) });
+        self.retry_rpc_call("eth_call", rpc_params![call, block_param]).await
+    }
+
+    /// ERC-20 `balanceOf(holder)` at `block` (default: latest)
+    pub async fn get_token_balance(&self, token: Address, holder: Address, block: Option<u64>) -> Result<U256> {
+        let data = [&BALANCE_OF_SELECTOR[..], holder.into_word().as_slice()].concat();
+        let output = self.call_contract(token, data, block).await?;
+        if output.len() < 32 {
+            return Err(RethClientError::Parse(format!("{} returned no balance; is it an ERC-20 token?", token)));
+        }
+        Ok(U256::from_be_slice(&output[..32]))
+    }
+
+    /// A token's `symbol()` and `decimals()`, looked up once per token
+    pub async fn get_token_metadata(&self, token: Address) -> Result<TokenMetadata> {
+        if let Some(metadata) = self.token_metadata.get(&token).await {
+            return Ok(metadata);
+        }
+
+        // A token without these functions reverts; that's an answer, not a
+        // failure. Any other error may be gone next time, so the result is
+        // only cached when both calls got an answer.
+        let is_answer = |result: &Result<Bytes>| match result {
+            Ok(_) => true,
+            Err(e) => matches!(e, RethClientError::RpcError { .. }),
+        };
+        let decimals_result = self.call(token, DECIMALS_SELECTOR.to_vec()).await;
+        let symbol_result = self.call(token, SYMBOL_SELECTOR.to_vec()).await;
+        let definitive = is_answer(&decimals_result) && is_answer(&symbol_result);
+
+        let decimals = match decimals_result {
+            Ok(output) if output.len() >= 32 => U256::from_be_slice(&output[..32]).try_into().ok(),
+            _ => None,
+        };
+        let symbol = match symbol_result {
+            Ok(output) => decode_token_symbol(&output),
+            Err(_) => None,
+        };
+
+        let metadata = TokenMetadata { symbol, decimals };
+        if definitive {
+            self.token_metadata.insert(token, metadata.clone()).await;
+        }
+        Ok(metadata)
+    }
+
+    /// Current ERC-20 balances of `holders`, read through Multicall3 in
+    /// batches. Holders whose `balanceOf` call fails get `None`. On chains
+    /// without Multicall3 each balance is read with its own call.
+    pub async fn get_token_balances(&self, token: Address, holders: &[Address]) -> Result<Vec<Option<U256>>> {
+        if self.get_code(&MULTICALL3, None).await?.is_empty() {
+            self.warn("Multicall3 is not deployed on this chain, reading balances one holder at a time");
+            return Ok(stream::iter(holders)
+                .map(|holder| async move { self.get_token_balance(token, *holder, None).await.ok() })
+                .buffered(BLOCK_FETCH_CONCURRENCY)
+                .collect()
+                .await);
+        }
+
+        let mut balances = Vec::with_capacity(holders.len());
+        for batch in holders.chunks(MULTICALL_BATCH_SIZE) {
+            let calls = batch.iter()
+                .map(|holder| DynSolValue::Tuple(vec![
+                    DynSolValue::Address(token),
+                    DynSolValue::Bool(true),
+                    DynSolValue::Bytes([&BALANCE_OF_SELECTOR[..], holder.into_word().as_slice()].concat()),
+                ]))
+                .collect();
+            let data = [&AGGREGATE3_SELECTOR[..], &DynSolValue::Tuple(vec![DynSolValue::Array(calls)]).abi_encode_params()].concat();
+            let output = self.call(MULTICALL3, data).await?;
+
+            let results = DynSolType::Array(Box::new(DynSolType::Tuple(vec![DynSolType::Bool, DynSolType::Bytes])))
+                .abi_decode(&output)
+                .map_err(|e| RethClientError::Parse(format!("Invalid Multicall3 response: {}", e)))?;
+            let results = results.as_array().unwrap_or_default();
+            if results.len() != batch.len() {
+                return Err(RethClientError::Parse(format!("Multicall3 returned {} results for {} calls", results.len(), batch.len())));
+            }
+            balances.extend(results.iter().map(|result| match result.as_tuple() {
+                Some([DynSolValue::Bool(true), DynSolValue::Bytes(data)]) if data.len() >= 32 => {
+                    Some(U256::from_be_slice(&data[..32]))
+                }
+                _ => None,
+            }));
+        }
+        Ok(balances)
+    }
+
+    /// Fetch the headers of every block in the inclusive range, returning
+    /// results in block order
+    pub async fn get_block_headers(&self, from_block: u64, to_block: u64) -> Vec<(u64, Result<Block>)> {
+        let numbers: Vec<u64> = (from_block..=to_block).collect();
+        self.get_blocks(&numbers, false).await
+    }
+
+    /// Priority fees paid to the block's fee recipient: each transaction's
+    /// effective gas price above the base fee, times its gas used. Before
+    /// London there is no base fee and the whole fee goes to the miner.
+    pub async fn get_priority_fees(&self, block: &Block) -> Result<U256> {
+        let base_fee = block.base_fee_per_gas.unwrap_or_default();
+        let receipts = self.get_block_receipts(block.number.saturating_to::<u64>()).await?;
+
+        let mut total = U256::ZERO;
+        for receipt in receipts.iter() {
+            let field = |name: &str| -> Result<U256> {
+                let hex = receipt.get(name).and_then(|v| v.as_str())
+                    .ok_or_else(|| RethClientError::Parse(format!("Receipt is missing {}", name)))?;
+                Ok(U256::from_str_radix(hex.trim_start_matches("0x"), 16)?)
+            };
+            let tip = field("effectiveGasPrice")?.saturating_sub(base_fee);
+            total += tip * field("gasUsed")?;
+        }
+        Ok(total)
+    }
+
+    /// Fee history of the last `block_count` blocks with priority fees at
+    /// the given percentiles, which must ascend within 0..=100
+    pub async fn get_fee_history(&self, block_count: u64, percentiles: &[f64]) -> Result<FeeHistory> {
+        if block_count == 0 || block_count > MAX_FEE_HISTORY_BLOCKS {
+            return Err(RethClientError::InvalidInput(format!(
+                "Fee history covers 1 to {} blocks", MAX_FEE_HISTORY_BLOCKS
+            )));
+        }
+        let in_range = percentiles.iter().all(|p| (0.0..=100.0).contains(p));
+        if !in_range || percentiles.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(RethClientError::InvalidInput(
+                "Percentiles must be between 0 and 100, in ascending order".to_string()
+            ));
+        }
+
+        let result: serde_json::Value = self
+            .retry_rpc_call("eth_feeHistory", rpc_params![format!("0x{:x}", block_count), "latest", percentiles])
+            .await?;
+        parse_fee_history(&result, percentiles)
+    }
+
+    /// Gas figures over the inclusive range `from_block..=to_block`, with
+    /// up to `concurrency` blocks being fetched at once. Ranges longer than
+    /// `MAX_SCAN_BLOCKS` are trimmed to their most recent blocks.
+    pub async fn get_gas_statistics(&self, from_block: u64, to_block: u64, concurrency: usize, tally: &mut ErrorTally) -> Result<GasStatistics> {
+        let start_block = self.gas_window_start(from_block, to_block)?;
+
+        let mut total_fees = U256::ZERO;
+        let mut total_tx_gas = 0u64;
+        let mut samples: Vec<BlockGasSample> = Vec::new();
+
+        let numbers: Vec<u64> = (start_block..=to_block).collect();
+        let progress = Progress::new("📊 Analyzing gas", numbers.len() as u64, !self.quiet);
+        let progress = &progress;
+        // Blocks arrive in batches, a chunk at a time so progress shows from
+        // the start; their receipts are fetched concurrently. Each result is
+        // tallied as it arrives, so fail-fast drops the fetches still running.
+        let results = stream::iter(numbers.chunks(GAS_STATS_CHUNK_BLOCKS))
+            .then(|chunk| self.get_blocks(chunk, true))
+            .flat_map(stream::iter)
+            .map(|(n, block)| async move {
+                let with_fees = match block {
+                    Ok(block) => self.block_fees(&block).await.map(|fees| (block, fees)),
+                    Err(e) => Err(e),
+                };
+                progress.inc(1);
+                (n, with_fees)
+            })
+            .buffer_unordered(concurrency.max(1));
+        tokio::pin!(results);
+        let scanned: Result<()> = async {
+            while let Some((block_num, block)) = results.next().await {
+                if let Some((block, (fees, tx_gas))) = tally.check(format!("block {}", block_num), block)? {
+                    samples.push(BlockGasSample::from_block(&block));
+                    total_fees += fees;
+                    total_tx_gas += tx_gas;
+                }
+            }
+            Ok(())
+        }.await;
+        progress.finish();
+        scanned?;
+        // Samples feed the trend chart and CSV, which go in block order
+        samples.sort_by_key(|sample| sample.number);
+
+        // Extremes come from the blocks actually analyzed, so a skipped or
+        // empty range can never report a placeholder like u64::MAX
+        let (Some(min_gas_used), Some(max_gas_used)) = (
+            samples.iter().map(|sample| sample.gas_used).min(),
+            samples.iter().map(|sample| sample.gas_used).max(),
+        ) else {
+            return Err(RethClientError::NotFound("No blocks found for gas statistics".into()));
+        };
+        let blocks_processed = samples.len();
+        let total_gas_used: u64 = samples.iter().map(|sample| sample.gas_used).sum();
+        let avg_gas_used = total_gas_used / blocks_processed as u64;
+        // Weighted by gas used, as block explorers report it
+        let avg_gas_price = match total_tx_gas {
+            0 => 0,
+            gas => (total_fees / U256::from(gas)).saturating_to::<u64>(),
+        };
+        let gas_utilization = (avg_gas_used as f64 / 30_000_000.0) * 100.0;
+
+        Ok(GasStatistics {
+            avg_gas_used,
+            avg_gas_price,
+            max_gas_used,
+            min_gas_used,
+            gas_utilization,
+            blocks_analyzed: blocks_processed,
+            blocks: samples,
+        })
+    }
+
+    /// Fee totals of a full block's transactions, from its receipts
+    async fn block_fees(&self, block: &Block) -> Result<(U256, u64)> {
+        if block.full_transactions.is_empty() {
+            return Ok((U256::ZERO, 0));
+        }
+        let receipts = self.get_block_receipts(block.number.saturating_to::<u64>()).await?;
+        block_fee_totals(&block.full_transactions, &receipts)
+    }
+
+    /// `cap_gas_range`, warning when the range was trimmed
+    fn gas_window_start(&self, from_block: u64, to_block: u64) -> Result<u64> {
+        let start_block = cap_gas_range(from_block, to_block)?;
+        if start_block != from_block && !self.quiet {
+            eprintln!(
+                "⚠️  Range of {} blocks exceeds the limit of {}; analyzing blocks {}..={} only",
+                format_number(to_block - from_block + 1), format_number(MAX_SCAN_BLOCKS), start_block, to_block
+            );
+        }
+        Ok(start_block)
+    }
+
+    /// Per-transaction gas figures over the same kind of window as
+    /// `get_gas_statistics`, read from each block's receipts
+    pub async fn get_tx_gas_samples(&self, from_block: u64, to_block: u64, concurrency: usize, tally: &mut ErrorTally) -> Result<(usize, Vec<TxGasSample>)> {
+        let start_block = self.gas_window_start(from_block, to_block)?;
+
+        let progress = Progress::new("📊 Analyzing transaction gas", to_block - start_block + 1, !self.quiet);
+        let progress = &progress;
+        // Tallied as they arrive, as in `get_gas_statistics`
+        let mut results = stream::iter(start_block..=to_block)
+            .map(|n| async move {
+                let receipts = self.get_block_receipts(n).await;
+                progress.inc(1);
+                (n, receipts)
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        let mut blocks_processed = 0usize;
+        let mut samples = Vec::new();
+        let scanned: Result<()> = async {
+            while let Some((block_num, receipts)) = results.next().await {
+                let Some(receipts) = tally.check(format!("block {}", block_num), receipts)? else {
+                    continue;
+                };
+                for receipt in receipts.iter() {
+                    samples.push(parse_tx_gas_sample(receipt, block_num)?);
+                }
+                blocks_processed += 1;
+            }
+            Ok(())
+        }.await;
+        progress.finish();
+        scanned?;
+        // Receipts within a block keep their order; blocks go oldest first
+        samples.sort_by_key(|sample| sample.block_number);
+
+        if blocks_processed == 0 {
+            return Err(RethClientError::NotFound("No blocks found for gas statistics".into()));
+        }
+        Ok((blocks_processed, samples))
+    }
+
+    /// Fetch logs matching `filter` over the inclusive range, one
+    /// `eth_getLogs` call per chunk of at most `chunk_size` blocks
+    pub async fn get_logs(&self, filter: &LogFilter, from_block: u64, to_block: u64, chunk_size: u64, tally: &mut ErrorTally) -> Result<Vec<Log>> {
+        self.get_logs_in_ranges(filter, block_chunks(from_block, to_block, chunk_size), tally).await
+    }
+
+    /// `get_logs`, but first test each block's logs bloom locally and only
+    /// query runs of blocks that may hold a match. Worth it for sparse
+    /// filters; blocks whose header can't be read are queried anyway.
+    pub async fn get_logs_bloom_filtered(&self, filter: &LogFilter, from_block: u64, to_block: u64, chunk_size: u64, tally: &mut ErrorTally) -> Result<Vec<Log>> {
+        let mut candidates = Vec::new();
+        for (number, header) in self.get_block_headers(from_block, to_block).await {
+            let bloom = header.ok().and_then(|block| block.logs_bloom);
+            if bloom.is_none_or(|bloom| filter.bloom_matches(&bloom)) {
+                candidates.push(number);
+            }
+        }
+        if !self.quiet {
+            eprintln!("🌸 Bloom filter kept {} of {} blocks", candidates.len(), to_block - from_block + 1);
+        }
+        self.get_logs_in_ranges(filter, block_runs(&candidates, chunk_size), tally).await
+    }
+
+    async fn get_logs_in_ranges(&self, filter: &LogFilter, ranges: Vec<(u64, u64)>, tally: &mut ErrorTally) -> Result<Vec<Log>> {
+        let mut logs = Vec::new();
+        for (start, end) in ranges {
+            let chunk = self.fetch_raw_logs(filter, start, end).await;
+            let Some(chunk) = tally.check(format!("blocks {}..={}", start, end), chunk)? else {
+                continue;
+            };
+            for value in chunk {
+                logs.push(self.parse_log(&value)?);
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Count logs matching `filter` per chunk without parsing them
+    pub async fn count_logs(&self, filter: &LogFilter, from_block: u64, to_block: u64, chunk_size: u64, tally: &mut ErrorTally) -> Result<Vec<((u64, u64), usize)>> {
+        let mut counts = Vec::new();
+        for chunk in block_chunks(from_block, to_block, chunk_size) {
+            let logs = self.fetch_raw_logs(filter, chunk.0, chunk.1).await;
+            if let Some(logs) = tally.check(format!("blocks {}..={}", chunk.0, chunk.1), logs)? {
+                counts.push((chunk, logs.len()));
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn fetch_raw_logs(&self, filter: &LogFilter, from_block: u64, to_block: u64) -> Result<Vec<serde_json::Value>> {
+        self.retry_rpc_call("eth_getLogs", rpc_params![filter.to_params(from_block, to_block)])
+            .await
+    }
+
+    /// Collect all transactions sent by `address` in the inclusive block range.
+    ///
+    /// This is a linear scan over full blocks, not an index lookup, so the
+    /// range is capped at `MAX_SCAN_BLOCKS`.
+    pub async fn get_transactions_from(&self, address: &str, from_block: u64, to_block: u64, tally: &mut ErrorTally) -> Result<Vec<Transaction>> {
+        let sender: Address = address.parse()
+            .map_err(|e| RethClientError::InvalidInput(format!("Invalid address format: {}", e)))?;
+        check_scan_range(from_block, to_block)?;
+
+        if !self.quiet {
+            eprintln!("🔎 Scanning {} blocks for transactions from {}...", to_block - from_block + 1, sender);
+        }
+
+        // Blocks are fetched a few at a time but come back in order, so the
+        // results stay chronological and fail-fast stops at the first error
+        let mut results = stream::iter(from_block..=to_block)
+            .map(|block_num| async move { (block_num, self.get_block_transactions_from(sender, block_num).await) })
+            .buffered(BLOCK_FETCH_CONCURRENCY);
+        let mut matches = Vec::new();
+        while let Some((block_num, sent)) = results.next().await {
+            if let Some(sent) = tally.check(format!("block {}", block_num), sent)? {
+                matches.extend(sent);
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Transactions sent by `sender` in one block, with receipts applied
+    async fn get_block_transactions_from(&self, sender: Address, block_num: u64) -> Result<Vec<Transaction>> {
+        let block = self.get_block_by_number(block_num).await?;
+        let mut sent: Vec<_> = block.full_transactions.into_iter().filter(|tx| tx.from == sender).collect();
+        if sent.is_empty() {
+            return Ok(sent);
+        }
+
+        // One call covers every match in the block; nodes without
+        // eth_getBlockReceipts fall back to per-transaction receipts
+        let receipts = self.get_block_receipts(block_num).await?;
+        for tx in &mut sent {
+            let receipt = receipts.iter()
+                .find(|receipt| {
+                    receipt.get("transactionHash")
+                        .and_then(|v| v.as_str())
+                        .and_then(|hash| hash.parse::<B256>().ok()) == Some(tx.hash)
+                })
+                .ok_or_else(|| RethClientError::NotFound(format!("No receipt for transaction {:?} in block {}", tx.hash, block_num)))?;
+            self.apply_receipt(tx, receipt)?;
+        }
+        Ok(sent)
+    }
+
+    fn disk_get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.disk.as_ref()?.get(key)
+    }
+
+    /// Write data from `block_number` through to the disk cache once that
+    /// block can no longer be reorganized away
+    async fn disk_put_if_final<T: Serialize>(&self, key: &str, block_number: u64, value: &T) {
+        let Some(disk) = &self.disk else {
+            return;
+        };
+        if self.finalized_block().await.is_some_and(|finalized| block_number <= finalized) {
+            disk.put(key, value);
+        }
+    }
+
+    /// The node's `finalized` block, or `FINALITY_DEPTH` blocks behind the
+    /// head on chains without one. `None` if neither can be found, in which
+    /// case nothing is written to disk.
+    async fn finalized_block(&self) -> Option<u64> {
+        self.finalized
+            .get_or_try_init(|| async {
+                match self.get_block_by_tag(BlockTag::Finalized, false).await {
+                    Ok(block) => Ok(block.number.saturating_to::<u64>()),
+                    Err(_) => self.get_latest_block_number().await
+                        .map(|latest| latest.saturating_sub(FINALITY_DEPTH)),
+                }
+            })
+            .await
+            .ok()
+            .copied()
+    }
+
+    /// The active endpoint's connection, for requests made without retries
+    /// New chain heads pushed by the node over `eth_subscribe("newHeads")`,
+    /// on transports that carry notifications (WebSocket and IPC). `None`
+    /// over HTTP, where callers poll instead.
+    pub async fn subscribe_new_heads(&self) -> Result<Option<BoxStream<'_, Result<Block>>>> {
+        let client = match self.transport() {
+            RpcTransport::Http(_) => return Ok(None),
+            RpcTransport::Ws(client) | RpcTransport::Ipc(client) => client,
+        };
+        let subscription = client
+            .subscribe::<serde_json::Value, _>("eth_subscribe", rpc_params!["newHeads"], "eth_unsubscribe")
+            .await
+            .map_err(|e| classify_rpc_error(&e, "Failed to subscribe to newHeads"))?;
+        let heads = subscription.map(|head| match head {
+            Ok(head) => self.parse_block(head),
+            Err(e) => Err(classify_rpc_error(&e, "newHeads subscription failed")),
+        });
+        Ok(Some(heads.boxed()))
+    }
+
+    fn transport(&self) -> &RpcTransport {
+        &self.endpoints[self.active.load(Ordering::Relaxed)].transport
+    }
+
+    /// Send a request with retries. Once retries against the active
+    /// endpoint are used up without reaching it, the next endpoint takes
+    /// over, for this request and the ones after it.
+    async fn retry_rpc_call<T>(&self, method: &str, params: jsonrpsee::core::params::ArrayParams) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut index = self.active.load(Ordering::Relaxed);
+        let mut switches = 0;
+        loop {
+            let result = self.retry_on(&self.endpoints[index], method, params.clone()).await;
+            let unreachable = result.as_ref().err()
+                .is_some_and(RethClientError::is_unreachable);
+            if !unreachable || switches + 1 >= self.endpoints.len() {
+                return result;
+            }
+
+            let next = (index + 1) % self.endpoints.len();
+            // Concurrent requests may fail together; only one announces it
+            if self.active.compare_exchange(index, next, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+                self.warn(format_args!("{} is not responding, switching to {}", self.endpoints[index].url, self.endpoints[next].url));
+            }
+            index = next;
+            switches += 1;
+        }
+    }
+
+    async fn retry_on<T>(&self, endpoint: &Endpoint, method: &str, params: jsonrpsee::core::params::ArrayParams) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let notify = |e: RethClientError, wait: Duration| {
+            self.warn(format_args!("{}, retrying in {:.1}s", e, wait.as_secs_f64()));
+        };
+        backoff::future::retry_notify(self.backoff.build(), || async {
+            endpoint.transport
+                .request(method, params.clone())
+                .await
+                .map_err(|e| {
+                    let error = classify_rpc_error(&e, "RPC call failed");
+                    if is_permanent_rpc_error(&e) {
+                        backoff::Error::permanent(error)
+                    } else {
+                        backoff::Error::transient(error)
+                    }
+                })
+        }, notify)
+        .await
+    }
+
+    fn parse_block(&self, value: serde_json::Value) -> Result<Block> {
+        self.timed_parse(|| self.parse_block_object(value))
+    }
+
+    fn parse_block_object(&self, value: serde_json::Value) -> Result<Block> {
+        let obj = value.as_object().ok_or_else(|| RethClientError::Parse("Invalid block format".into()))?;
+        
+        Ok(Block {
+            number: U256::from_str_radix(
+                obj.get("number").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            hash: obj.get("hash").and_then(|v| v.as_str()).map(str::parse).transpose()?,
+            parent_hash: obj.get("parentHash").and_then(|v| v.as_str()).unwrap_or("0x0").parse()?,
+            timestamp: U256::from_str_radix(
+                obj.get("timestamp").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            gas_used: U256::from_str_radix(
+                obj.get("gasUsed").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            gas_limit: U256::from_str_radix(
+                obj.get("gasLimit").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            base_fee_per_gas: obj.get("baseFeePerGas").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            transactions: obj.get("transactions")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|tx| {
+                    if let Some(hash_str) = tx.as_str() {
+                        hash_str.parse().ok()
+                    } else {
+                        tx.get("hash").and_then(|h| h.as_str()?.parse().ok())
+                    }
+                }).collect())
+                .unwrap_or_default(),
+            full_transactions: obj.get("transactions")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter()
+                    .filter(|tx| tx.is_object())
+                    .map(|tx| self.parse_tx_object(tx))
+                    .collect::<Result<Vec<_>>>())
+                .transpose()?
+                .unwrap_or_default(),
+            miner: obj.get("miner").and_then(|v| v.as_str()).unwrap_or("0x0000000000000000000000000000000000000000").parse()?,
+            difficulty: U256::from_str_radix(
+                obj.get("difficulty").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            size: U256::from_str_radix(
+                obj.get("size").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            extra_data: obj.get("extraData").and_then(|v| v.as_str()).unwrap_or("0x").parse()?,
+            logs_bloom: obj.get("logsBloom").and_then(|v| v.as_str()).and_then(|s| s.parse().ok()),
+            uncles: obj.get("uncles")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|hash| hash.as_str()?.parse().ok()).collect())
+                .unwrap_or_default(),
+            withdrawals: obj.get("withdrawals")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().map(parse_withdrawal).collect::<Result<Vec<_>>>())
+                .transpose()?
+                .unwrap_or_default(),
+            blob_gas_used: obj.get("blobGasUsed").and_then(|v| v.as_str()).and_then(|s| hex_to_decimal(s).ok()),
+            excess_blob_gas: obj.get("excessBlobGas").and_then(|v| v.as_str()).and_then(|s| hex_to_decimal(s).ok()),
+        })
+    }
+
+    fn parse_transaction(&self, tx_value: serde_json::Value, receipt_value: serde_json::Value) -> Result<Transaction> {
+        self.timed_parse(|| {
+            let mut transaction = self.parse_tx_object(&tx_value)?;
+            self.apply_receipt(&mut transaction, &receipt_value)?;
+            Ok(transaction)
+        })
+    }
+
+    /// Fill in the receipt-only fields (gas used, effective price, status)
+    /// of a transaction. A pending transaction has no receipt yet (null),
+    /// which leaves them `None`.
+    fn apply_receipt(&self, transaction: &mut Transaction, receipt_value: &serde_json::Value) -> Result<()> {
+        if receipt_value.is_null() {
+            return Ok(());
+        }
+        let receipt_obj = receipt_value.as_object().ok_or_else(|| RethClientError::Parse("Invalid receipt format".into()))?;
+
+        transaction.gas_used = receipt_obj.get("gasUsed").and_then(|v| v.as_str()).and_then(|s| {
+            U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+        });
+        // The price actually paid, which the transaction object only gives
+        // as caps for EIP-1559 transactions on some nodes
+        if let Some(price) = receipt_obj.get("effectiveGasPrice").and_then(|v| v.as_str()) {
+            transaction.gas_price = U256::from_str_radix(price.trim_start_matches("0x"), 16)?;
+        }
+        transaction.status = receipt_obj.get("status").and_then(parse_status);
+        transaction.logs = receipt_obj.get("logs")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().map(|log| self.parse_log(log)).collect::<Result<Vec<_>>>())
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn parse_receipt(&self, value: &serde_json::Value) -> Result<Receipt> {
+        let obj = value.as_object().ok_or_else(|| RethClientError::Parse("Invalid receipt format".into()))?;
+        let quantity = |name: &str| -> Result<Option<U256>> {
+            obj.get(name)
+                .and_then(|v| v.as_str())
+                .map(|s| U256::from_str_radix(s.trim_start_matches("0x"), 16))
+                .transpose()
+                .map_err(|e| RethClientError::Parse(format!("Invalid receipt {}: {}", name, e)))
+        };
+        // Nodes send null rather than omitting these
+        let field = |name: &str| obj.get(name).and_then(|v| v.as_str());
+
+        Ok(Receipt {
+            transaction_hash: field("transactionHash")
+                .ok_or_else(|| RethClientError::Parse("Receipt is missing transactionHash".into()))?
+                .parse()?,
+            block_number: quantity("blockNumber")?,
+            block_hash: field("blockHash").map(str::parse).transpose()?,
+            transaction_index: quantity("transactionIndex")?,
+            // Every receipt names its sender; the zero address would be a
+            // plausible-looking wrong answer
+            from: field("from")
+                .ok_or_else(|| RethClientError::Parse("Receipt is missing from".into()))?
+                .parse()?,
+            to: field("to").map(str::parse).transpose()?,
+            status: obj.get("status").and_then(parse_status),
+            gas_used: quantity("gasUsed")?.unwrap_or_default(),
+            cumulative_gas_used: quantity("cumulativeGasUsed")?.unwrap_or_default(),
+            effective_gas_price: quantity("effectiveGasPrice")?.unwrap_or_default(),
+            contract_address: field("contractAddress").map(str::parse).transpose()?,
+            logs_bloom: field("logsBloom").map(str::parse).transpose()?,
+            logs: obj.get("logs")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().map(|log| self.parse_log(log)).collect::<Result<Vec<_>>>())
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+
+    fn parse_log(&self, value: &serde_json::Value) -> Result<Log> {
+        let obj = value.as_object().ok_or_else(|| RethClientError::Parse("Invalid log format".into()))?;
+
+        Ok(Log {
+            address: obj.get("address").and_then(|v| v.as_str()).unwrap_or("0x0000000000000000000000000000000000000000").parse()?,
+            topics: obj.get("topics")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|t| t.as_str()?.parse().ok()).collect())
+                .unwrap_or_default(),
+            data: obj.get("data").and_then(|v| v.as_str()).unwrap_or("0x").parse()?,
+            block_number: obj.get("blockNumber").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            transaction_hash: obj.get("transactionHash").and_then(|v| v.as_str()).and_then(|s| s.parse().ok()),
+            log_index: obj.get("logIndex").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+        })
+    }
+
+    /// Parse a transaction object without its receipt, as found in a block
+    /// fetched with full transactions.
+    fn parse_tx_object(&self, tx_value: &serde_json::Value) -> Result<Transaction> {
+        let tx_obj = tx_value.as_object().ok_or_else(|| RethClientError::Parse("Invalid transaction format".into()))?;
+        
+        Ok(Transaction {
+            hash: tx_obj.get("hash").and_then(|v| v.as_str()).unwrap_or("0x0").parse()?,
+            block_number: tx_obj.get("blockNumber").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            transaction_index: tx_obj.get("transactionIndex").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            from: tx_obj.get("from").and_then(|v| v.as_str()).unwrap_or("0x0000000000000000000000000000000000000000").parse()?,
+            to: tx_obj.get("to").and_then(|v| v.as_str()).and_then(|s| s.parse().ok()),
+            nonce: U256::from_str_radix(
+                tx_obj.get("nonce").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            value: U256::from_str_radix(
+                tx_obj.get("value").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            gas: U256::from_str_radix(
+                tx_obj.get("gas").and_then(|v| v.as_str()).unwrap_or("0x0").trim_start_matches("0x"), 16
+            )?,
+            // Some nodes leave out `gasPrice` for EIP-1559 transactions; the
+            // fee cap is then the closest figure available
+            gas_price: U256::from_str_radix(
+                tx_obj.get("gasPrice")
+                    .or_else(|| tx_obj.get("maxFeePerGas"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("0x0")
+                    .trim_start_matches("0x"), 16
+            )?,
+            tx_type: tx_obj.get("type").and_then(|v| v.as_str()).and_then(|s| {
+                u8::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            max_fee_per_gas: tx_obj.get("maxFeePerGas").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            max_priority_fee_per_gas: tx_obj.get("maxPriorityFeePerGas").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            max_fee_per_blob_gas: tx_obj.get("maxFeePerBlobGas").and_then(|v| v.as_str()).and_then(|s| {
+                U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+            }),
+            blob_versioned_hashes: tx_obj.get("blobVersionedHashes")
+                .filter(|v| !v.is_null())
+                .map(|v| serde_json::from_value(v.clone()))
+                .transpose()
+                .map_err(|e| RethClientError::Parse(format!("Invalid blob versioned hashes: {}", e)))?
+                .unwrap_or_default(),
+            gas_used: None,
+            status: None,
+            input: tx_obj.get("input").and_then(|v| v.as_str()).unwrap_or("0x").parse()?,
+            // Some nodes send `"accessList": null` for legacy transactions
+            access_list: tx_obj.get("accessList")
+                .filter(|v| !v.is_null())
+                .map(|v| serde_json::from_value(v.clone()))
+                .transpose()
+                .map_err(|e| RethClientError::Parse(format!("Invalid access list: {}", e)))?,
+            logs: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::utils::ErrorPolicy;
+    use crate::test_support::{self, rpc_error, rpc_result, RpcResponder};
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Start a mock node that answers the connection check, and a client for it
+    async fn mock_node() -> (MockServer, RethClient) {
+        let server = test_support::mock_node().await;
+        let client = RethClient::new(&[server.uri()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_url_scheme() {
+        let error = RethClient::new(&["ftp://node.example".to_string()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.err().unwrap();
+        assert!(matches!(error, RethClientError::InvalidInput(_)));
+        let error = RethClient::new(&["ipc:///nonexistent/reth.ipc".to_string()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.err().unwrap();
+        assert!(error.to_string().contains("IPC socket /nonexistent/reth.ipc does not exist"));
+        // Nothing listens here, so the WebSocket handshake itself fails
+        let error = RethClient::new(&["ws://127.0.0.1:1".to_string()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.err().unwrap();
+        assert!(matches!(error, RethClientError::Connection(_)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ipc_transport() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let path = std::env::temp_dir().join(format!("eth_data_extractor_{}.ipc", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 1024];
+            loop {
+                let read = socket.read(&mut chunk).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                buffer.extend_from_slice(&chunk[..read]);
+                while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+                    let request: serde_json::Value = serde_json::from_slice(&buffer[..end]).unwrap();
+                    buffer.drain(..=end);
+                    let result = match request["method"].as_str() {
+                        Some("eth_chainId") => json!("0x1"),
+                        _ => json!("0x2a"),
+                    };
+                    // Split each response across writes, as a busy socket might
+                    let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+                    let (head, tail) = response.split_at(response.len() / 2);
+                    socket.write_all(head.as_bytes()).await.unwrap();
+                    socket.write_all(tail.as_bytes()).await.unwrap();
+                }
+            }
+        });
+
+        let client = RethClient::new(&[path.display().to_string()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        assert_eq!(client.get_latest_block_number().await.unwrap(), 42);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_new_heads_subscription() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let path = std::env::temp_dir().join(format!("eth_data_extractor_heads_{}.ipc", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (read, mut write) = socket.into_split();
+            let mut lines = BufReader::new(read).lines();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+                let result = match request["method"].as_str() {
+                    Some("eth_subscribe") => json!("0xabc"),
+                    _ => json!("0x1"),
+                };
+                let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": result });
+                write.write_all(format!("{}\n", response).as_bytes()).await.unwrap();
+                if request["method"] == "eth_subscribe" {
+                    for number in ["0x10", "0x11"] {
+                        let mut head = test_support::block("0x10");
+                        head["number"] = json!(number);
+                        let notification = json!({
+                            "jsonrpc": "2.0",
+                            "method": "eth_subscription",
+                            "params": { "subscription": "0xabc", "result": head },
+                        });
+                        write.write_all(format!("{}\n", notification).as_bytes()).await.unwrap();
+                    }
+                }
+            }
+        });
+
+        let client = RethClient::new(&[path.display().to_string()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        let heads = client.subscribe_new_heads().await.unwrap().unwrap();
+        let numbers: Vec<U256> = heads.take(2).map(|head| head.unwrap().number).collect().await;
+        assert_eq!(numbers, [U256::from(16), U256::from(17)]);
+        std::fs::remove_file(path).unwrap();
+
+        // HTTP can't carry notifications
+        let (_server, client) = mock_node().await;
+        assert!(client.subscribe_new_heads().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(json!({ "jsonrpc": "2.0", "id": 0, "result": "0x1" }))
+                .set_delay(Duration::from_secs(2)))
+            .mount(&server)
+            .await;
+
+        let error = RethClient::new(&[server.uri()], &CacheConfig::default(), Duration::from_millis(200), false).await.err().unwrap();
+        assert!(matches!(error, RethClientError::Timeout(_)));
+    }
+
+    #[tokio::test]
+    async fn test_with_client_skips_handshake() {
+        let server = MockServer::start().await;
+        rpc_result("eth_chainId", json!("0x1")).expect(0).mount(&server).await;
+        rpc_result("eth_blockNumber", json!("0x20")).mount(&server).await;
+
+        let http = HttpClientBuilder::default().build(server.uri()).unwrap();
+        let client = RethClient::with_client(http, &CacheConfig::default(), 137).unwrap();
+        assert_eq!(client.chain_id(), 137);
+        assert_eq!(client.native_currency().symbol, "POL");
+        assert_eq!(client.get_latest_block_number().await.unwrap(), 0x20);
+        assert!(client.quiet && !client.warnings);
+    }
+
+    #[test]
+    fn test_retry_config() {
+        let retry = RetryConfig::new(Duration::from_secs(5), Duration::from_millis(100)).unwrap();
+        assert_eq!(retry.backoff().build().initial_interval, Duration::from_millis(100));
+        assert_eq!(retry.backoff().build().max_elapsed_time, Some(Duration::from_secs(5)));
+        assert!(RetryConfig::new(Duration::from_secs(1), Duration::from_secs(2)).is_err());
+        assert!(RetryConfig::new(Duration::from_secs(1), Duration::ZERO).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_next_endpoint() {
+        let (primary, _) = mock_node().await;
+        let (backup, _) = mock_node().await;
+        rpc_result("eth_blockNumber", json!("0x20")).mount(&backup).await;
+        let urls = ["http://127.0.0.1:1".to_string(), primary.uri(), backup.uri()];
+
+        // The unreachable first URL is skipped at connection time
+        let client = RethClient::new(&urls, &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap().with_retry(RetryConfig::NONE);
+        assert_eq!(client.endpoints.len(), 2);
+
+        // The primary goes away mid-run
+        primary.reset().await;
+        assert_eq!(client.get_latest_block_number().await.unwrap(), 0x20);
+        assert_eq!(client.active.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_endpoints_must_share_a_chain() {
+        let (mainnet, _) = mock_node().await;
+        let other = MockServer::start().await;
+        rpc_result("eth_chainId", json!("0x5")).mount(&other).await;
+        let error = RethClient::new(&[mainnet.uri(), other.uri()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.err().unwrap();
+        assert!(matches!(error, RethClientError::InvalidInput(_)));
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_serves_final_blocks_across_clients() {
+        let dir = std::env::temp_dir().join(format!("eth_data_extractor_rpc_cache_{}", std::process::id()));
+        let config = CacheConfig { dir: Some(dir.clone()), ..CacheConfig::default() };
+        let server = test_support::mock_node().await;
+        rpc_result("eth_blockNumber", json!("0x30")).mount(&server).await;
+        let mut finalized = test_support::block("0x10");
+        finalized["number"] = json!("0x20");
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["finalized", false] })))
+            .respond_with(RpcResponder(json!({ "result": finalized })))
+            .mount(&server)
+            .await;
+        // Block 0x10 is final, so only the first client may fetch it
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["0x10", true] })))
+            .respond_with(RpcResponder(json!({ "result": test_support::block("0x10") })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let first = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        assert_eq!(first.get_block_by_number(16).await.unwrap().number, U256::from(16));
+        let second = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        assert_eq!(second.get_block_by_number(16).await.unwrap().number, U256::from(16));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_record_across_runs() {
+        let dir = std::env::temp_dir().join(format!("eth_data_extractor_endpoints_{}", std::process::id()));
+        let config = CacheConfig { dir: Some(dir.clone()), ..CacheConfig::default() };
+        let server = MockServer::start().await;
+        // The chain id is asked for on every run, record or not
+        rpc_result("eth_chainId", json!("0x89")).up_to_n_times(2).expect(2).mount(&server).await;
+        rpc_result("eth_blockNumber", json!("0x100")).up_to_n_times(2).mount(&server).await;
+
+        let first = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        assert_eq!(first.chain_id(), 137);
+        let record = DiskCache::endpoints(&dir).unwrap().get::<EndpointRecord>(&server.uri());
+        assert_eq!(record, Some(EndpointRecord { chain_id: 137, head: 256 }));
+        let second = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        assert_eq!(second.native_currency().symbol, "POL");
+
+        // The same URL now serving another chain is noticed, not trusted
+        rpc_result("eth_chainId", json!("0x1")).mount(&server).await;
+        rpc_result("eth_blockNumber", json!("0x5")).mount(&server).await;
+        let third = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+        assert_eq!(third.chain_id(), 1);
+        assert_eq!(third.native_currency().symbol, "ETH");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_transient_server_error_is_retried() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_blockNumber" })))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        rpc_result("eth_blockNumber", json!("0x10")).mount(&server).await;
+
+        assert_eq!(client.get_latest_block_number().await.unwrap(), 16);
+    }
+
+    #[tokio::test]
+    async fn test_method_not_found_fails_fast() {
+        let (server, client) = mock_node().await;
+        rpc_error("eth_blockNumber", -32601, "the method eth_blockNumber does not exist")
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = client.get_latest_block_number().await.unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_params_fails_fast() {
+        let (server, client) = mock_node().await;
+        rpc_error("eth_getBlockByNumber", -32602, "invalid argument 0: hex string without 0x prefix")
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = client.get_block_by_number(16).await.unwrap_err();
+        assert!(err.to_string().contains("hex string without 0x prefix"));
+        assert_eq!(err.exit_code(), 5);
+        // The node's code and message survive for callers to match on
+        assert_eq!(err, RethClientError::RpcError {
+            code: -32602,
+            message: "invalid argument 0: hex string without 0x prefix".to_string(),
+        });
+    }
+
+    #[tokio::test]
+    async fn test_null_block_is_not_found() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getBlockByNumber", serde_json::Value::Null).mount(&server).await;
+
+        let err = client.get_block_by_number(99).await.unwrap_err();
+        assert_eq!(err.to_string(), "Block 99 not found");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_block_is_a_parse_error() {
+        let (server, client) = mock_node().await;
+        let mut block = test_support::block("0x10");
+        block["miner"] = json!("0xnot-an-address");
+        rpc_result("eth_getBlockByNumber", block).mount(&server).await;
+
+        let err = client.get_block_by_number(16).await.unwrap_err();
+        assert!(matches!(err, RethClientError::Parse(_)));
+        assert_eq!(err.exit_code(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_block_parse() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getBlockByNumber", test_support::block("0x10")).mount(&server).await;
+
+        let block = client.get_block_by_number(16).await.unwrap();
+        assert_eq!(block.number, U256::from(16));
+        assert_eq!(block.gas_used, U256::from(15_000_000));
+        assert_eq!(block.gas_limit, U256::from(30_000_000));
+        assert!(block.transactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_block_uncles() {
+        let (server, client) = mock_node().await;
+        let mut block = test_support::block("0x10");
+        block["uncles"] = json!([format!("{:?}", B256::with_last_byte(7))]);
+        rpc_result("eth_getBlockByNumber", block).mount(&server).await;
+        let mut uncle = test_support::block("0x10");
+        uncle["number"] = json!("0xf");
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "eth_getUncleByBlockHashAndIndex",
+                "params": [test_support::block("0x10")["hash"], "0x0"],
+            })))
+            .respond_with(RpcResponder(json!({ "result": uncle })))
+            .mount(&server)
+            .await;
+
+        let block = client.get_block_by_number(16).await.unwrap();
+        assert_eq!(block.uncles, vec![B256::with_last_byte(7)]);
+        // Pre-Shanghai, so no withdrawals
+        assert!(block.withdrawals.is_empty());
+        let uncle = client.get_uncle_by_block_hash_and_index(block.hash.unwrap(), 0).await.unwrap();
+        assert_eq!(uncle.number, U256::from(15));
+    }
+
+    #[tokio::test]
+    async fn test_block_withdrawals() {
+        let (server, client) = mock_node().await;
+        let mut block = test_support::block("0x10");
+        block["withdrawals"] = json!([{
+            "index": "0x1a2b",
+            "validatorIndex": "0x3039",
+            "address": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "amount": "0xf4240",
+        }]);
+        rpc_result("eth_getBlockByNumber", block).mount(&server).await;
+
+        let block = client.get_block_by_number(16).await.unwrap();
+        let withdrawal = &block.withdrawals[0];
+        assert_eq!((withdrawal.index, withdrawal.validator_index), (0x1a2b, 12_345));
+        // 1,000,000 Gwei
+        assert_eq!(withdrawal.amount_wei(), U256::from(10).pow(U256::from(15)));
+    }
+
+    #[tokio::test]
+    async fn test_pending_block_without_hash() {
+        let (server, client) = mock_node().await;
+        let mut pending = test_support::block("0x10");
+        pending["hash"] = serde_json::Value::Null;
+        pending["miner"] = serde_json::Value::Null;
+        rpc_result("eth_getBlockByNumber", pending).mount(&server).await;
+
+        let block = client.get_pending_block().await.unwrap().unwrap();
+        assert_eq!(block.hash, None);
+        assert_eq!(block.gas_used, U256::from(15_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_block_header_requests_hashes_only() {
+        let server = test_support::mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["0x10", false] })))
+            .respond_with(RpcResponder(json!({ "result": test_support::block("0x10") })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = RethClient::new(&[server.uri()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+
+        let block = client.get_block_header_by_number(16).await.unwrap();
+        assert_eq!(block.number, U256::from(16));
+        // Second lookup is served from the cache
+        client.get_block_header_by_number(16).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_zero_cache_size_disables_caching() {
+        let server = test_support::mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["0x10", false] })))
+            .respond_with(RpcResponder(json!({ "result": test_support::block("0x10") })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let config = CacheConfig { capacity: 0, ..CacheConfig::default() };
+        let client = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+
+        client.get_block_header_by_number(16).await.unwrap();
+        client.get_block_header_by_number(16).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_zero_cache_ttl_never_expires() {
+        let server = test_support::mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["0x10", false] })))
+            .respond_with(RpcResponder(json!({ "result": test_support::block("0x10") })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = CacheConfig { ttl: Duration::ZERO, ..CacheConfig::default() };
+        let client = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+
+        client.get_block_header_by_number(16).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        client.get_block_header_by_number(16).await.unwrap();
+
+        // Control: the same wait with a short TTL does refetch
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["0x11", false] })))
+            .respond_with(RpcResponder(json!({ "result": test_support::block("0x10") })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let config = CacheConfig { ttl: Duration::from_millis(50), ..CacheConfig::default() };
+        let client = RethClient::new(&[server.uri()], &config, DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+
+        client.get_block_header_by_number(17).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        client.get_block_header_by_number(17).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_latest_block_header_is_not_cached() {
+        let server = test_support::mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["latest", false] })))
+            .respond_with(RpcResponder(json!({ "result": test_support::block("0x10") })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let client = RethClient::new(&[server.uri()], &CacheConfig::default(), DEFAULT_RPC_TIMEOUT, false).await.unwrap();
+
+        assert_eq!(client.get_latest_block_header().await.unwrap().number, U256::from(16));
+        client.get_latest_block_header().await.unwrap();
+    }
+
+    // Signed example transaction from EIP-155
+    const EIP155_RAW_TX: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+    const EIP155_SIGNER: Address = alloy_primitives::address!("9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+
+    fn eip155_hash() -> String {
+        format!("{:?}", keccak256(EIP155_RAW_TX.parse::<Bytes>().unwrap()))
+    }
+
+    #[tokio::test]
+    async fn test_raw_transaction_recovers_signer() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getRawTransactionByHash", json!(EIP155_RAW_TX)).mount(&server).await;
+
+        let raw = client.get_raw_transaction(&eip155_hash()).await.unwrap();
+        assert_eq!(raw.signer, EIP155_SIGNER);
+        assert!(!raw.reconstructed);
+    }
+
+    #[tokio::test]
+    async fn test_raw_transaction_reconstructed_without_node_support() {
+        let (server, client) = mock_node().await;
+        rpc_error("eth_getRawTransactionByHash", -32601, "method not found").mount(&server).await;
+        rpc_result("eth_getTransactionByHash", json!({
+            "hash": eip155_hash(),
+            "nonce": "0x9",
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "from": EIP155_SIGNER,
+            "to": "0x3535353535353535353535353535353535353535",
+            "value": "0xde0b6b3a7640000",
+            "gasPrice": "0x4a817c800",
+            "gas": "0x5208",
+            "input": "0x",
+            "v": "0x25",
+            "r": "0x28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+            "s": "0x67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+            "chainId": "0x1",
+            "type": "0x0",
+        })).mount(&server).await;
+
+        let raw = client.get_raw_transaction(&eip155_hash()).await.unwrap();
+        assert_eq!(raw.bytes.to_string(), EIP155_RAW_TX);
+        assert_eq!(raw.signer, EIP155_SIGNER);
+        assert!(raw.reconstructed);
+    }
+
+    #[tokio::test]
+    async fn test_raw_transaction_errors_are_not_reconstructed() {
+        let (server, client) = mock_node().await;
+        let client = client.with_retry(RetryConfig::NONE);
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getRawTransactionByHash" })))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        rpc_result("eth_getTransactionByHash", json!(null)).expect(0).mount(&server).await;
+
+        let err = client.get_raw_transaction(&eip155_hash()).await.unwrap_err();
+        assert!(matches!(err, RethClientError::Connection(_)));
+
+        let (server, client) = mock_node().await;
+        rpc_error("eth_getRawTransactionByHash", INVALID_PARAMS_CODE, "invalid argument 0").mount(&server).await;
+        rpc_result("eth_getTransactionByHash", json!(null)).expect(0).mount(&server).await;
+
+        let err = client.get_raw_transaction(&eip155_hash()).await.unwrap_err();
+        assert!(matches!(err, RethClientError::RpcError { code: INVALID_PARAMS_CODE, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_receipt_served_from_block_receipts() {
+        let (server, client) = mock_node().await;
+        let tx_hash = "0x1111111111111111111111111111111111111111111111111111111111111111";
+        rpc_result("eth_getTransactionByHash", json!({
+            "hash": tx_hash,
+            "blockNumber": "0x10",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": null,
+            "nonce": "0x0",
+            "value": "0x0",
+            "gas": "0x5208",
+            "gasPrice": "0x1",
+            "input": "0x",
+        })).mount(&server).await;
+        rpc_result("eth_getBlockReceipts", json!([
+            { "transactionHash": tx_hash, "gasUsed": "0x5208", "status": "0x1", "logs": [] },
+        ])).mount(&server).await;
+        rpc_result("eth_getTransactionReceipt", serde_json::Value::Null)
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        client.get_block_receipts(16).await.unwrap();
+        let tx = client.get_transaction(tx_hash).await.unwrap();
+        assert_eq!(tx.gas_used, Some(U256::from(21_000)));
+        assert_eq!(tx.status, Some(U256::from(1)));
+    }
+
+    #[tokio::test]
+    async fn test_transactions_from_stay_in_block_order() {
+        let (server, client) = mock_node().await;
+        let sender = "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f";
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber" })))
+            .respond_with(move |request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let number = body["params"][0].as_str().unwrap().to_string();
+                let mut block = test_support::block("0x10");
+                block["number"] = json!(number);
+                block["transactions"] = json!([{
+                    "hash": format!("{:?}", B256::with_last_byte(hex_to_decimal(&number).unwrap() as u8)),
+                    "blockNumber": number,
+                    "from": sender,
+                    "nonce": number,
+                    "value": "0x0",
+                    "gas": "0x5208",
+                    "gasPrice": "0x1",
+                    "input": "0x",
+                }]);
+                // The first block answers last, yet still comes first
+                let delay = if number == "0xe" { 300 } else { 0 };
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": block }))
+                    .set_delay(Duration::from_millis(delay))
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockReceipts" })))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let number = hex_to_decimal(body["params"][0].as_str().unwrap()).unwrap();
+                let receipt = json!({ "transactionHash": format!("{:?}", B256::with_last_byte(number as u8)), "gasUsed": "0x5208", "status": "0x1" });
+                ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": [receipt] }))
+            })
+            .mount(&server)
+            .await;
+
+        let mut tally = ErrorTally::new(ErrorPolicy::FailFast);
+        let sent = client.get_transactions_from(sender, 14, 16, &mut tally).await.unwrap();
+        let nonces: Vec<u64> = sent.iter().map(|tx| tx.nonce.to()).collect();
+        assert_eq!(nonces, [14, 15, 16]);
+        assert!(sent.iter().all(|tx| tx.status == Some(U256::from(1))));
+    }
+
+    #[tokio::test]
+    async fn test_transactions_from_need_block_receipts() {
+        let (server, client) = mock_node().await;
+        let sender = "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f";
+        let mut block = test_support::block("0x10");
+        block["transactions"] = json!([{
+            "hash": format!("{:?}", B256::with_last_byte(1)),
+            "blockNumber": "0x10",
+            "from": sender,
+            "nonce": "0x0",
+            "value": "0x0",
+            "gas": "0x5208",
+            "gasPrice": "0x1",
+            "input": "0x",
+        }]);
+        rpc_result("eth_getBlockByNumber", block).mount(&server).await;
+        rpc_result("eth_getBlockReceipts", json!([])).mount(&server).await;
+        // A receipt missing from the block's is reported, not papered over
+        // with a call per transaction
+        rpc_result("eth_getTransactionReceipt", json!(null)).expect(0).mount(&server).await;
+
+        let mut tally = ErrorTally::new(ErrorPolicy::FailFast);
+        assert!(client.get_transactions_from(sender, 16, 16, &mut tally).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_cache_hits_and_parses() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getBlockByNumber", test_support::block("0x10")).expect(1).mount(&server).await;
+
+        client.get_block_by_number(16).await.unwrap();
+        client.get_block_by_number(16).await.unwrap();
+        let stats = client.stats();
+        assert_eq!((stats.cache_hits, stats.cache_misses), (1, 1));
+        // The repeat was served parsed
+        assert_eq!(stats.parsed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_pending_transaction_without_receipt() {
+        let (_server, client) = mock_node().await;
+        let tx = client.parse_transaction(json!({
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": "0x3535353535353535353535353535353535353535",
+            "nonce": "0x0",
+            "value": "0x0",
+            "gas": "0x5208",
+            "gasPrice": "0x1",
+            "input": "0x",
+        }), serde_json::Value::Null).unwrap();
+        assert_eq!(tx.block_number, None);
+        assert_eq!(tx.gas_used, None);
+        assert_eq!(tx.status, None);
+        assert_eq!(crate::utils::format_tx_status(&tx.status), "⏳ Pending");
+    }
+
+    #[tokio::test]
+    async fn test_access_list_parse() {
+        let (_server, client) = mock_node().await;
+        let tx = client.parse_tx_object(&json!({
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "type": "0x2",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "nonce": "0x7",
+            "value": "0x0",
+            "gas": "0x186a0",
+            "gasPrice": "0x4a817c800",
+            "input": "0x",
+            "accessList": [
+                {
+                    "address": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                    "storageKeys": [
+                        "0x0000000000000000000000000000000000000000000000000000000000000003",
+                        "0x0000000000000000000000000000000000000000000000000000000000000007",
+                    ],
+                },
+                { "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "storageKeys": [] },
+            ],
+        })).unwrap();
+
+        let access_list = tx.access_list.unwrap();
+        assert_eq!(access_list.0.len(), 2);
+        assert_eq!(access_list.0[0].storage_keys[1], B256::with_last_byte(7));
+        assert!(access_list.0[1].storage_keys.is_empty());
+
+        let legacy = client.parse_tx_object(&json!({ "hash": format!("{:?}", B256::ZERO) })).unwrap();
+        assert!(legacy.access_list.is_none());
+        assert!(legacy.blob_versioned_hashes.is_empty());
+
+        let explicit_null = client.parse_tx_object(&json!({
+            "hash": format!("{:?}", B256::ZERO),
+            "accessList": null,
+            "blobVersionedHashes": null,
+        })).unwrap();
+        assert!(explicit_null.access_list.is_none());
+        assert!(explicit_null.blob_versioned_hashes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_block_blob_gas() {
+        let (server, client) = mock_node().await;
+        let mut block = test_support::block("0x10");
+        block["timestamp"] = json!("0x68200000");
+        block["blobGasUsed"] = json!("0xc0000");
+        block["excessBlobGas"] = json!(format!("0x{:x}", 10 * 5_007_716u64));
+        rpc_result("eth_getBlockByNumber", block).mount(&server).await;
+
+        let block = client.get_block_by_number(16).await.unwrap();
+        assert_eq!(block.blob_count(), Some(6));
+        // e^10 under the Prague update fraction
+        assert_eq!(block.blob_base_fee(1), Some(U256::from(22_026)));
+        // Another chain's blob schedule isn't known
+        assert_eq!(block.blob_base_fee(11_155_111), None);
+        assert_eq!(fake_exponential(U256::from(1), U256::ZERO, U256::from(3_338_477)), U256::from(1));
+        let pre_cancun = client.parse_block(test_support::block("0x10")).unwrap();
+        assert_eq!(pre_cancun.blob_base_fee(1), None);
+    }
+
+    #[tokio::test]
+    async fn test_blob_transaction_parse() {
+        let (_server, client) = mock_node().await;
+        let tx = client.parse_tx_object(&json!({
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "type": "0x3",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "nonce": "0x7",
+            "value": "0x0",
+            "gas": "0x5208",
+            "maxFeePerGas": "0x4a817c800",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "maxFeePerBlobGas": "0x3b9aca00",
+            "blobVersionedHashes": [
+                "0x0100000000000000000000000000000000000000000000000000000000000001",
+                "0x0100000000000000000000000000000000000000000000000000000000000002",
+            ],
+            "input": "0x",
+        })).unwrap();
+
+        assert_eq!(tx.tx_type, Some(3));
+        assert_eq!(tx.max_fee_per_blob_gas, Some(U256::from(1_000_000_000u64)));
+        assert_eq!(tx.blob_versioned_hashes.len(), 2);
+    }
+
+    /// Matches `eth_call` requests addressed to `to`
+    fn call_to(to: &str) -> impl Fn(&Request) -> bool {
+        let to = to.to_lowercase();
+        move |request: &Request| {
+            request.body_json::<serde_json::Value>().ok().is_some_and(|body| {
+                body["method"] == "eth_call"
+                    && body["params"][0]["to"].as_str().is_some_and(|t| t.to_lowercase() == to)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_ens_name() {
+        let (server, client) = mock_node().await;
+        let resolver = "0x231b0Ee14048e9dCcD1d247744d114a4EB5E8E63";
+        Mock::given(method("POST"))
+            .and(call_to(&ENS_REGISTRY.to_string()))
+            .respond_with(RpcResponder(json!({
+                "result": format!("0x{:0>64}", resolver.trim_start_matches("0x")),
+            })))
+            .mount(&server)
+            .await;
+        let name = DynSolValue::String("vitalik.eth".to_string()).abi_encode();
+        Mock::given(method("POST"))
+            .and(call_to(resolver))
+            .respond_with(RpcResponder(json!({ "result": Bytes::from(name) })))
+            .mount(&server)
+            .await;
+
+        let address = alloy_primitives::address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert_eq!(client.lookup_ens_name(&address).await.unwrap().as_deref(), Some("vitalik.eth"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ens() {
+        let (server, client) = mock_node().await;
+        let resolver = "0x231b0Ee14048e9dCcD1d247744d114a4EB5E8E63";
+        let vitalik = alloy_primitives::address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let node = namehash("vitalik.eth");
+        Mock::given(method("POST"))
+            .and(call_to(&ENS_REGISTRY.to_string()))
+            .and(body_partial_json(json!({ "params": [{ "data": Bytes::from([&ENS_RESOLVER_SELECTOR[..], node.as_slice()].concat()) }] })))
+            .respond_with(RpcResponder(json!({
+                "result": format!("0x{:0>64}", resolver.trim_start_matches("0x")),
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Any other name has no resolver
+        Mock::given(method("POST"))
+            .and(call_to(&ENS_REGISTRY.to_string()))
+            .respond_with(RpcResponder(json!({ "result": format!("0x{}", "0".repeat(64)) })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(call_to(resolver))
+            .respond_with(RpcResponder(json!({ "result": B256::left_padding_from(vitalik.as_slice()) })))
+            .mount(&server)
+            .await;
+
+        assert_eq!(client.resolve_ens("Vitalik.eth").await.unwrap(), vitalik);
+        // Served from the cache the second time
+        assert_eq!(client.resolve_ens("vitalik.eth").await.unwrap(), vitalik);
+        let err = client.resolve_ens("unregistered.eth").await.unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+    }
+
+    #[test]
+    fn test_sort_logs() {
+        let log = |address: u8, topic: u8, block: u64, index: u64| Log {
+            address: Address::with_last_byte(address),
+            topics: vec![B256::with_last_byte(topic)],
+            data: Bytes::new(),
+            block_number: Some(U256::from(block)),
+            transaction_hash: None,
+            log_index: Some(U256::from(index)),
+        };
+        let mut logs = vec![log(2, 1, 10, 0), log(1, 2, 10, 1), log(2, 2, 11, 0), log(1, 1, 9, 3)];
+        let order = |logs: &[Log]| logs.iter()
+            .map(|l| (l.block_number.unwrap().to::<u64>(), l.log_index.unwrap().to::<u64>()))
+            .collect::<Vec<_>>();
+
+        sort_logs(&mut logs, LogSort::Block, false);
+        assert_eq!(order(&logs), vec![(9, 3), (10, 0), (10, 1), (11, 0)]);
+
+        sort_logs(&mut logs, LogSort::Address, false);
+        assert_eq!(order(&logs), vec![(9, 3), (10, 1), (10, 0), (11, 0)]);
+
+        sort_logs(&mut logs, LogSort::Event, true);
+        assert_eq!(order(&logs), vec![(11, 0), (10, 1), (10, 0), (9, 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_trace_block_call_frames() {
+        let (server, client) = mock_node().await;
+        rpc_result("debug_traceBlockByNumber", json!([{
+            "txHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "result": {
+                "type": "CALL",
+                "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+                "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "value": "0x0",
+                "calls": [{
+                    "type": "CALL",
+                    "from": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                    "to": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+                    "value": "0xde0b6b3a7640000",
+                    "calls": [{
+                        "type": "STATICCALL",
+                        "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+                        "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                        "error": "execution reverted",
+                    }],
+                }],
+            },
+        }])).mount(&server).await;
+
+        let traces = client.trace_block("16", "callTracer").await.unwrap();
+        let root: CallFrame = serde_json::from_value(traces[0].result.clone()).unwrap();
+        let frames = root.flatten();
+        assert_eq!(frames.iter().map(|(depth, _)| *depth).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(frames[1].1.value, Some(U256::from(10).pow(U256::from(18))));
+        assert_eq!(frames[2].1.error.as_deref(), Some("execution reverted"));
+    }
+
+    #[tokio::test]
+    async fn test_trace_block_reports_missing_debug_namespace() {
+        let (server, client) = mock_node().await;
+        rpc_error("debug_traceBlockByHash", METHOD_NOT_FOUND_CODE, "the method does not exist").mount(&server).await;
+
+        let err = client.trace_block(&format!("{:?}", B256::ZERO), "callTracer").await.unwrap_err();
+        assert!(err.to_string().contains("debug namespace"));
+    }
+
+    #[tokio::test]
+    async fn test_get_txpool_content() {
+        let (server, client) = mock_node().await;
+        let sender = "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f";
+        rpc_result("txpool_content", json!({
+            "pending": {
+                sender: {
+                    "7": { "hash": format!("{:?}", B256::with_last_byte(1)), "from": sender, "nonce": "0x7", "gasPrice": "0x3b9aca00" },
+                    "8": {
+                        "hash": format!("{:?}", B256::with_last_byte(2)), "from": sender, "nonce": "0x8",
+                        "gasPrice": "0x1", "maxFeePerGas": "0x77359400", "maxPriorityFeePerGas": "0x1",
+                    },
+                },
+            },
+            "queued": {},
+        })).mount(&server).await;
+
+        let content = client.get_txpool_content().await.unwrap();
+        assert!(content.queued.is_empty());
+        let mut pending = content.pending;
+        pending.sort_by_key(|tx| tx.nonce);
+        assert_eq!(pending[0].gas_price, U256::from(1_000_000_000u64));
+        // EIP-1559 transactions bid their fee cap
+        assert_eq!(pending[1].gas_price, U256::from(2_000_000_000u64));
+        assert_eq!(pending[1].from, sender.parse::<Address>().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_txpool_reports_missing_namespace() {
+        let (server, client) = mock_node().await;
+        rpc_error("txpool_status", METHOD_NOT_FOUND_CODE, "the method does not exist").mount(&server).await;
+
+        let err = client.get_txpool_status().await.unwrap_err();
+        assert!(err.to_string().contains("txpool namespace"));
+        assert_eq!(err.exit_code(), 5);
+    }
+
+    #[test]
+    fn test_response_too_large() {
+        use jsonrpsee::http_client::transport::Error as HttpError;
+        assert!(is_response_too_large(&jsonrpsee::core::Error::Transport(HttpError::RequestTooLarge.into())));
+        assert!(!is_response_too_large(&jsonrpsee::core::Error::Transport(HttpError::RequestFailure { status_code: 500 }.into())));
+        assert!(!is_response_too_large(&jsonrpsee::core::Error::RequestTimeout));
+    }
+
+    #[tokio::test]
+    async fn test_trace_transaction() {
+        let (server, client) = mock_node().await;
+        rpc_result("debug_traceTransaction", json!({
+            "type": "CALL",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "gas": "0x5208",
+            "gasUsed": "0x5000",
+            "error": "execution reverted",
+            "revertReason": "nope",
+        })).mount(&server).await;
+
+        let trace = client.trace_transaction(B256::with_last_byte(1), "callTracer").await.unwrap();
+        let root: CallFrame = serde_json::from_value(trace).unwrap();
+        assert_eq!(root.gas, Some(U256::from(21_000)));
+        assert_eq!(root.gas_used, Some(U256::from(0x5000)));
+        assert_eq!(root.revert_reason.as_deref(), Some("nope"));
+        assert!(root.calls.is_empty());
+
+        // Structured output prints amounts in decimal, like every other view
+        let output = serde_json::to_value(&root).unwrap();
+        assert_eq!(output["gas"], "21000");
+        assert_eq!(output["gasUsed"], "20480");
+        assert!(output["value"].is_null());
+        let round_trip: CallFrame = serde_json::from_value(output).unwrap();
+        assert_eq!(round_trip.gas, root.gas);
+    }
+
+    #[tokio::test]
+    async fn test_pruned_state_requires_archive_node() {
+        let (server, client) = mock_node().await;
+        rpc_error("eth_getBalance", -32000, "missing trie node").mount(&server).await;
+
+        let err = client.check_historical_state(15).await.unwrap_err();
+        assert!(err.to_string().contains("archive node"));
+    }
+
+    #[tokio::test]
+    async fn test_nonce_at_tag() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getTransactionCount" })))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let nonce = if body["params"][1] == "pending" { "0x2c" } else { "0x2a" };
+                ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": nonce }))
+            })
+            .mount(&server)
+            .await;
+
+        let address = Address::with_last_byte(1);
+        assert_eq!(client.get_nonce_at_tag(&address, BlockTag::Latest).await.unwrap(), 42);
+        assert_eq!(client.get_nonce_at_tag(&address, BlockTag::Pending).await.unwrap(), 44);
+    }
+
+    #[tokio::test]
+    async fn test_first_active_block_binary_search() {
+        let (server, client) = mock_node().await;
+        // The account sends its first transaction in block 1234
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getTransactionCount" })))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let block = u64::from_str_radix(body["params"][1].as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+                let nonce = if block >= 1234 { "0x1" } else { "0x0" };
+                ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": nonce }))
+            })
+            .mount(&server)
+            .await;
+        rpc_result("eth_getCode", json!("0x")).mount(&server).await;
+
+        let address = Address::with_last_byte(1);
+        assert_eq!(client.first_active_block(&address, 20_000).await.unwrap(), Some(1234));
+        assert_eq!(client.first_active_block(&address, 1000).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_code_at_block() {
+        let (server, client) = mock_node().await;
+        let address = Address::with_last_byte(1);
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getCode", "params": [address, "0x64"] })))
+            .respond_with(RpcResponder(json!({ "result": "0x6080604052" })))
+            .mount(&server)
+            .await;
+        rpc_result("eth_getCode", json!("0x")).mount(&server).await;
+
+        assert_eq!(client.get_code(&address, Some(100)).await.unwrap().as_ref(), [0x60, 0x80, 0x60, 0x40, 0x52]);
+        assert!(client.get_code(&address, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tx_gas_samples_from_receipts() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getBlockReceipts", json!([
+            { "transactionHash": format!("{:?}", B256::with_last_byte(1)), "gasUsed": "0x5208", "effectiveGasPrice": "0x3b9aca00" },
+            { "transactionHash": format!("{:?}", B256::with_last_byte(2)), "gasUsed": "0x186a0", "effectiveGasPrice": "0x77359400" },
+        ])).mount(&server).await;
+
+        let mut tally = ErrorTally::new(ErrorPolicy::FailFast);
+        let (blocks, samples) = client.get_tx_gas_samples(16, 16, BLOCK_FETCH_CONCURRENCY, &mut tally).await.unwrap();
+        assert_eq!(blocks, 1);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].block_number, 16);
+        assert_eq!(samples[1].gas_used, 100_000);
+        assert_eq!(samples[1].gas_price, U256::from(2_000_000_000u64));
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_fallback() {
+        let (server, client) = mock_node().await;
+        rpc_error("eth_getBlockReceipts", -32601, "the method eth_getBlockReceipts does not exist").expect(1).mount(&server).await;
+        let mut block = test_support::block("0x10");
+        block["transactions"] = json!([format!("{:?}", B256::with_last_byte(1)), format!("{:?}", B256::with_last_byte(2))]);
+        rpc_result("eth_getBlockByNumber", block).mount(&server).await;
+        for (byte, gas_used) in [(1u8, "0x5208"), (2, "0x186a0")] {
+            let hash = format!("{:?}", B256::with_last_byte(byte));
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({ "method": "eth_getTransactionReceipt", "params": [hash] })))
+                .respond_with(RpcResponder(json!({ "result": {
+                    "transactionHash": hash,
+                    "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+                    "to": null,
+                    "gasUsed": gas_used,
+                    "cumulativeGasUsed": gas_used,
+                    "effectiveGasPrice": "0x3b9aca00",
+                    "logs": [],
+                } })))
+                .mount(&server)
+                .await;
+        }
+
+        let receipts = client.get_receipts(16).await.unwrap();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[1].gas_used, U256::from(100_000));
+        // Later blocks skip straight to the fallback
+        assert_eq!(client.get_receipts(17).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_errors_do_not_fall_back() {
+        for (code, message) in [(-32603, "internal error"), (-32000, "header not found")] {
+            let (server, client) = mock_node().await;
+            let client = client.with_retry(RetryConfig::NONE);
+            rpc_error("eth_getBlockReceipts", code, message).mount(&server).await;
+            rpc_result("eth_getBlockByNumber", test_support::block("0x10")).expect(0).mount(&server).await;
+
+            let err = client.get_receipts(16).await.unwrap_err();
+            assert!(matches!(err, RethClientError::RpcError { code: c, .. } if c == code));
+            // Still tried first for the next block
+            assert!(client.get_receipts(17).await.is_err());
+            let requests = server.received_requests().await.unwrap();
+            let tried = requests.iter().filter(|r| r.body_json::<serde_json::Value>().unwrap()["method"] == "eth_getBlockReceipts").count();
+            assert_eq!(tried, 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contract_creation_by_bisection() {
+        let (server, client) = mock_node().await;
+        let contract = Address::with_last_byte(0xc0);
+        let deployer = Address::with_last_byte(0xd0);
+        rpc_error("ots_getContractCreator", METHOD_NOT_FOUND_CODE, "the method does not exist").mount(&server).await;
+        rpc_result("eth_blockNumber", json!("0x100")).mount(&server).await;
+        // Deployed in block 0x42
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getCode" })))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let block = u64::from_str_radix(body["params"][1].as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+                let code = if block >= 0x42 { "0x6080" } else { "0x" };
+                ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": code }))
+            })
+            .expect(1..=10)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockReceipts", "params": ["0x42"] })))
+            .respond_with(RpcResponder(json!({ "result": [
+                { "transactionHash": format!("{:?}", B256::with_last_byte(1)), "from": deployer, "to": Address::with_last_byte(2), "gasUsed": "0x5208", "cumulativeGasUsed": "0x5208", "effectiveGasPrice": "0x1", "logs": [] },
+                { "transactionHash": format!("{:?}", B256::with_last_byte(2)), "from": deployer, "to": null, "contractAddress": contract, "gasUsed": "0x30d40", "cumulativeGasUsed": "0x35f48", "effectiveGasPrice": "0x1", "logs": [] },
+            ] })))
+            .mount(&server)
+            .await;
+
+        let creation = client.get_contract_creation(contract).await.unwrap();
+        assert_eq!(creation.block_number, 0x42);
+        assert_eq!(creation.transaction_hash, B256::with_last_byte(2));
+        assert_eq!(creation.deployer, deployer);
+        assert_eq!(creation.factory, None);
+        // Cached: the code lookups aren't repeated
+        client.get_contract_creation(contract).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_contract_creation_otterscan_error_is_returned() {
+        let (server, client) = mock_node().await;
+        rpc_error("ots_getContractCreator", -32000, "database unavailable").mount(&server).await;
+        rpc_result("eth_blockNumber", json!("0x100")).expect(0).mount(&server).await;
+
+        let err = client.get_contract_creation(Address::with_last_byte(0xc0)).await.unwrap_err();
+        assert!(matches!(err, RethClientError::RpcError { code: -32000, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_contract_creation_from_otterscan() {
+        let (server, client) = mock_node().await;
+        let tx_hash = B256::with_last_byte(9);
+        let factory = Address::with_last_byte(0xfa);
+        rpc_result("ots_getContractCreator", json!({ "hash": tx_hash, "creator": factory })).mount(&server).await;
+        rpc_result("eth_getTransactionByHash", json!({
+            "hash": tx_hash,
+            "blockNumber": "0x10",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": factory,
+            "value": "0x0",
+            "gas": "0x5208",
+            "gasPrice": "0x3b9aca00",
+            "input": "0x",
+            "nonce": "0x0",
+        })).mount(&server).await;
+        rpc_result("eth_getTransactionReceipt", json!({
+            "transactionHash": tx_hash, "gasUsed": "0x5208", "effectiveGasPrice": "0x3b9aca00", "status": "0x1", "logs": [],
+        })).mount(&server).await;
+
+        let creation = client.get_contract_creation(Address::with_last_byte(0xc0)).await.unwrap();
+        assert_eq!(creation.block_number, 0x10);
+        assert_eq!(creation.factory, Some(factory));
+        assert_eq!(creation.deployer, "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f".parse::<Address>().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_gas_statistics_weights_price_by_gas_used() {
+        let (server, client) = mock_node().await;
+        let mut block = test_support::block("0x10");
+        block["transactions"] = json!([
+            { "hash": format!("{:?}", B256::with_last_byte(1)), "gasPrice": "0x3b9aca00" },
+            // EIP-1559 transaction reported without gasPrice, with a 4 gwei fee cap
+            { "hash": format!("{:?}", B256::with_last_byte(2)), "maxFeePerGas": "0xee6b2800" },
+        ]);
+        rpc_result("eth_getBlockByNumber", block).mount(&server).await;
+        rpc_result("eth_getBlockReceipts", json!([
+            { "transactionHash": format!("{:?}", B256::with_last_byte(1)), "gasUsed": "0x5208", "effectiveGasPrice": "0x3b9aca00" },
+            { "transactionHash": format!("{:?}", B256::with_last_byte(2)), "gasUsed": "0xf618", "effectiveGasPrice": "0xb2d05e00" },
+        ])).mount(&server).await;
+
+        let mut tally = ErrorTally::new(ErrorPolicy::FailFast);
+        let stats = client.get_gas_statistics(16, 16, BLOCK_FETCH_CONCURRENCY, &mut tally).await.unwrap();
+        // 21,000 gas at 1 gwei and 63,000 gas at the 3 gwei actually paid
+        assert_eq!(stats.avg_gas_price, 2_500_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_get_receipt_of_contract_creation() {
+        let (server, client) = mock_node().await;
+        let tx_hash = format!("{:?}", B256::with_last_byte(7));
+        rpc_result("eth_getTransactionReceipt", json!({
+            "transactionHash": tx_hash,
+            "blockNumber": "0x10",
+            "blockHash": format!("{:?}", B256::with_last_byte(8)),
+            "transactionIndex": "0x2",
+            "from": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "to": null,
+            "status": "0x1",
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x1f4a0",
+            "effectiveGasPrice": "0x3b9aca00",
+            "contractAddress": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "logs": [{
+                "address": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                "topics": [format!("{:?}", B256::with_last_byte(9))],
+                "data": "0x",
+                "logIndex": "0x0",
+            }],
+        })).mount(&server).await;
+
+        let receipt = client.get_receipt(&tx_hash).await.unwrap();
+        assert_eq!(receipt.to, None);
+        assert_eq!(receipt.contract_address, Some(alloy_primitives::address!("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")));
+        assert_eq!(receipt.cumulative_gas_used, U256::from(128_160));
+        assert_eq!(receipt.effective_gas_price, U256::from(1_000_000_000));
+        assert_eq!(receipt.transaction_index, Some(U256::from(2)));
+        assert_eq!(receipt.logs_bloom, Some(Bloom::ZERO));
+        assert_eq!(receipt.logs.len(), 1);
+
+        let mut without_sender = json!({ "transactionHash": tx_hash, "gasUsed": "0x5208" });
+        let err = client.parse_receipt(&without_sender).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+        without_sender["from"] = json!("0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f");
+        assert!(client.parse_receipt(&without_sender).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_receipt_not_found() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getTransactionReceipt", json!(null)).mount(&server).await;
+        let err = client.get_receipt(&format!("{:?}", B256::ZERO)).await.unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+    }
+
+    /// Matches JSON-RPC batch requests, whose body is an array
+    fn is_batch(request: &Request) -> bool {
+        request.body.first() == Some(&b'[')
+    }
+
+    #[tokio::test]
+    async fn test_find_block_at_timestamp() {
+        let (server, client) = mock_node().await;
+        // Twelve-second blocks from genesis at 1,000, with a one-hour gap after block 500
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber" })))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let number = hex_to_decimal(body["params"][0].as_str().unwrap()).unwrap();
+                let timestamp = 1_000 + 12 * number + if number > 500 { 3_600 } else { 0 };
+                let mut block = test_support::block("0x10");
+                block["number"] = body["params"][0].clone();
+                block["timestamp"] = json!(format!("{:#x}", timestamp));
+                ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": block }))
+            })
+            .mount(&server)
+            .await;
+
+        assert_eq!(client.find_block_at_timestamp(1_000 + 12 * 300, 1_000).await.unwrap(), Some(300));
+        // Between two blocks, the later one
+        assert_eq!(client.find_block_at_timestamp(1_000 + 12 * 300 + 5, 1_000).await.unwrap(), Some(301));
+        // Inside the gap, the first block after it
+        assert_eq!(client.find_block_at_timestamp(8_000, 1_000).await.unwrap(), Some(501));
+        // Before genesis, genesis; after the head, nothing
+        assert_eq!(client.find_block_at_timestamp(0, 1_000).await.unwrap(), Some(0));
+        assert_eq!(client.find_block_at_timestamp(u64::MAX, 1_000).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_blocks_batches_calls() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(is_batch)
+            .respond_with(|request: &Request| {
+                let calls: Vec<serde_json::Value> = request.body_json().unwrap();
+                let responses: Vec<_> = calls.iter()
+                    .map(|call| {
+                        let number = call["params"][0].clone();
+                        // Block 3 isn't known to the node
+                        let result = if number == json!("0x3") {
+                            json!(null)
+                        } else {
+                            let mut block = test_support::block("0x10");
+                            block["number"] = number;
+                            block
+                        };
+                        json!({ "jsonrpc": "2.0", "id": call["id"], "result": result })
+                    })
+                    .collect();
+                ResponseTemplate::new(200).set_body_json(responses)
+            })
+            .expect(1)
+            .mount(&server)
+            .await;
+        rpc_result("eth_getBlockByNumber", test_support::block("0x10")).expect(0).mount(&server).await;
+
+        let blocks = client.get_blocks(&[4, 3, 2], false).await;
+        assert_eq!(blocks.iter().map(|(n, _)| *n).collect::<Vec<_>>(), vec![4, 3, 2]);
+        assert_eq!(blocks[0].1.as_ref().unwrap().number, U256::from(4));
+        assert_eq!(blocks[2].1.as_ref().unwrap().number, U256::from(2));
+        let missing = blocks.into_iter().nth(1).unwrap().1.unwrap_err();
+        assert_eq!(missing.exit_code(), 3);
+
+        // Blocks fetched by the batch are cached like any other
+        assert_eq!(client.get_block_header_by_number(4).await.unwrap().number, U256::from(4));
+    }
+
+    #[tokio::test]
+    async fn test_get_blocks_retries_failed_batch_entries() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(is_batch)
+            .respond_with(|request: &Request| {
+                let calls: Vec<serde_json::Value> = request.body_json().unwrap();
+                let responses: Vec<_> = calls.iter()
+                    .map(|call| {
+                        let number = call["params"][0].clone();
+                        // Block 3 hit a node behind a load balancer that was behind
+                        if number == json!("0x3") {
+                            return json!({ "jsonrpc": "2.0", "id": call["id"], "error": { "code": -32000, "message": "header not found" } });
+                        }
+                        let mut block = test_support::block("0x10");
+                        block["number"] = number;
+                        json!({ "jsonrpc": "2.0", "id": call["id"], "result": block })
+                    })
+                    .collect();
+                ResponseTemplate::new(200).set_body_json(responses)
+            })
+            .expect(2)
+            .mount(&server)
+            .await;
+        let mut block = test_support::block("0x10");
+        block["number"] = json!("0x3");
+        rpc_result("eth_getBlockByNumber", block).expect(1).mount(&server).await;
+
+        let blocks = client.get_blocks(&[4, 3, 2], false).await;
+        assert!(blocks.iter().all(|(_, block)| block.is_ok()));
+        assert_eq!(blocks[1].1.as_ref().unwrap().number, U256::from(3));
+        // Batching stays on
+        assert!(client.get_blocks(&[7, 8], false).await.iter().all(|(_, block)| block.is_ok()));
+    }
+
+    #[tokio::test]
+    async fn test_get_blocks_keeps_batching_after_a_timeout() {
+        let (server, _) = mock_node().await;
+        let client = RethClient::new(&[server.uri()], &CacheConfig::default(), Duration::from_millis(200), false)
+            .await
+            .unwrap()
+            .with_retry(RetryConfig::NONE);
+        Mock::given(method("POST"))
+            .and(is_batch)
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])).set_delay(Duration::from_secs(2)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        rpc_result("eth_getBlockByNumber", test_support::block("0x10")).mount(&server).await;
+
+        // The timed-out batch falls back to single calls for this range only
+        assert!(client.get_blocks(&[1, 2], false).await.iter().all(|(_, block)| block.is_ok()));
+        assert!(!client.batch_unsupported.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_get_blocks_falls_back_without_batches() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(is_batch)
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&server)
+            .await;
+        rpc_result("eth_getBlockByNumber", test_support::block("0x10")).mount(&server).await;
+
+        for numbers in [[1, 2], [5, 6]] {
+            let blocks = client.get_blocks(&numbers, true).await;
+            assert!(blocks.iter().all(|(_, block)| block.is_ok()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gas_statistics_min_ignores_skipped_blocks() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["0xf", true] })))
+            .respond_with(RpcResponder(json!({ "result": null })))
+            .mount(&server)
+            .await;
+        rpc_result("eth_getBlockByNumber", test_support::block("0x10")).mount(&server).await;
+
+        let mut tally = ErrorTally::new(ErrorPolicy::Continue);
+        // Two blocks, of which 15 is missing
+        let stats = client.get_gas_statistics(15, 16, BLOCK_FETCH_CONCURRENCY, &mut tally).await.unwrap();
+        assert_eq!(stats.blocks_analyzed, 1);
+        assert_eq!(stats.min_gas_used, 15_000_000);
+        assert_eq!(stats.max_gas_used, 15_000_000);
+        assert!(tally.finish().is_err());
+    }
+
+    #[test]
+    fn test_cap_gas_range() {
+        assert_eq!(cap_gas_range(100, 200).unwrap(), 100);
+        assert_eq!(cap_gas_range(7, 7).unwrap(), 7);
+        // Huge spans keep their most recent blocks
+        assert_eq!(cap_gas_range(0, 1_000_000).unwrap(), 1_000_000 - MAX_SCAN_BLOCKS + 1);
+        assert!(cap_gas_range(200, 100).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gas_scan_fail_fast_stops_early() {
+        let (server, client) = mock_node().await;
+        // Block 0 is missing; every other block's receipts take a while
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockReceipts", "params": ["0x0"] })))
+            .respond_with(RpcResponder(json!({ "result": null })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockReceipts" })))
+            .respond_with(move |request: &Request| RpcResponder(json!({ "result": [] })).respond(request).set_delay(Duration::from_millis(300)))
+            .mount(&server)
+            .await;
+
+        let client = client.with_retry(RetryConfig::NONE);
+        let mut tally = ErrorTally::new(ErrorPolicy::FailFast);
+        assert!(client.get_tx_gas_samples(0, 99, 4, &mut tally).await.is_err());
+        // The failure ends the scan instead of waiting for the other 99 blocks
+        let requests = server.received_requests().await.unwrap();
+        let fetched = requests.iter().filter(|request| request.body_json::<serde_json::Value>().unwrap()["method"] == "eth_getBlockReceipts").count();
+        assert!(fetched <= 4, "{} blocks fetched", fetched);
+
+        // Continue still sees every block, at most `concurrency` at a time
+        let mut tally = ErrorTally::new(ErrorPolicy::Continue);
+        let started = std::time::Instant::now();
+        let (blocks, _) = client.get_tx_gas_samples(1, 8, 4, &mut tally).await.unwrap();
+        assert_eq!(blocks, 8);
+        assert!(started.elapsed() >= Duration::from_millis(600));
+    }
+
+    #[tokio::test]
+    async fn test_gas_statistics_with_no_blocks_analyzed() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getBlockByNumber", json!(null)).mount(&server).await;
+
+        let mut tally = ErrorTally::new(ErrorPolicy::Continue);
+        // Every block skipped: an error rather than a report with min u64::MAX
+        assert!(client.get_gas_statistics(15, 16, BLOCK_FETCH_CONCURRENCY, &mut tally).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fee_history() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_feeHistory", "params": ["0x3", "latest", [25.0, 50.0, 75.0]] })))
+            .respond_with(RpcResponder(json!({ "result": {
+                "oldestBlock": "0x10",
+                "baseFeePerGas": ["0x3b9aca00", "0x4190ab00", "0x3b9aca00", "0x37e11d60"],
+                "gasUsedRatio": [0.9, 0.2, 0.4],
+                "reward": [
+                    ["0x1", "0x3b9aca00", "0x77359400"],
+                    ["0x1", "0x77359400", "0xb2d05e00"],
+                    ["0x1", "0x5f5e100", "0x3b9aca00"],
+                ],
+            }})))
+            .mount(&server)
+            .await;
+
+        let history = client.get_fee_history(3, &[25.0, 50.0, 75.0]).await.unwrap();
+        assert_eq!(history.blocks.len(), 3);
+        assert_eq!(history.blocks[2].number, 0x12);
+        assert_eq!(history.blocks[1].base_fee, U256::from(1_100_000_000));
+        assert_eq!(history.next_base_fee, U256::from(937_500_000));
+        // Median of the 50th percentile tips: 0.1, 1 and 2 gwei
+        let (max_fee, tip) = history.suggested_fees().unwrap();
+        assert_eq!(tip, U256::from(1_000_000_000));
+        assert_eq!(max_fee, U256::from(2 * 937_500_000 + 1_000_000_000u64));
+
+        assert!(client.get_fee_history(3, &[75.0, 25.0]).await.is_err());
+        assert!(client.get_fee_history(0, &[50.0]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pending_block_by_tag() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_blockNumber", json!("0x10")).mount(&server).await;
+        let mut pending = test_support::block("0x10");
+        pending["number"] = json!(null);
+        pending["hash"] = json!(null);
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getBlockByNumber", "params": ["pending", true] })))
+            .respond_with(RpcResponder(json!({ "result": pending })))
+            .mount(&server)
+            .await;
+
+        let block = client.get_block_by_tag(BlockTag::Pending, true).await.unwrap();
+        assert_eq!(block.number, U256::from(17));
+        assert_eq!(block.hash, None);
+        assert_eq!(BlockTag::parse("finalized"), Some(BlockTag::Finalized));
+        assert_eq!(BlockTag::parse("head"), None);
+    }
+
+    #[test]
+    fn test_decode_token_symbol() {
+        assert_eq!(decode_token_symbol(&DynSolValue::String("USDC".into()).abi_encode()).as_deref(), Some("USDC"));
+        let mut mkr = [0u8; 32];
+        mkr[..3].copy_from_slice(b"MKR");
+        assert_eq!(decode_token_symbol(&mkr).as_deref(), Some("MKR"));
+        assert_eq!(decode_token_symbol(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_token_balance_and_metadata() {
+        let (server, client) = mock_node().await;
+        let token: Address = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".parse().unwrap();
+        Mock::given(method("POST"))
+            .and(call_to(&token.to_string()))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let data = body["params"][0]["data"].as_str().unwrap();
+                let result = match &data[..10] {
+                    "0x70a08231" => Bytes::from(U256::from(1_500_000u64).to_be_bytes_vec()),
+                    "0x313ce567" => Bytes::from(U256::from(6).to_be_bytes_vec()),
+                    _ => Bytes::from(DynSolValue::String("USDC".into()).abi_encode()),
+                };
+                ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": result }))
+            })
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let holder = Address::with_last_byte(7);
+        assert_eq!(client.get_token_balance(token, holder, Some(100)).await.unwrap(), U256::from(1_500_000u64));
+        let metadata = client.get_token_metadata(token).await.unwrap();
+        assert_eq!(metadata.decimals, Some(6));
+        assert_eq!(metadata.symbol.as_deref(), Some("USDC"));
+        // Served from the cache: the mock expects exactly four calls
+        client.get_token_metadata(token).await.unwrap();
+        client.get_token_balance(token, holder, None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_token_metadata_failures_not_cached() {
+        let (server, client) = mock_node().await;
+        let token = Address::with_last_byte(0xaa);
+        // The first two calls fail in transit, later ones answer
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = calls.clone();
+        Mock::given(method("POST"))
+            .and(call_to(&token.to_string()))
+            .respond_with(move |request: &Request| {
+                if seen.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return ResponseTemplate::new(503);
+                }
+                RpcResponder(json!({ "result": Bytes::from(U256::from(18).to_be_bytes_vec()) })).respond(request)
+            })
+            .mount(&server)
+            .await;
+
+        let client = client.with_retry(RetryConfig::NONE);
+        assert_eq!(client.get_token_metadata(token).await.unwrap().decimals, None);
+        assert_eq!(client.get_token_metadata(token).await.unwrap().decimals, Some(18));
+        // Now an answer, and cached
+        client.get_token_metadata(token).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_token_balances_via_multicall() {
+        let (server, client) = mock_node().await;
+        // Every holder's balance is its last address byte; holder 0 reverts
+        Mock::given(method("POST"))
+            .and(call_to(&MULTICALL3.to_string()))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let data: Bytes = body["params"][0]["data"].as_str().unwrap().parse().unwrap();
+                let calls_type = DynSolType::Array(Box::new(DynSolType::Tuple(vec![DynSolType::Address, DynSolType::Bool, DynSolType::Bytes])));
+                let calls = calls_type.abi_decode(&data[4..]).unwrap();
+                let results = calls.as_array().unwrap().iter()
+                    .map(|call| {
+                        let holder_byte = call.as_tuple().unwrap()[2].as_bytes().unwrap()[35];
+                        let success = holder_byte != 0;
+                        DynSolValue::Tuple(vec![
+                            DynSolValue::Bool(success),
+                            DynSolValue::Bytes(if success { U256::from(holder_byte).to_be_bytes_vec() } else { Vec::new() }),
+                        ])
+                    })
+                    .collect();
+                let result = Bytes::from(DynSolValue::Array(results).abi_encode());
+                ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": result }))
+            })
+            .mount(&server)
+            .await;
+
+        rpc_result("eth_getCode", json!("0x6080")).mount(&server).await;
+
+        let holders = [Address::with_last_byte(5), Address::ZERO, Address::with_last_byte(9)];
+        let balances = client.get_token_balances(Address::with_last_byte(0xaa), &holders).await.unwrap();
+        assert_eq!(balances, vec![Some(U256::from(5)), None, Some(U256::from(9))]);
+    }
+
+    #[tokio::test]
+    async fn test_token_balances_without_multicall() {
+        let (server, client) = mock_node().await;
+        rpc_result("eth_getCode", json!("0x")).mount(&server).await;
+        Mock::given(method("POST"))
+            .and(call_to(&MULTICALL3.to_string()))
+            .respond_with(RpcResponder(json!({ "result": "0x" })))
+            .expect(0)
+            .mount(&server)
+            .await;
+        // Each holder's balance is its last address byte; holder 0 reverts
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_call" })))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let data: Bytes = body["params"][0]["data"].as_str().unwrap().parse().unwrap();
+                let holder_byte = data[35];
+                let reply = if holder_byte == 0 {
+                    json!({ "error": { "code": 3, "message": "execution reverted" } })
+                } else {
+                    json!({ "result": Bytes::from(U256::from(holder_byte).to_be_bytes_vec()) })
+                };
+                let mut response = json!({ "jsonrpc": "2.0", "id": body["id"] });
+                response.as_object_mut().unwrap().extend(reply.as_object().unwrap().clone());
+                ResponseTemplate::new(200).set_body_json(response)
+            })
+            .mount(&server)
+            .await;
+
+        let holders = [Address::with_last_byte(5), Address::ZERO, Address::with_last_byte(9)];
+        let client = client.with_retry(RetryConfig::NONE);
+        let balances = client.get_token_balances(Address::with_last_byte(0xaa), &holders).await.unwrap();
+        assert_eq!(balances, vec![Some(U256::from(5)), None, Some(U256::from(9))]);
+    }
+
+    #[test]
+    fn test_log_filter_bloom_matches() {
+        let token = Address::with_last_byte(0xaa);
+        let topic = B256::with_last_byte(1);
+        let mut bloom = Bloom::ZERO;
+        bloom.accrue(alloy_primitives::BloomInput::Raw(token.as_slice()));
+        bloom.accrue(alloy_primitives::BloomInput::Raw(topic.as_slice()));
+
+        let filter = LogFilter { address: Some(token), topics: vec![Some(topic), None] };
+        assert!(filter.bloom_matches(&bloom));
+        let other = LogFilter { address: Some(token), topics: vec![Some(B256::with_last_byte(2))] };
+        assert!(!other.bloom_matches(&bloom));
+        assert!(LogFilter { address: None, topics: Vec::new() }.bloom_matches(&Bloom::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_raw_request_passes_params_through() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_getStorageAt", "params": ["0xaa", "0x0", "latest"] })))
+            .respond_with(RpcResponder(json!({ "result": "0x01" })))
+            .mount(&server)
+            .await;
+
+        let params = vec![json!("0xaa"), json!("0x0"), json!("latest")];
+        assert_eq!(client.raw_request("eth_getStorageAt", params).await.unwrap(), json!("0x01"));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_transaction_captures_revert_data() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "eth_call" })))
+            .respond_with(RpcResponder(json!({
+                "error": { "code": 3, "message": "execution reverted", "data": "0xdeadbeef" }
+            })))
+            .mount(&server)
+            .await;
+
+        let tx = Transaction {
+            hash: B256::ZERO,
+            block_number: Some(U256::from(16)),
+            transaction_index: Some(U256::ZERO),
+            from: Address::ZERO,
+            to: Some(Address::ZERO),
+            nonce: U256::ZERO,
+            value: U256::ZERO,
+            gas: U256::from(21_000),
+            gas_price: U256::ZERO,
+            tx_type: Some(0),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: Vec::new(),
+            gas_used: None,
+            status: None,
+            input: Bytes::new(),
+            access_list: None,
+            logs: Vec::new(),
+        };
+        match client.simulate_transaction(&tx, 15).await.unwrap() {
+            CallOutcome::Revert(data) => assert_eq!(data.to_string(), "0xdeadbeef"),
+            CallOutcome::Success(_) => panic!("expected a revert"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_eth_call_sends_optional_fields() {
+        let (server, client) = mock_node().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "eth_call",
+                "params": [{ "from": format!("{:?}", Address::with_last_byte(2)), "value": "0x2a", "data": "0x12345678" }, "0x10"],
+            })))
+            .respond_with(RpcResponder(json!({ "result": format!("0x{:064x}", 1) })))
+            .mount(&server)
+            .await;
+
+        let request = CallRequest {
+            to: Address::with_last_byte(1),
+            from: Some(Address::with_last_byte(2)),
+            value: Some(U256::from(42)),
+            data: Bytes::from(vec![0x12, 0x34, 0x56, 0x78]),
+        };
+        match client.eth_call(&request, Some(16)).await.unwrap() {
+            CallOutcome::Success(output) => assert_eq!(U256::from_be_slice(&output), U256::from(1)),
+            CallOutcome::Revert(_) => panic!("expected success"),
+        }
     }
 }
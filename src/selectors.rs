@@ -0,0 +1,180 @@
+use alloy_dyn_abi::JsonAbiExt;
+use alloy_json_abi::Function;
+use alloy_primitives::{hex, keccak256, Selector};
+use color_eyre::{eyre::eyre, Result};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::abi::Decoded;
+
+/// Functions recognised without an ABI: token standards, WETH, the Uniswap
+/// routers and Multicall3
+const COMMON_SIGNATURES: &[&str] = &[
+    "transfer(address,uint256)",
+    "approve(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "increaseAllowance(address,uint256)",
+    "decreaseAllowance(address,uint256)",
+    "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+    "mint(address,uint256)",
+    "burn(uint256)",
+    "deposit()",
+    "withdraw(uint256)",
+    "safeTransferFrom(address,address,uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "safeTransferFrom(address,address,uint256,uint256,bytes)",
+    "setApprovalForAll(address,bool)",
+    "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactTokens(uint256,uint256,address[],address,uint256)",
+    "swapExactETHForTokens(uint256,address[],address,uint256)",
+    "swapETHForExactTokens(uint256,address[],address,uint256)",
+    "swapExactTokensForETH(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactETH(uint256,uint256,address[],address,uint256)",
+    "addLiquidity(address,address,uint256,uint256,uint256,uint256,address,uint256)",
+    "addLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidity(address,address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "exactInput((bytes,address,uint256,uint256,uint256))",
+    "exactOutputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "exactOutput((bytes,address,uint256,uint256,uint256))",
+    "multicall(bytes[])",
+    "multicall(uint256,bytes[])",
+    "execute(bytes,bytes[],uint256)",
+    "aggregate3((address,bool,bytes)[])",
+];
+
+const FOUR_BYTE_URL: &str = "https://www.4byte.directory/api/v1/signatures/";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn common_signatures() -> &'static HashMap<Selector, &'static str> {
+    static SIGNATURES: OnceLock<HashMap<Selector, &'static str>> = OnceLock::new();
+    SIGNATURES.get_or_init(|| {
+        COMMON_SIGNATURES.iter()
+            .map(|signature| (Selector::from_slice(&keccak256(signature)[..4]), *signature))
+            .collect()
+    })
+}
+
+/// Names function selectors that no loaded ABI covers: from the embedded
+/// table, and with `--online-4byte` from 4byte.directory
+#[derive(Default)]
+pub struct SelectorLookup {
+    online: Option<reqwest::Client>,
+}
+
+impl SelectorLookup {
+    /// A lookup that also asks 4byte.directory about unknown selectors
+    pub fn online() -> Result<Self> {
+        let http = reqwest::Client::builder().timeout(HTTP_TIMEOUT).build()?;
+        Ok(Self { online: Some(http) })
+    }
+
+    /// The text signature for `selector`, if known. A failed online lookup
+    /// is reported and treated as unknown.
+    pub async fn signature(&self, selector: Selector) -> Option<String> {
+        if let Some(signature) = common_signatures().get(&selector) {
+            return Some(signature.to_string());
+        }
+        let http = self.online.as_ref()?;
+        match fetch_4byte(http, FOUR_BYTE_URL, selector).await {
+            Ok(signature) => signature,
+            Err(e) => {
+                eprintln!("⚠️  4byte.directory lookup failed: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Decode calldata against a bare text signature. Signatures carry no
+/// parameter names, so parameters are numbered instead.
+pub fn decode_with_signature(signature: &str, input: &[u8]) -> Option<Decoded> {
+    let function = Function::parse(signature).ok()?;
+    if input.get(..4)? != function.selector().as_slice() {
+        return None;
+    }
+    let values = function.abi_decode_input(&input[4..], true).ok()?;
+    Some(Decoded {
+        signature: function.signature(),
+        params: values.into_iter().enumerate().map(|(i, value)| (format!("arg{}", i), value)).collect(),
+    })
+}
+
+/// Ask a 4byte.directory-style API for `selector`. Colliding signatures
+/// are registered after the real one, so the earliest entry wins; entries
+/// that don't hash to the selector are ignored.
+async fn fetch_4byte(http: &reqwest::Client, url: &str, selector: Selector) -> Result<Option<String>> {
+    let body: serde_json::Value = http.get(url)
+        .query(&[("hex_signature", format!("0x{}", hex::encode(selector)))])
+        .send().await?
+        .error_for_status()?
+        .json().await?;
+    let results = body["results"].as_array()
+        .ok_or_else(|| eyre!("Unexpected 4byte.directory response: {}", body))?;
+    Ok(results.iter()
+        .filter_map(|entry| Some((entry["id"].as_u64()?, entry["text_signature"].as_str()?)))
+        .filter(|(_, signature)| keccak256(signature)[..4] == selector[..])
+        .min_by_key(|(id, _)| *id)
+        .map(|(_, signature)| signature.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::format_sol_value;
+    use serde_json::json;
+    use wiremock::matchers::{method, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_common_selectors() {
+        let lookup = SelectorLookup::default();
+        let transfer = Selector::from([0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(lookup.signature(transfer).await.as_deref(), Some("transfer(address,uint256)"));
+        let swap = Selector::from([0x38, 0xed, 0x17, 0x39]);
+        assert_eq!(
+            lookup.signature(swap).await.as_deref(),
+            Some("swapExactTokensForTokens(uint256,uint256,address[],address,uint256)")
+        );
+        // Offline, anything else stays unknown
+        assert_eq!(lookup.signature(Selector::from([0xde, 0xad, 0xbe, 0xef])).await, None);
+    }
+
+    #[test]
+    fn test_decode_with_signature() {
+        let input = hex::decode(concat!(
+            "a9059cbb",
+            "000000000000000000000000742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+            "00000000000000000000000000000000000000000000000000000000000003e8",
+        )).unwrap();
+        let decoded = decode_with_signature("transfer(address,uint256)", &input).unwrap();
+        assert_eq!(decoded.params[0].0, "arg0");
+        assert_eq!(format_sol_value(&decoded.params[1].1), "1000");
+        // A signature for a different selector never decodes
+        assert!(decode_with_signature("approve(address,uint256)", &input).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_4byte_prefers_earliest_entry() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("hex_signature", "0xa9059cbb"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "count": 3,
+                "results": [
+                    { "id": 31780, "text_signature": "many_msg_babbage(bytes1)" },
+                    { "id": 145, "text_signature": "transfer(address,uint256)" },
+                    // Doesn't hash to the selector, however early
+                    { "id": 1, "text_signature": "bogus()" },
+                ],
+            })))
+            .mount(&server)
+            .await;
+
+        let http = reqwest::Client::new();
+        let signature = fetch_4byte(&http, &server.uri(), Selector::from([0xa9, 0x05, 0x9c, 0xbb])).await.unwrap();
+        assert_eq!(signature.as_deref(), Some("transfer(address,uint256)"));
+    }
+}
@@ -0,0 +1,80 @@
+//! A mock JSON-RPC node for tests. The library's and the binary's unit
+//! tests and the integration tests under `tests/` all build on this one
+//! copy, so it uses nothing from the crate itself.
+
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Wrap `response`, a `result` or `error` object, in a JSON-RPC reply
+/// that echoes the request id
+pub fn rpc_response(request: &Request, mut response: Value) -> ResponseTemplate {
+    let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+    response["jsonrpc"] = json!("2.0");
+    response["id"] = body.get("id").cloned().unwrap_or(json!(0));
+    ResponseTemplate::new(200).set_body_json(response)
+}
+
+/// Answers every matching request with the same `result` or `error` object
+pub struct RpcResponder(pub Value);
+
+impl Respond for RpcResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        rpc_response(request, self.0.clone())
+    }
+}
+
+/// Answer requests whose body contains `request` with `reply(params)`
+pub fn rpc_call(request: Value, reply: impl Fn(&Value) -> Value + Send + Sync + 'static) -> Mock {
+    Mock::given(method("POST"))
+        .and(body_partial_json(request))
+        .respond_with(move |request: &Request| {
+            let params = serde_json::from_slice::<Value>(&request.body).unwrap_or_default()["params"].take();
+            rpc_response(request, reply(&params))
+        })
+}
+
+/// Answer `rpc_method` with `reply(params)`
+pub fn rpc(rpc_method: &str, reply: impl Fn(&Value) -> Value + Send + Sync + 'static) -> Mock {
+    rpc_call(json!({ "method": rpc_method }), reply)
+}
+
+/// Answer `rpc_method` with a fixed result
+pub fn rpc_result(rpc_method: &str, result: Value) -> Mock {
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": rpc_method })))
+        .respond_with(RpcResponder(json!({ "result": result })))
+}
+
+/// Answer `rpc_method` with an error object
+pub fn rpc_error(rpc_method: &str, code: i32, message: &str) -> Mock {
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": rpc_method })))
+        .respond_with(RpcResponder(json!({ "error": { "code": code, "message": message } })))
+}
+
+/// A block header as the node sends it, without transactions
+pub fn block(number: &str) -> Value {
+    json!({
+        "number": number,
+        "hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+        "parentHash": "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890",
+        "timestamp": "0x6500b8a0",
+        "gasUsed": "0xe4e1c0",
+        "gasLimit": "0x1c9c380",
+        "miner": "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f",
+        "difficulty": "0x0",
+        "size": "0x220",
+        "extraData": "0x",
+        "transactions": [],
+    })
+}
+
+/// Start a mock node that answers the connection check as mainnet
+pub async fn mock_node() -> MockServer {
+    let server = MockServer::start().await;
+    rpc_result("eth_chainId", json!("0x1")).mount(&server).await;
+    server
+}
@@ -1,36 +1,226 @@
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{b256, hex, keccak256, Address, Bloom, B256, U256};
 use chrono::{DateTime, Utc};
+use std::sync::OnceLock;
+use std::time::Duration;
 
-/// Format U256 Wei values to ETH with proper decimal places
+use crate::error::{ExtractorError, RethClientError};
+use crate::labels::address_label;
+
+/// A chain's native currency: the symbol for whole units and the name of
+/// the 10^-9 unit gas prices are quoted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NativeCurrency {
+    pub symbol: &'static str,
+    pub gas_unit: &'static str,
+}
+
+pub const ETHER: NativeCurrency = NativeCurrency { symbol: "ETH", gas_unit: "Gwei" };
+
+/// Chains whose native currency isn't ether. Rollups like Optimism, Arbitrum
+/// and Base pay gas in ETH and need no entry.
+const KNOWN_NATIVE_CURRENCIES: &[(u64, NativeCurrency)] = &[
+    (25, NativeCurrency { symbol: "CRO", gas_unit: "Gwei" }),
+    (56, NativeCurrency { symbol: "BNB", gas_unit: "Gwei" }),
+    (97, NativeCurrency { symbol: "tBNB", gas_unit: "Gwei" }),
+    (100, NativeCurrency { symbol: "xDAI", gas_unit: "Gwei" }),
+    (137, NativeCurrency { symbol: "POL", gas_unit: "Gwei" }),
+    (250, NativeCurrency { symbol: "FTM", gas_unit: "Gwei" }),
+    (1284, NativeCurrency { symbol: "GLMR", gas_unit: "Gwei" }),
+    (42220, NativeCurrency { symbol: "CELO", gas_unit: "Gwei" }),
+    (43113, NativeCurrency { symbol: "AVAX", gas_unit: "nAVAX" }),
+    (43114, NativeCurrency { symbol: "AVAX", gas_unit: "nAVAX" }),
+    (80002, NativeCurrency { symbol: "POL", gas_unit: "Gwei" }),
+];
+
+/// Native currency of the chain amounts are shown for, set once after
+/// connecting
+static NATIVE_CURRENCY: OnceLock<NativeCurrency> = OnceLock::new();
+
+/// The native currency of `chain_id`, or ether for chains not listed
+pub fn native_currency_for_chain(chain_id: u64) -> NativeCurrency {
+    KNOWN_NATIVE_CURRENCIES
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map_or(ETHER, |(_, currency)| *currency)
+}
+
+/// Label every amount formatted from here on in `currency`. Only the first
+/// call takes effect.
+pub fn install_native_currency(currency: NativeCurrency) {
+    let _ = NATIVE_CURRENCY.set(currency);
+}
+
+/// The currency amounts are labelled in: the installed one, or ether
+pub fn native_currency() -> NativeCurrency {
+    NATIVE_CURRENCY.get().copied().unwrap_or(ETHER)
+}
+
+/// Format wei in the chain's native currency exactly, with integer
+/// arithmetic so no amount loses precision or overflows: up to 18
+/// decimals, trailing zeros trimmed
 pub fn format_wei_u256(wei: &U256) -> String {
-    let wei_as_u128 = wei.to::<u128>();
-    let eth = wei_as_u128 as f64 / 1_000_000_000_000_000_000.0;
-    if eth >= 1.0 {
-        format!("{:.4} ETH", eth)
-    } else if eth >= 0.001 {
-        format!("{:.6} ETH", eth)
-    } else {
-        format!("{:.9} ETH", eth)
+    format_wei_in(wei, native_currency())
+}
+
+/// `format_wei_u256` labelled in `currency`
+pub fn format_wei_in(wei: &U256, currency: NativeCurrency) -> String {
+    format!("{} {}", format_units(wei, 18), currency.symbol)
+}
+
+/// Format a raw token amount with `decimals` decimal places, exactly and
+/// with trailing zeros trimmed, e.g. `1500000` with 6 decimals is `1.5`
+pub fn format_units(amount: &U256, decimals: u8) -> String {
+    if amount.is_zero() {
+        return "0".to_string();
+    }
+    let Some(unit) = U256::from(10).checked_pow(U256::from(decimals)) else {
+        // No U256 amount has a whole part at this scale
+        return format!("0.{:0>width$}", amount, width = decimals as usize).trim_end_matches('0').to_string();
+    };
+    let (whole, fraction) = amount.div_rem(unit);
+    if fraction.is_zero() {
+        return whole.to_string();
     }
+    let fraction = format!("{:0>width$}", fraction, width = decimals as usize);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
 }
 
 /// Format Wei values to ETH with proper decimal places
 pub fn format_wei(wei_str: &str) -> String {
+    let symbol = native_currency().symbol;
     match wei_str.parse::<u128>() {
         Ok(wei) => {
             let eth = wei as f64 / 1_000_000_000_000_000_000.0;
             if eth >= 1.0 {
-                format!("{:.4} ETH", eth)
+                format!("{:.4} {}", eth, symbol)
             } else if eth >= 0.001 {
-                format!("{:.6} ETH", eth)
+                format!("{:.6} {}", eth, symbol)
             } else {
-                format!("{:.9} ETH", eth)
+                format!("{:.9} {}", eth, symbol)
             }
         }
         Err(_) => format!("{} wei", wei_str),
     }
 }
 
+/// Parse a decimal ETH amount (e.g. `"1.5"`) into wei without float rounding
+pub fn parse_eth(amount: &str) -> Result<U256, String> {
+    let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(format!("Invalid ETH amount: {:?}", amount));
+    }
+    if fraction.len() > 18 {
+        return Err(format!("Too many decimal places in {:?} (max 18)", amount));
+    }
+    let digits = format!("{}{:0<18}", whole, fraction);
+    U256::from_str_radix(&digits, 10).map_err(|_| format!("Invalid ETH amount: {:?}", amount))
+}
+
+/// EIP-1967 proxy slots, `keccak256("eip1967.proxy.<name>") - 1`
+pub const EIP1967_IMPLEMENTATION_SLOT: B256 = b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+pub const EIP1967_ADMIN_SLOT: B256 = b256!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+pub const EIP1967_BEACON_SLOT: B256 = b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+
+/// Storage slots accepted by name in `--slot`
+const NAMED_STORAGE_SLOTS: [(&str, B256); 3] = [
+    ("impl", EIP1967_IMPLEMENTATION_SLOT),
+    ("admin", EIP1967_ADMIN_SLOT),
+    ("beacon", EIP1967_BEACON_SLOT),
+];
+
+/// Parse a storage slot: a hex (`0x`-prefixed) or decimal index, or one of
+/// the EIP-1967 names `impl`, `admin` and `beacon`
+pub fn parse_storage_slot(input: &str) -> Result<B256, String> {
+    let input = input.trim();
+    if let Some((_, slot)) = NAMED_STORAGE_SLOTS.iter().find(|(name, _)| *name == input) {
+        return Ok(*slot);
+    }
+    let (digits, radix) = match input.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (input, 10),
+    };
+    Some(digits).filter(|digits| !digits.is_empty())
+        .and_then(|digits| U256::from_str_radix(digits, radix).ok())
+        .map(B256::from)
+        .ok_or_else(|| format!("Invalid storage slot {:?} (expected a hex or decimal index, or impl/admin/beacon)", input))
+}
+
+/// The name `--slot` accepts for a well-known slot, if any
+pub fn storage_slot_name(slot: &B256) -> Option<&'static str> {
+    NAMED_STORAGE_SLOTS.iter().find(|(_, named)| named == slot).map(|(name, _)| *name)
+}
+
+/// One end of a block range: a number, or `latest` for the current head
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockBound {
+    Number(u64),
+    Latest,
+}
+
+/// Parse a block range bound: a decimal block number or `latest`
+pub fn parse_block_bound(input: &str) -> Result<BlockBound, String> {
+    match input.trim() {
+        "latest" => Ok(BlockBound::Latest),
+        number => number.parse()
+            .map(BlockBound::Number)
+            .map_err(|_| format!("Invalid block {:?} (expected a number or `latest`)", input)),
+    }
+}
+
+/// Parse a duration such as `90s`, `5m` or `1h30m`; a bare number is
+/// seconds. Units: `ms`, `s`, `m`, `h`, `d`.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let trimmed = input.trim();
+    if let Ok(secs) = trimmed.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+    if trimmed.is_empty() {
+        return Err("Empty duration".to_string());
+    }
+
+    let invalid = || format!("Invalid duration {:?} (expected e.g. 90s, 5m, 1h30m)", input);
+    let mut total_ms: u64 = 0;
+    let mut rest = trimmed;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit_ms: u64 = match &rest[..unit_len] {
+            "ms" => 1,
+            "s" => 1_000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            "d" => 86_400_000,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+
+        total_ms = value.checked_mul(unit_ms)
+            .and_then(|ms| total_ms.checked_add(ms))
+            .ok_or_else(|| format!("Duration {:?} is too long", input))?;
+    }
+    Ok(Duration::from_millis(total_ms))
+}
+
+/// Format a duration the way `parse_duration` reads one, e.g. `10m` or
+/// `1h30m`, rather than Debug's `600s`
+pub fn format_duration(duration: Duration) -> String {
+    let mut ms = duration.as_millis();
+    if ms == 0 {
+        return "0s".to_string();
+    }
+    let mut formatted = String::new();
+    for (unit, unit_ms) in [("d", 86_400_000), ("h", 3_600_000), ("m", 60_000), ("s", 1_000), ("ms", 1)] {
+        if ms >= unit_ms {
+            formatted.push_str(&format!("{}{}", ms / unit_ms, unit));
+            ms %= unit_ms;
+        }
+    }
+    formatted
+}
+
 /// Format hash to show first 10 and last 4 characters
 pub fn format_hash(hash: &str) -> String {
     if hash.len() >= 14 {
@@ -55,10 +245,67 @@ pub fn hex_to_decimal(hex_str: &str) -> Result<u64, std::num::ParseIntError> {
     u64::from_str_radix(clean_hex, 16)
 }
 
+/// Split an inclusive block range into inclusive chunks of at most `size` blocks
+pub fn block_chunks(from_block: u64, to_block: u64, size: u64) -> Vec<(u64, u64)> {
+    let size = size.max(1);
+    let mut chunks = Vec::new();
+    let mut start = from_block;
+    while start <= to_block {
+        let end = start.saturating_add(size - 1).min(to_block);
+        chunks.push((start, end));
+        if end == u64::MAX {
+            break;
+        }
+        start = end + 1;
+    }
+    chunks
+}
+
+/// Group sorted block numbers into inclusive runs of consecutive blocks,
+/// each at most `max_len` long
+pub fn block_runs(blocks: &[u64], max_len: u64) -> Vec<(u64, u64)> {
+    let max_len = max_len.max(1);
+    let mut runs: Vec<(u64, u64)> = Vec::new();
+    for &block in blocks {
+        match runs.last_mut() {
+            Some((start, end)) if block == *end + 1 && block - *start < max_len => *end = block,
+            _ => runs.push((block, block)),
+        }
+    }
+    runs
+}
+
+/// Test a 2048-bit logs bloom for an address or topic. Each item sets three
+/// bits, taken from the first three byte pairs of its keccak256 hash (low
+/// 11 bits each, counted from the end of the filter).
+pub fn bloom_might_contain(bloom: &Bloom, item: &[u8]) -> bool {
+    let hash = keccak256(item);
+    (0..3).all(|i| {
+        let bit = (u16::from_be_bytes([hash[2 * i], hash[2 * i + 1]]) & 0x7ff) as usize;
+        bloom[255 - bit / 8] & (1 << (bit % 8)) != 0
+    })
+}
+
+/// Dollar value of an amount of wei at `eth_usd` dollars per ETH, e.g. `$1,234.56`
+pub fn format_usd(wei: &U256, eth_usd: f64) -> String {
+    let eth: f64 = wei.to_string().parse().unwrap_or(0.0) / 1e18;
+    let cents = (eth * eth_usd * 100.0).round() as u64;
+    format!("${}.{:02}", format_number(cents / 100), cents % 100)
+}
+
 /// Format large numbers with commas
 pub fn format_number(num: u64) -> String {
-    let result = num.to_string();
-    let mut chars: Vec<char> = result.chars().collect();
+    group_digits(&num.to_string())
+}
+
+/// `format_number` for values that may not fit in a u64, such as pre-merge
+/// difficulty
+pub fn format_number_u256(num: &U256) -> String {
+    group_digits(&num.to_string())
+}
+
+fn group_digits(digits: &str) -> String {
+    let mut chars: Vec<char> = digits.chars().collect();
     
     let mut i = chars.len();
     while i > 3 {
@@ -69,17 +316,135 @@ pub fn format_number(num: u64) -> String {
     chars.into_iter().collect()
 }
 
+/// How gas amounts are rendered in tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GasFormat {
+    /// Comma-grouped digits, e.g. `12,345,678`
+    Raw,
+    /// Suffixed with one decimal, e.g. `12.3M`
+    Human,
+}
+
+/// Format gas with a K/M/B suffix and one decimal place
+pub fn format_gas_human(gas: u64) -> String {
+    const UNITS: [(u128, &str); 3] = [(1_000, "K"), (1_000_000, "M"), (1_000_000_000, "B")];
+
+    if gas < 1_000 {
+        return gas.to_string();
+    }
+    for (i, (unit, suffix)) in UNITS.iter().enumerate() {
+        // Round to tenths first so 999,999 becomes 1.0M rather than 1000.0K
+        let tenths = (gas as u128 * 10 + unit / 2) / unit;
+        if tenths < 10_000 || i == UNITS.len() - 1 {
+            return format!("{}.{}{}", tenths / 10, tenths % 10, suffix);
+        }
+    }
+    unreachable!()
+}
+
+/// Format gas in the requested representation
+pub fn format_gas(gas: u64, format: GasFormat) -> String {
+    match format {
+        GasFormat::Raw => format_number(gas),
+        GasFormat::Human => format_gas_human(gas),
+    }
+}
+
+/// `format_gas` for header fields, which are U256 on the wire. Values too
+/// large for a u64 are shown digit for digit rather than clamped.
+pub fn format_gas_u256(gas: &U256, format: GasFormat) -> String {
+    match u64::try_from(*gas) {
+        Ok(gas) => format_gas(gas, format),
+        Err(_) => format_number_u256(gas),
+    }
+}
+
+/// What bulk and range commands do when a single item fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorPolicy {
+    /// Abort the whole command on the first error
+    FailFast,
+    /// Skip failed items and summarize them at the end
+    Continue,
+}
+
+/// An error `ErrorTally` can pass through under fail-fast, naming the item
+/// that failed without losing what kind of error it was
+pub trait TalliedError: std::fmt::Display {
+    fn failed(self, item: impl std::fmt::Display) -> Self;
+}
+
+impl TalliedError for color_eyre::Report {
+    fn failed(self, item: impl std::fmt::Display) -> Self {
+        self.wrap_err(format!("{} failed", item))
+    }
+}
+
+impl TalliedError for RethClientError {
+    fn failed(self, item: impl std::fmt::Display) -> Self {
+        self.context(format!("{} failed", item))
+    }
+}
+
+/// Per-item failures collected by a bulk command under an `ErrorPolicy`
+#[derive(Debug)]
+pub struct ErrorTally {
+    policy: ErrorPolicy,
+    attempted: usize,
+    failures: Vec<String>,
+}
+
+impl ErrorTally {
+    pub fn new(policy: ErrorPolicy) -> Self {
+        Self { policy, attempted: 0, failures: Vec::new() }
+    }
+
+    /// Record one item's outcome. Under fail-fast the error is returned;
+    /// under continue it is noted and `None` stands in for the value.
+    pub fn check<T, E: TalliedError>(&mut self, item: impl std::fmt::Display, result: Result<T, E>) -> Result<Option<T>, E> {
+        self.attempted += 1;
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self.policy == ErrorPolicy::FailFast => Err(e.failed(item)),
+            Err(e) => {
+                self.failures.push(format!("{}: {}", item, e));
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Print the failures, if any, with an "N of M failed" summary, and turn
+    /// them into a `PartialFailure` so the exit code shows the output is
+    /// incomplete. Goes to stderr so it never mixes into piped output.
+    pub fn finish(self) -> color_eyre::Result<()> {
+        if self.failures.is_empty() {
+            return Ok(());
+        }
+        eprintln!("\n⚠️  {} of {} failed:", self.failures.len(), self.attempted);
+        for failure in &self.failures {
+            eprintln!("  - {}", failure);
+        }
+        Err(ExtractorError::PartialFailure { failed: self.failures.len(), attempted: self.attempted }.into())
+    }
+}
+
 /// Format timestamp from Unix timestamp to human readable
 pub fn format_timestamp(timestamp: u64) -> String {
     let dt = DateTime::from_timestamp(timestamp as i64, 0)
-        .unwrap_or_else(|| Utc::now());
+        .unwrap_or_else(Utc::now);
     dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
 }
 
 /// Format U256 timestamp to human readable
 pub fn format_timestamp_u256(timestamp: &U256) -> String {
-    let timestamp_u64 = timestamp.to::<u64>();
-    format_timestamp(timestamp_u64)
+    match u64::try_from(*timestamp) {
+        Ok(timestamp) => format_timestamp(timestamp),
+        Err(_) => timestamp.to_string(),
+    }
 }
 
 /// Format B256 hash for display
@@ -88,16 +453,39 @@ pub fn format_b256_hash(hash: &B256) -> String {
     format_hash(&hash_str)
 }
 
-/// Format Address for display
+/// Format a block hash, which the pending block doesn't have yet
+pub fn format_block_hash(hash: &Option<B256>) -> String {
+    hash.as_ref().map_or("(pending)".to_string(), format_b256_hash)
+}
+
+/// Format Address for display: shortened, EIP-55 checksummed, and named
+/// when it has a label
 pub fn format_eth_address(address: &Address) -> String {
-    let addr_str = format!("{:?}", address);
-    format_address(&addr_str)
+    with_label(address, format_address(&address.to_checksum(None)))
+}
+
+/// `format_eth_address` keeping the whole checksummed address, for detail
+/// tables where it's likely to be copied
+pub fn format_eth_address_full(address: &Address) -> String {
+    with_label(address, address.to_checksum(None))
+}
+
+fn with_label(address: &Address, formatted: String) -> String {
+    match address_label(address) {
+        Some(label) => format!("{} ({})", label, formatted),
+        None => formatted,
+    }
 }
 
-/// Format gas price from wei to gwei
+/// Format gas price from wei to gwei, or the chain's equivalent unit
 pub fn format_gas_price(gas_price: &U256) -> String {
-    let gwei = gas_price.to::<u128>() as f64 / 1_000_000_000.0;
-    format!("{:.2} Gwei", gwei)
+    format_gas_price_in(gas_price, native_currency())
+}
+
+/// `format_gas_price` labelled in `currency`'s gas unit
+pub fn format_gas_price_in(gas_price: &U256, currency: NativeCurrency) -> String {
+    let gwei = gas_price.saturating_to::<u128>() as f64 / 1_000_000_000.0;
+    format!("{:.2} {}", gwei, currency.gas_unit)
 }
 
 /// Format transaction status
@@ -109,6 +497,33 @@ pub fn format_tx_status(status: &Option<U256>) -> String {
     }
 }
 
+/// Format an EIP-2718 transaction type with its name
+pub fn format_tx_type(tx_type: u8) -> String {
+    let name = match tx_type {
+        0 => "Legacy",
+        1 => "Access List",
+        2 => "EIP-1559",
+        3 => "Blob",
+        4 => "Set Code",
+        _ => "Unknown",
+    };
+    format!("{} (0x{:x})", name, tx_type)
+}
+
+/// The fee paid for `gas_used` at `gas_price`, in wei. `None` when the
+/// product overflows, which only nonsensical node data can cause.
+pub fn transaction_fee(gas_price: U256, gas_used: U256) -> Option<U256> {
+    gas_price.checked_mul(gas_used)
+}
+
+/// Split a fee into the part burned by the base fee (EIP-1559) and the
+/// priority fee left for the block producer, both in wei
+pub fn fee_breakdown(effective_gas_price: U256, gas_used: U256, base_fee: U256) -> (U256, U256) {
+    let burned = effective_gas_price.min(base_fee).saturating_mul(gas_used);
+    let tip = effective_gas_price.saturating_sub(base_fee).saturating_mul(gas_used);
+    (burned, tip)
+}
+
 /// Calculate time ago from timestamp
 pub fn time_ago(timestamp: u64) -> String {
     let now = Utc::now().timestamp() as u64;
@@ -129,6 +544,47 @@ pub fn time_ago(timestamp: u64) -> String {
     }
 }
 
+/// Irregularity in a sender's sequence of nonces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonceAnomaly {
+    /// The nonce was used by more than one transaction (a replacement)
+    Duplicate { nonce: u64, count: usize },
+    /// The nonce falls between observed nonces but was never seen
+    Missing { nonce: u64 },
+}
+
+impl std::fmt::Display for NonceAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NonceAnomaly::Duplicate { nonce, count } => {
+                write!(f, "nonce {} appears {} times (replacement)", nonce, count)
+            }
+            NonceAnomaly::Missing { nonce } => write!(f, "nonce {} missing in range", nonce),
+        }
+    }
+}
+
+/// Find duplicate and missing nonces between the lowest and highest observed nonce
+pub fn nonce_anomalies(nonces: &[u64]) -> Vec<NonceAnomaly> {
+    let mut sorted = nonces.to_vec();
+    sorted.sort_unstable();
+
+    let mut anomalies = Vec::new();
+    let mut i = 0;
+    while i < sorted.len() {
+        let nonce = sorted[i];
+        let count = sorted[i..].iter().take_while(|&&n| n == nonce).count();
+        if count > 1 {
+            anomalies.push(NonceAnomaly::Duplicate { nonce, count });
+        }
+        if let Some(&next) = sorted.get(i + count) {
+            anomalies.extend((nonce + 1..next).map(|nonce| NonceAnomaly::Missing { nonce }));
+        }
+        i += count;
+    }
+    anomalies
+}
+
 /// Detect contract vs EOA
 pub fn account_type(code_size: &U256) -> &'static str {
     if code_size.is_zero() {
@@ -138,6 +594,86 @@ pub fn account_type(code_size: &U256) -> &'static str {
     }
 }
 
+/// Genesis block hashes of well-known networks. Unlike the chain id, the
+/// genesis hash can't be misreported without serving a different chain.
+const KNOWN_GENESIS_HASHES: &[(B256, &str)] = &[
+    (b256!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"), "Ethereum Mainnet"),
+    (b256!("25a5cc106eea7138acab33231d7160d69cb777ee0c2c553fcddf5138993e6dd9"), "Sepolia"),
+    (b256!("b5f7f912443c940f21fd611f12828d75b534364ed9e95ca4e307729a4661bde4"), "Holesky"),
+    (b256!("bf7e331f7f7c1dd2e05159666b3bf8bc7a8a3a9eb1d518969eab529dd9b88c1a"), "Goerli"),
+    (b256!("41941023680923e0fe4d74a34bdac8141f2540e3ae90623718e47d66d1ca4a2d"), "Ropsten"),
+    (b256!("6341fd3daf94b748c72ced5a5b26028f2474f5f00d824504e4fa37a75767e177"), "Rinkeby"),
+];
+
+/// Identify a network by its genesis block hash
+pub fn known_genesis(hash: &B256) -> Option<&'static str> {
+    KNOWN_GENESIS_HASHES
+        .iter()
+        .find(|(known, _)| known == hash)
+        .map(|(_, name)| *name)
+}
+
+/// Runtime code hashes of well-known contracts. Only bytecode that is
+/// identical across every deployment belongs here; anything with
+/// constructor-embedded immutables hashes differently per instance.
+const KNOWN_CODE_HASHES: &[(B256, &str)] = &[
+    (b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"), "No code (EOA)"),
+    (b256!("2fa86add0aed31f33a762c9d88e807c475bd51d0f52bd0955754b2608f7e4989"), "Deterministic Deployment Proxy (CREATE2 factory)"),
+    (b256!("f57acd40259872606d76197ef052f3d35588dadf919ee1f0e3cb9b62d3f4b02c"), "EIP-4788 Beacon Roots"),
+    // Safe proxies keep their singleton in storage slot 0, so every wallet
+    // of a version shares one runtime code
+    (b256!("b89c1b3bdf2cf8827818646bce9a8f6e372885f8c55e5c07acbd307cb133b000"), "Gnosis Safe Proxy v1.3.0"),
+    (b256!("aea7d4252f6245f301e540cfbee27d3a88de543af8e49c5c62405d5499fab7e5"), "Gnosis Safe Proxy v1.1.1"),
+    // Keeps its implementation in the ERC-1967 slot rather than in the code
+    (b256!("aaa52c8cc8a0e3fd27ce756cc6b4e70c51423e9b597b11f32d3e49f8b1fc890d"), "ERC-1967 Minimal Proxy (Solady LibClone / ERC1967Factory)"),
+];
+
+/// Recognize deployed bytecode by its keccak256 hash
+pub fn known_code_hash(hash: &B256) -> Option<&'static str> {
+    KNOWN_CODE_HASHES
+        .iter()
+        .find(|(known, _)| known == hash)
+        .map(|(_, name)| *name)
+}
+
+/// Runtime code of an EIP-1167 minimal proxy (as deployed by OpenZeppelin
+/// `Clones`), around the 20-byte implementation address
+const EIP1167_PREFIX: [u8; 10] = hex!("363d3d373d3d3d363d73");
+const EIP1167_SUFFIX: [u8; 15] = hex!("5af43d82803e903d91602b57fd5bf3");
+
+/// The implementation an EIP-1167 minimal proxy forwards to. These embed
+/// the address, so each clone hashes differently and needs matching by shape.
+pub fn minimal_proxy_target(code: &[u8]) -> Option<Address> {
+    let address = code.strip_prefix(&EIP1167_PREFIX)?.strip_suffix(&EIP1167_SUFFIX)?;
+    (address.len() == 20).then(|| Address::from_slice(address))
+}
+
+/// ENS namehash (EIP-137) of a dot-separated name
+pub fn namehash(name: &str) -> B256 {
+    name.rsplit('.')
+        .filter(|label| !label.is_empty())
+        .fold(B256::ZERO, |node, label| {
+            keccak256([node.as_slice(), keccak256(label.as_bytes()).as_slice()].concat())
+        })
+}
+
+/// Parse a `raw` command param as JSON, falling back to a plain string so
+/// hex values and block tags don't need quoting
+pub fn parse_raw_param(param: &str) -> serde_json::Value {
+    serde_json::from_str(param).unwrap_or_else(|_| serde_json::Value::String(param.to_string()))
+}
+
+/// Normalize a receipt `status` to 1 (success) or 0 (failure). Nodes send
+/// it as a hex string, but some encode it as a boolean or a plain number.
+pub fn parse_status(value: &serde_json::Value) -> Option<U256> {
+    match value {
+        serde_json::Value::String(s) => U256::from_str_radix(s.trim_start_matches("0x"), 16).ok(),
+        serde_json::Value::Bool(success) => Some(U256::from(*success as u8)),
+        serde_json::Value::Number(n) => n.as_u64().map(U256::from),
+        _ => None,
+    }
+}
+
 /// Calculate gas utilization percentage
 pub fn calculate_gas_utilization(gas_used: u64, gas_limit: u64) -> f64 {
     if gas_limit == 0 {
@@ -147,6 +683,72 @@ pub fn calculate_gas_utilization(gas_used: u64, gas_limit: u64) -> f64 {
     }
 }
 
+/// Nearest-rank percentile (0–100) of already sorted values
+pub fn percentile<T: Copy>(sorted: &[T], pct: f64) -> Option<T> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.saturating_sub(1)])
+}
+
+/// Count values into `buckets` equal-width ranges spanning min..=max.
+/// Each entry is the bucket's inclusive lower bound and its count.
+pub fn histogram(values: &[u64], buckets: usize) -> Vec<(u64, usize)> {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return Vec::new();
+    };
+    let buckets = buckets.max(1);
+    let width = ((max - min) / buckets as u64).max(1);
+    let mut counts = vec![0usize; buckets];
+    for &value in values {
+        let index = (((value - min) / width) as usize).min(buckets - 1);
+        counts[index] += 1;
+    }
+    counts.into_iter()
+        .enumerate()
+        .map(|(i, count)| (min + i as u64 * width, count))
+        .collect()
+}
+
+/// Addresses listed one per line, skipping blank lines and `#` comments
+pub fn parse_address_list(contents: &str) -> Vec<String> {
+    contents.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Levels of a sparkline, lowest first
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Draw `values` as a sparkline at most `width` characters wide, scaled
+/// between their own minimum and maximum. Longer series are averaged down
+/// so every value lands in exactly one column.
+pub fn sparkline(values: &[u64], width: usize) -> String {
+    let columns = values.len().min(width.max(1));
+    let averages: Vec<u64> = (0..columns)
+        .map(|i| {
+            let group = &values[i * values.len() / columns..(i + 1) * values.len() / columns];
+            (group.iter().map(|&v| v as u128).sum::<u128>() / group.len() as u128) as u64
+        })
+        .collect();
+    let (Some(&min), Some(&max)) = (averages.iter().min(), averages.iter().max()) else {
+        return String::new();
+    };
+    averages.iter()
+        .map(|&value| {
+            let level = match max - min {
+                // A flat series sits mid-height rather than looking empty
+                0 => SPARK_LEVELS.len() / 2,
+                span => ((value - min) as u128 * (SPARK_LEVELS.len() - 1) as u128 / span as u128) as usize,
+            };
+            SPARK_LEVELS[level]
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,6 +760,16 @@ mod tests {
         assert!(format_wei("invalid").contains("wei"));
     }
 
+    #[test]
+    fn test_parse_eth() {
+        assert_eq!(parse_eth("1").unwrap(), U256::from(1_000_000_000_000_000_000u128));
+        assert_eq!(parse_eth("0.5").unwrap(), U256::from(500_000_000_000_000_000u128));
+        assert_eq!(parse_eth("0.000000000000000001").unwrap(), U256::from(1));
+        assert!(parse_eth("1.0000000000000000001").is_err());
+        assert!(parse_eth("abc").is_err());
+        assert!(parse_eth(".").is_err());
+    }
+
     #[test]
     fn test_format_hash() {
         let hash = "0x1234567890abcdef1234567890abcdef12345678";
@@ -168,6 +780,11 @@ mod tests {
     fn test_format_address() {
         let addr = "0x742d35Cc622C1E0532F7fd0e7c0e6f7D8F2B2B6f";
         assert_eq!(format_address(addr), "0x742d...2B6f");
+
+        // Test vector from EIP-55
+        let addr: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+        assert_eq!(format_eth_address_full(&addr), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(format_eth_address(&addr), "0x5aAe...eAed");
     }
 
     #[test]
@@ -176,10 +793,305 @@ mod tests {
         assert_eq!(hex_to_decimal("FF"), Ok(255));
     }
 
+    #[test]
+    fn test_block_chunks() {
+        assert_eq!(block_chunks(0, 9, 5), vec![(0, 4), (5, 9)]);
+        assert_eq!(block_chunks(10, 12, 5), vec![(10, 12)]);
+        assert_eq!(block_chunks(3, 3, 1), vec![(3, 3)]);
+        assert!(block_chunks(5, 4, 10).is_empty());
+    }
+
+    #[test]
+    fn test_parse_status() {
+        use serde_json::json;
+        assert_eq!(parse_status(&json!("0x1")), Some(U256::from(1)));
+        assert_eq!(parse_status(&json!("0x0")), Some(U256::ZERO));
+        assert_eq!(parse_status(&json!(true)), Some(U256::from(1)));
+        assert_eq!(parse_status(&json!(false)), Some(U256::ZERO));
+        assert_eq!(parse_status(&json!(1)), Some(U256::from(1)));
+        assert_eq!(parse_status(&json!(0)), Some(U256::ZERO));
+        assert_eq!(parse_status(&json!(null)), None);
+        assert_eq!(parse_status(&json!("pending")), None);
+    }
+
+    #[test]
+    fn test_fee_breakdown() {
+        let gwei = |n: u64| U256::from(n * 1_000_000_000);
+        // 21,000 gas at 32 gwei over a 30 gwei base fee
+        let (burned, tip) = fee_breakdown(gwei(32), U256::from(21_000), gwei(30));
+        assert_eq!(burned, gwei(30) * U256::from(21_000));
+        assert_eq!(tip, gwei(2) * U256::from(21_000));
+        // Nothing burned before London
+        assert_eq!(fee_breakdown(gwei(20), U256::from(21_000), U256::ZERO).0, U256::ZERO);
+        assert_eq!(format_tx_type(2), "EIP-1559 (0x2)");
+        assert_eq!(format_tx_type(0x7e), "Unknown (0x7e)");
+    }
+
+    #[test]
+    fn test_transaction_fee_overflow() {
+        assert_eq!(transaction_fee(U256::from(30), U256::from(21_000)), Some(U256::from(630_000)));
+        let near_max = U256::MAX - U256::from(1);
+        assert_eq!(transaction_fee(near_max, near_max), None);
+        assert_eq!(transaction_fee(near_max, U256::from(1)), Some(near_max));
+    }
+
+    #[test]
+    fn test_parse_raw_param() {
+        use serde_json::json;
+        assert_eq!(parse_raw_param("latest"), json!("latest"));
+        assert_eq!(parse_raw_param("0x10"), json!("0x10"));
+        assert_eq!(parse_raw_param("true"), json!(true));
+        assert_eq!(parse_raw_param("42"), json!(42));
+        assert_eq!(parse_raw_param(r#"{"to":"0xaa"}"#), json!({ "to": "0xaa" }));
+        assert_eq!(parse_raw_param("\"quoted\""), json!("quoted"));
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&values, 50.0), Some(50));
+        assert_eq!(percentile(&values, 99.0), Some(99));
+        assert_eq!(percentile(&values, 0.0), Some(1));
+        assert_eq!(percentile(&values, 100.0), Some(100));
+        assert_eq!(percentile::<u64>(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let buckets = histogram(&[21_000, 21_000, 50_000, 100_000, 121_000], 4);
+        assert_eq!(buckets, vec![(21_000, 2), (46_000, 1), (71_000, 0), (96_000, 2)]);
+        assert_eq!(histogram(&[7, 7], 3), vec![(7, 2), (8, 0), (9, 0)]);
+        assert!(histogram(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_block_runs() {
+        assert_eq!(block_runs(&[1, 2, 3, 7, 8, 20], 10), vec![(1, 3), (7, 8), (20, 20)]);
+        assert_eq!(block_runs(&[1, 2, 3, 4, 5], 2), vec![(1, 2), (3, 4), (5, 5)]);
+        assert!(block_runs(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_bloom_might_contain() {
+        use alloy_primitives::BloomInput;
+        let usdc = b256!("000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        let transfer = b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let mut bloom = Bloom::ZERO;
+        bloom.accrue(BloomInput::Raw(&usdc[12..]));
+        bloom.accrue(BloomInput::Raw(transfer.as_slice()));
+
+        assert!(bloom_might_contain(&bloom, &usdc[12..]));
+        assert!(bloom_might_contain(&bloom, transfer.as_slice()));
+        assert!(!bloom_might_contain(&bloom, Address::with_last_byte(1).as_slice()));
+        assert!(!bloom_might_contain(&Bloom::ZERO, transfer.as_slice()));
+        assert!(bloom_might_contain(&Bloom::repeat_byte(0xff), transfer.as_slice()));
+    }
+
+    #[test]
+    fn test_format_usd() {
+        assert_eq!(format_usd(&U256::from(1_500_000_000_000_000_000u128), 2000.0), "$3,000.00");
+        assert_eq!(format_usd(&U256::from(21_000u64 * 20_000_000_000), 3123.45), "$1.31");
+        assert_eq!(format_usd(&U256::ZERO, 3000.0), "$0.00");
+    }
+
     #[test]
     fn test_format_number() {
         assert_eq!(format_number(1234567), "1,234,567");
         assert_eq!(format_number(123), "123");
+        // Mainnet difficulty around the Merge, well past u64::MAX
+        let difficulty = U256::from(58_750_003_716_598_352_816_469u128);
+        assert_eq!(format_number_u256(&difficulty), "58,750,003,716,598,352,816,469");
+        assert_eq!(format_timestamp_u256(&U256::MAX), U256::MAX.to_string());
+    }
+
+    #[test]
+    fn test_parse_address_list() {
+        let list = "# treasury\n0x742d35Cc622C1E0532F7fd0e7c0e6f7D8F2B2B6f\n\n  vitalik.eth  \n";
+        assert_eq!(parse_address_list(list), ["0x742d35Cc622C1E0532F7fd0e7c0e6f7D8F2B2B6f", "vitalik.eth"]);
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0, 10, 20, 30, 40, 50, 60, 70], 80), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[5, 5, 5], 80), "▅▅▅");
+        assert_eq!(sparkline(&[], 80), "");
+        // Eight values in four columns average in pairs
+        assert_eq!(sparkline(&[0, 0, 10, 10, 0, 0, 10, 10], 4), "▁█▁█");
+        assert_eq!(sparkline(&(0..1000).collect::<Vec<_>>(), 50).chars().count(), 50);
+    }
+
+    #[test]
+    fn test_parse_storage_slot() {
+        assert_eq!(parse_storage_slot("0").unwrap(), B256::ZERO);
+        assert_eq!(parse_storage_slot("10").unwrap(), B256::with_last_byte(10));
+        assert_eq!(parse_storage_slot("0x0a").unwrap(), B256::with_last_byte(10));
+        assert_eq!(parse_storage_slot("impl").unwrap(), EIP1967_IMPLEMENTATION_SLOT);
+        assert_eq!(
+            parse_storage_slot("0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc").unwrap(),
+            EIP1967_IMPLEMENTATION_SLOT
+        );
+        assert_eq!(storage_slot_name(&EIP1967_BEACON_SLOT), Some("beacon"));
+        assert!(parse_storage_slot("0x").is_err());
+        assert!(parse_storage_slot("").is_err());
+        assert!(parse_storage_slot("implementation").is_err());
+        // One hex digit more than a word holds
+        assert!(parse_storage_slot(&format!("0x1{}", "0".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn test_format_large_numbers() {
+        let thirty_digits: U256 = "123456789012345678901234567890".parse().unwrap();
+        assert_eq!(format_number_u256(&thirty_digits), "123,456,789,012,345,678,901,234,567,890");
+        assert_eq!(format_number_u256(&U256::ZERO), "0");
+        assert_eq!(format_gas_u256(&thirty_digits, GasFormat::Human), "123,456,789,012,345,678,901,234,567,890");
+        assert_eq!(format_gas_u256(&U256::from(30_000_000), GasFormat::Human), "30.0M");
+        assert_eq!(format_gas_u256(&U256::from(30_000_000), GasFormat::Raw), "30,000,000");
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units(&U256::from(1_500_000u64), 6), "1.5");
+        assert_eq!(format_units(&U256::from(42), 0), "42");
+        assert_eq!(format_units(&U256::from(5), 2), "0.05");
+        assert_eq!(format_units(&U256::from(1), 255), format!("0.{}1", "0".repeat(254)));
+        assert_eq!(format_units(&U256::ZERO, 255), "0");
+        assert_eq!(format_units(&U256::ZERO, 18), "0");
+    }
+
+    #[test]
+    fn test_format_wei_u256() {
+        assert_eq!(format_wei_u256(&parse_eth("1").unwrap()), "1 ETH");
+        assert_eq!(format_wei_u256(&parse_eth("1.5").unwrap()), "1.5 ETH");
+        assert_eq!(format_wei_u256(&U256::from(1)), "0.000000000000000001 ETH");
+        assert_eq!(format_wei_u256(&U256::ZERO), "0 ETH");
+        assert_eq!(format_wei_u256(&parse_eth("1000").unwrap()), "1000 ETH");
+        assert_eq!(
+            format_wei_u256(&U256::MAX),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935 ETH"
+        );
+    }
+
+    #[test]
+    fn test_native_currency_labels() {
+        let bsc = native_currency_for_chain(56);
+        assert_eq!(format_wei_in(&parse_eth("1.5").unwrap(), bsc), "1.5 BNB");
+        assert_eq!(format_gas_price_in(&U256::from(3_000_000_000u64), bsc), "3.00 Gwei");
+        let avalanche = native_currency_for_chain(43114);
+        assert_eq!(format_gas_price_in(&U256::from(25_000_000_000u64), avalanche), "25.00 nAVAX");
+        // Rollups and unknown chains fall back to ether
+        assert_eq!(native_currency_for_chain(8453), ETHER);
+        assert_eq!(native_currency_for_chain(999_999), ETHER);
+    }
+
+    #[test]
+    fn test_error_tally() {
+        let mut tally = ErrorTally::new(ErrorPolicy::Continue);
+        assert_eq!(tally.check("block 1", Ok::<_, color_eyre::Report>(1)).unwrap(), Some(1));
+        assert_eq!(tally.check("block 2", Err::<u32, _>(color_eyre::eyre::eyre!("timeout"))).unwrap(), None);
+        assert_eq!(tally.failed(), 1);
+        assert_eq!(tally.failures, vec!["block 2: timeout".to_string()]);
+        let err = tally.finish().unwrap_err();
+        assert_eq!(err.downcast_ref::<ExtractorError>(), Some(&ExtractorError::PartialFailure { failed: 1, attempted: 2 }));
+
+        let mut tally = ErrorTally::new(ErrorPolicy::FailFast);
+        assert!(tally.check("block 2", Err::<u32, _>(color_eyre::eyre::eyre!("timeout"))).is_err());
+        let err = tally.check("block 3", Err::<u32, _>(RethClientError::NotFound("Block 3 not found".into()))).unwrap_err();
+        assert_eq!(err, RethClientError::NotFound("block 3 failed: Block 3 not found".into()));
+    }
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(namehash("eth"), b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"));
+        assert_eq!(namehash("addr.reverse"), b256!("91d1777781884d03a6757a803996e38de2a42967fb37eeaca72729271025a9e2"));
+    }
+
+    #[test]
+    fn test_parse_block_bound() {
+        assert_eq!(parse_block_bound("18234000"), Ok(BlockBound::Number(18_234_000)));
+        assert_eq!(parse_block_bound("latest"), Ok(BlockBound::Latest));
+        assert!(parse_block_bound("0x10").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5_400)));
+        assert_eq!(parse_duration("12"), Ok(Duration::from_secs(12)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5 minutes").is_err());
+        assert!(parse_duration("m5").is_err());
+        assert!(parse_duration("1.5h").is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(600)), "10m");
+        assert_eq!(format_duration(Duration::from_secs(5_400)), "1h30m");
+        assert_eq!(format_duration(Duration::from_secs(12)), "12s");
+        assert_eq!(format_duration(Duration::from_millis(1_250)), "1s250ms");
+        assert_eq!(format_duration(Duration::ZERO), "0s");
+        for input in ["90s", "1d2h", "250ms"] {
+            assert_eq!(parse_duration(&format_duration(parse_duration(input).unwrap())), parse_duration(input));
+        }
+    }
+
+    #[test]
+    fn test_known_genesis() {
+        let mainnet = "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3".parse().unwrap();
+        assert_eq!(known_genesis(&mainnet), Some("Ethereum Mainnet"));
+        assert_eq!(known_genesis(&B256::ZERO), None);
+    }
+
+    #[test]
+    fn test_known_code_hash() {
+        let create2_factory: alloy_primitives::Bytes = "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3".parse().unwrap();
+        let hash = alloy_primitives::keccak256(&create2_factory);
+        assert_eq!(known_code_hash(&hash), Some("Deterministic Deployment Proxy (CREATE2 factory)"));
+        assert_eq!(known_code_hash(&alloy_primitives::keccak256([])), Some("No code (EOA)"));
+        let safe_proxy: alloy_primitives::Bytes = "0x608060405273ffffffffffffffffffffffffffffffffffffffff600054167fa619486e0000000000000000000000000000000000000000000000000000000060003514156050578060005260206000f35b3660008037600080366000845af43d6000803e60008114156070573d6000fd5b3d6000f3fea2646970667358221220d1429297349653a4918076d650332de1a1068c5f3e07c5c82360c277770b955264736f6c63430007060033".parse().unwrap();
+        assert_eq!(known_code_hash(&alloy_primitives::keccak256(&safe_proxy)), Some("Gnosis Safe Proxy v1.3.0"));
+        let erc1967_proxy: alloy_primitives::Bytes = "0x363d3d373d3d363d7f360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc545af43d6000803e6038573d6000fd5b3d6000f3".parse().unwrap();
+        assert_eq!(known_code_hash(&alloy_primitives::keccak256(&erc1967_proxy)), Some("ERC-1967 Minimal Proxy (Solady LibClone / ERC1967Factory)"));
+        assert_eq!(known_code_hash(&B256::ZERO), None);
+    }
+
+    #[test]
+    fn test_minimal_proxy_target() {
+        let implementation = Address::with_last_byte(0xaa);
+        let clone = [&EIP1167_PREFIX[..], implementation.as_slice(), &EIP1167_SUFFIX[..]].concat();
+        assert_eq!(minimal_proxy_target(&clone), Some(implementation));
+        assert_eq!(minimal_proxy_target(&clone[..44]), None);
+        assert_eq!(minimal_proxy_target(&[]), None);
+    }
+
+    #[test]
+    fn test_nonce_anomalies() {
+        assert!(nonce_anomalies(&[3, 1, 2]).is_empty());
+        assert!(nonce_anomalies(&[]).is_empty());
+        assert_eq!(
+            nonce_anomalies(&[44, 42, 42, 41]),
+            vec![
+                NonceAnomaly::Duplicate { nonce: 42, count: 2 },
+                NonceAnomaly::Missing { nonce: 43 },
+            ]
+        );
+        assert_eq!(
+            NonceAnomaly::Duplicate { nonce: 42, count: 2 }.to_string(),
+            "nonce 42 appears 2 times (replacement)"
+        );
+    }
+
+    #[test]
+    fn test_format_gas_human() {
+        assert_eq!(format_gas_human(999), "999");
+        assert_eq!(format_gas_human(1_000), "1.0K");
+        assert_eq!(format_gas_human(999_999), "1.0M");
+        assert_eq!(format_gas_human(1_000_000), "1.0M");
+        assert_eq!(format_gas_human(12_345_678), "12.3M");
+        assert_eq!(format_gas_human(30_000_000_000), "30.0B");
+        assert_eq!(format_gas(1_234_567, GasFormat::Raw), "1,234,567");
     }
 
     #[test]
@@ -0,0 +1,39 @@
+//! `account` pinned to a block hash, checked against the real binary
+//! talking to a mock node.
+
+mod common;
+
+use common::{block, mock_node, rpc, run};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_account_at_block_hash() {
+    let hash = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+    let address = "0x742d35cc622c1e0532f7fd0e7c0e6f7d8f2b2b6f";
+    let server = mock_node().await;
+    rpc("eth_getBlockByHash", |_| json!({ "result": block("0x10") })).mount(&server).await;
+    rpc("eth_getBlockByNumber", |_| json!({ "result": block("0x10") })).mount(&server).await;
+    // State is only served for the block the hash resolved to
+    for state_method in ["eth_getBalance", "eth_getTransactionCount"] {
+        rpc(state_method, |params| {
+            assert_eq!(params[1], "0x10");
+            json!({ "result": "0x1" })
+        }).mount(&server).await;
+    }
+    rpc("eth_getCode", |_| json!({ "result": "0x" })).mount(&server).await;
+
+    let output = run(&server.uri(), &["account", address, "--block-hash", hash]).await;
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Block: 16"));
+    assert_eq!(run(&server.uri(), &["account", address, "--block-hash", hash, "--block", "16"]).await.status.code(), Some(2));
+
+    // A hash whose height now holds another block was reorged out
+    let reorged = mock_node().await;
+    rpc("eth_getBlockByHash", |_| json!({ "result": block("0x10") })).mount(&reorged).await;
+    rpc("eth_getBlockByNumber", |_| {
+        let mut replacement = block("0x10");
+        replacement["hash"] = json!(format!("0x{}", "ab".repeat(32)));
+        json!({ "result": replacement })
+    }).mount(&reorged).await;
+    assert_eq!(run(&reorged.uri(), &["account", address, "--block-hash", hash]).await.status.code(), Some(3));
+}
@@ -0,0 +1,20 @@
+//! Helpers shared by the integration tests: the crate's mock node from
+//! `src/test_support.rs` and a way to run the real binary against it.
+
+#![allow(dead_code)]
+
+use std::process::Output;
+
+#[path = "../../src/test_support.rs"]
+mod test_support;
+pub use test_support::*;
+
+pub async fn run(rpc_url: &str, args: &[&str]) -> Output {
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_eth_data_extractor"))
+        .arg("--rpc-url")
+        .arg(rpc_url)
+        .args(args)
+        .output()
+        .await
+        .unwrap()
+}
@@ -0,0 +1,93 @@
+//! The exit-code contract documented in the README, checked against the
+//! real binary talking to a mock node.
+
+mod common;
+
+use common::{block, mock_node, rpc, run};
+use serde_json::json;
+use std::process::Stdio;
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn test_success_exits_zero() {
+    let server = mock_node().await;
+    rpc("eth_getBlockByNumber", |_| json!({ "result": block("0x10") })).mount(&server).await;
+
+    assert_eq!(run(&server.uri(), &["block", "16"]).await.status.code(), Some(0));
+}
+
+#[tokio::test]
+async fn test_invalid_input_exits_two() {
+    let server = mock_node().await;
+
+    assert_eq!(run(&server.uri(), &["block", "sixteen"]).await.status.code(), Some(2));
+    // A first retry wait longer than the whole retry window
+    let args = ["--max-retry-secs", "1", "--retry-initial-ms", "5000", "block", "16"];
+    assert_eq!(run(&server.uri(), &args).await.status.code(), Some(2));
+}
+
+#[tokio::test]
+async fn test_not_found_exits_three() {
+    let server = mock_node().await;
+    rpc("eth_getTransactionByHash", |_| json!({ "result": null })).mount(&server).await;
+
+    let hash = format!("0x{}", "00".repeat(32));
+    assert_eq!(run(&server.uri(), &["transaction", &hash]).await.status.code(), Some(3));
+}
+
+#[tokio::test]
+async fn test_unreachable_node_exits_four() {
+    assert_eq!(run("http://127.0.0.1:1", &["block", "16"]).await.status.code(), Some(4));
+}
+
+#[tokio::test]
+async fn test_rpc_error_exits_five() {
+    let server = mock_node().await;
+    rpc("eth_getBlockByNumber", |_| json!({ "error": { "code": -32601, "message": "method not found" } }))
+        .mount(&server)
+        .await;
+
+    assert_eq!(run(&server.uri(), &["block", "16"]).await.status.code(), Some(5));
+}
+
+#[tokio::test]
+async fn test_partial_failure_exits_six() {
+    let server = mock_node().await;
+    rpc("eth_blockNumber", |_| json!({ "result": "0x10" })).mount(&server).await;
+    // Block 15 is missing, block 16 is fine
+    rpc("eth_getBlockByNumber", |params| match params[0].as_str() {
+        Some("0x10") => json!({ "result": block("0x10") }),
+        _ => json!({ "result": null }),
+    })
+    .mount(&server)
+    .await;
+
+    let output = run(&server.uri(), &["latest", "--count", "2"]).await;
+    assert_eq!(output.status.code(), Some(6));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 of 2 failed"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_interrupt_exits_130() {
+    let server = mock_node().await;
+    // A node that never answers in time, so the command is mid-request
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "eth_getBlockByNumber" })))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(60)))
+        .mount(&server)
+        .await;
+
+    let child = tokio::process::Command::new(env!("CARGO_BIN_EXE_eth_data_extractor"))
+        .args(["--rpc-url", &server.uri(), "block", "16"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    unsafe { libc::kill(child.id().unwrap() as i32, libc::SIGINT) };
+    let output = tokio::time::timeout(Duration::from_secs(10), child.wait_with_output()).await.unwrap().unwrap();
+    assert_eq!(output.status.code(), Some(130));
+}
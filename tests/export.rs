@@ -0,0 +1,37 @@
+//! `export` to NDJSON, checked against the real binary talking to a mock
+//! node.
+
+mod common;
+
+use common::{block, mock_node, rpc, run};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_export_keeps_blocks_around_a_failure() {
+    let server = mock_node().await;
+    // Block 15 is missing, its neighbours are fine
+    rpc("eth_getBlockByNumber", |params| match params[0].as_str() {
+        Some("0xf") => json!({ "result": null }),
+        Some(number) => json!({ "result": block(number) }),
+        None => json!({ "result": null }),
+    })
+    .mount(&server)
+    .await;
+
+    let out = std::env::temp_dir().join(format!("eth_data_extractor_export_{}.ndjson", std::process::id()));
+    let output = run(
+        &server.uri(),
+        &["--on-error", "continue", "export", "--from", "14", "--to", "16", "--out", out.to_str().unwrap()],
+    )
+    .await;
+    assert_eq!(output.status.code(), Some(6));
+    let exported: Vec<Value> = std::fs::read_to_string(&out).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(exported.iter().map(|block| block["number"].as_str().unwrap()).collect::<Vec<_>>(), ["14", "16"]);
+    std::fs::remove_file(out).unwrap();
+
+    // Ranges run oldest to newest
+    assert_eq!(run(&server.uri(), &["export", "--from", "16", "--to", "14", "--out", "unused"]).await.status.code(), Some(2));
+}
@@ -0,0 +1,39 @@
+//! `gas` over explicit block ranges, checked against the real binary
+//! talking to a mock node.
+
+mod common;
+
+use common::{block, mock_node, rpc, run};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_gas_over_explicit_range() {
+    let server = mock_node().await;
+    rpc("eth_blockNumber", |_| json!({ "result": "0x10" })).mount(&server).await;
+    rpc("eth_getBlockByNumber", |params| match params[0].as_str() {
+        Some(number) if u64::from_str_radix(&number[2..], 16).unwrap() <= 16 => json!({ "result": block(number) }),
+        _ => json!({ "result": null }),
+    })
+    .mount(&server)
+    .await;
+
+    let output = run(&server.uri(), &["--format", "json", "gas", "--from", "14", "--to", "16"]).await;
+    assert_eq!(output.status.code(), Some(0));
+    let stats: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["blocks_analyzed"], 3);
+
+    assert_eq!(run(&server.uri(), &["gas", "--from", "16", "--to", "14"]).await.status.code(), Some(2));
+    // Past the head is a bad range, not a partial failure
+    let output = run(&server.uri(), &["gas", "--from", "14", "--to", "20"]).await;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("past the latest block 16"));
+    assert_eq!(run(&server.uri(), &["gas", "--from", "14", "--blocks", "5"]).await.status.code(), Some(2));
+}
+
+#[tokio::test]
+async fn test_gas_csv_stdout_rejects_format_csv() {
+    let server = mock_node().await;
+    let output = run(&server.uri(), &["--format", "csv", "gas", "--csv-stdout"]).await;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,30 @@
+//! The library half of the crate, used the way an embedding program would:
+//! a jsonrpsee client it built itself, wrapped in a `RethClient`.
+
+mod common;
+
+use common::{block, rpc};
+use eth_data_extractor::error::RethClientError;
+use eth_data_extractor::rpc::{CacheConfig, RethClient};
+use jsonrpsee::http_client::HttpClientBuilder;
+use serde_json::json;
+use wiremock::MockServer;
+
+#[tokio::test]
+async fn test_embedded_client() {
+    let server = MockServer::start().await;
+    rpc("eth_getBlockByNumber", |params| match params[0].as_str() {
+        Some("0x10") => json!({ "result": block("0x10") }),
+        _ => json!({ "result": null }),
+    })
+    .mount(&server)
+    .await;
+
+    let http = HttpClientBuilder::default().build(server.uri()).unwrap();
+    let client = RethClient::with_client(http, &CacheConfig::default(), 1).unwrap();
+    let header = client.get_block_header_by_number(16).await.unwrap();
+    assert_eq!(header.gas_used.to::<u64>(), 0xe4e1c0);
+
+    let missing = client.get_block_header_by_number(17).await.unwrap_err();
+    assert!(matches!(missing, RethClientError::NotFound(_)));
+}
@@ -0,0 +1,24 @@
+//! `--output-file`, checked against the real binary talking to a mock node.
+
+mod common;
+
+use common::{block, mock_node, rpc, run};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_output_file_takes_stdout() {
+    let server = mock_node().await;
+    rpc("eth_getBlockByNumber", |_| json!({ "result": block("0x10") })).mount(&server).await;
+
+    // A missing parent directory is created
+    let dir = std::env::temp_dir().join(format!("eth_data_extractor_output_{}", std::process::id()));
+    let path = dir.join("reports").join("block.json");
+    let output = run(&server.uri(), &["--format", "json", "--output-file", path.to_str().unwrap(), "block", "16"]).await;
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Output written to"));
+
+    let written: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written["number"], "16");
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,19 @@
+//! `--quiet`, checked against the real binary talking to a mock node.
+
+mod common;
+
+use common::{block, mock_node, rpc, run};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_quiet_keeps_only_data() {
+    let server = mock_node().await;
+    rpc("eth_getBlockByNumber", |_| json!({ "result": block("0x10") })).mount(&server).await;
+
+    let output = run(&server.uri(), &["--quiet", "block", "16"]).await;
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Block Number"));
+    assert!(!stdout.contains("Block Information"));
+    assert!(!String::from_utf8(output.stderr).unwrap().contains("Connected"));
+}
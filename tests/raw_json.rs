@@ -0,0 +1,31 @@
+//! `--raw-json`, checked against the real binary talking to a mock node.
+
+mod common;
+
+use common::{block, mock_node, rpc, run};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_raw_json_skips_parsing() {
+    let server = mock_node().await;
+    rpc("eth_getBlockByNumber", |params| {
+        assert_eq!(params, &json!(["0x10", true]));
+        let mut block = block("0x10");
+        block["gasUsed"] = json!("lots");
+        block["l1BatchNumber"] = json!("0x5");
+        json!({ "result": block })
+    })
+    .mount(&server)
+    .await;
+    rpc("eth_getTransactionReceipt", |_| json!({ "result": null })).mount(&server).await;
+
+    assert_ne!(run(&server.uri(), &["block", "16"]).await.status.code(), Some(0));
+    let output = run(&server.uri(), &["block", "16", "--raw-json"]).await;
+    assert_eq!(output.status.code(), Some(0));
+    let raw: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(raw["gasUsed"], "lots");
+    assert_eq!(raw["l1BatchNumber"], "0x5");
+
+    let hash = format!("0x{}", "ab".repeat(32));
+    assert_eq!(run(&server.uri(), &["receipt", &hash, "--raw-json"]).await.status.code(), Some(3));
+}